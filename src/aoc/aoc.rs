@@ -0,0 +1,1020 @@
+use common::answers::{self, ExpectedAnswers, Verdict};
+use common::bench::{self, Report};
+use common::client::{self, Client, Fetched};
+use common::day01;
+use common::day02::{self, policy};
+use common::examples::{self, Examples};
+use common::generate;
+use common::leaderboard::{self, Leaderboard};
+use common::pool;
+use common::record::{self, ResultRecord};
+use common::scaffold;
+use common::solution::{parse_day_selection, Answer, Runnable, TimedRun, FIRST_YEAR};
+use common::submit::{self, Guess, GuessLog, Outcome};
+use common::{InputResolver, InputSource};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+
+fn usage() -> ! {
+    eprintln!("Usage: aoc run <day|first-last|all>... [--input <file|->] [--input-dir <dir>]");
+    eprintln!("                 [--jobs <n>] [--json]");
+    eprintln!("       aoc verify [<day|first-last|all>...] [--input-dir <dir>] [--jobs <n>]");
+    eprintln!("                 [--answers <file>]");
+    eprintln!("       aoc fetch <day|first-last|all>... [--cache-dir <dir>] [--session <token>]");
+    eprintln!("                 [--base-url <url>]");
+    eprintln!("       aoc submit <day> <part> [--input <file|->] [--input-dir <dir>]");
+    eprintln!("                 [--cache-dir <dir>] [--session <token>] [--base-url <url>]");
+    eprintln!("       aoc extract <day> <page.html> [--examples <file>]");
+    eprintln!("       aoc new <year> <day>");
+    eprintln!("       aoc generate <day> [--size <n>] [--seed <n>] [--output <file>]");
+    eprintln!("       aoc check <day> <file|->");
+    eprintln!("       aoc audit [--input <file|->] [--input-dir <dir>] [--target <n>]");
+    eprintln!("       aoc passwords <policy> [--input <file|->] [--input-dir <dir>]");
+    eprintln!("                 [--explain]");
+    eprintln!("       aoc leaderboard <id|file|-> [--cache-dir <dir>] [--session <token>]");
+    eprintln!("                 [--base-url <url>]");
+    eprintln!("       aoc bench [<day|first-last|all>...] [--input-dir <dir>] [--runs <n>]");
+    eprintln!("                 [--report <file>] [--baseline <file>] [--threshold <percent>]");
+    eprintln!();
+    eprintln!(
+        "  --year <year>          Year of the selected days (defaults to {})",
+        FIRST_YEAR
+    );
+    eprintln!("  --input <file|->       Read the input from a file or stdin (single day only)");
+    eprintln!("  --input-dir <dir>      Look for dayNN.txt or dayNN/input.txt in <dir>, or in");
+    eprintln!(
+        "                         <dir>/<year> for other years than {}",
+        FIRST_YEAR
+    );
+    eprintln!(
+        "                         (defaults to ${})",
+        common::input::INPUT_DIR_VAR
+    );
+    eprintln!("  --cache-dir <dir>      Directory of downloaded inputs and submitted answers");
+    eprintln!(
+        "                         (defaults to ${} or ~/.cache/aoc)",
+        common::input::CACHE_DIR_VAR
+    );
+    eprintln!(
+        "  --session <token>      Session cookie of your login (defaults to ${})",
+        client::SESSION_VAR
+    );
+    eprintln!(
+        "  --base-url <url>       Server to talk to (defaults to ${} or {})",
+        client::BASE_URL_VAR,
+        client::DEFAULT_BASE_URL
+    );
+    eprintln!("  --jobs <n>             Number of days solved in parallel (defaults to the CPUs)");
+    eprintln!("  --json                 Print one JSON record per part instead of text");
+    eprintln!("  --answers <file>       Answers to compare against (defaults to answers.toml)");
+    eprintln!("  --examples <file>      Example answers, fixtures go to src/dayNN next to it");
+    eprintln!("                         (defaults to examples.toml)");
+    eprintln!("  --runs <n>             Number of timed runs per day (defaults to 3)");
+    eprintln!("  --report <file>        Write the timings as CSV, or as JSON for *.json files");
+    eprintln!("  --baseline <file>      Compare against a report of an earlier run");
+    eprintln!("  --threshold <percent>  Slowdown flagged as regression (defaults to 20)");
+    eprintln!("  --size <n>             Size of the generated input, like its number of lines");
+    eprintln!("  --seed <n>             Seed of the generated input (defaults to a random one)");
+    eprintln!("  --output <file>        Write the generated input to <file> instead of stdout");
+    eprintln!(
+        "  --target <n>           Sum the audited expenses have to reach (defaults to {})",
+        day01::TARGET
+    );
+    eprintln!("  --explain              List every invalid password and why it fails its policy");
+    process::exit(2);
+}
+
+struct Options {
+    year: u32,
+    days: Vec<u32>,
+    resolver: InputResolver,
+    explicit_input: Option<String>,
+    answers: Option<String>,
+    examples: Option<String>,
+    session: Option<String>,
+    base_url: Option<String>,
+    jobs: usize,
+    json: bool,
+    runs: usize,
+    report: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+    size: Option<usize>,
+    seed: Option<u64>,
+    output: Option<String>,
+    target: i64,
+    explain: bool,
+}
+
+/// Days of `year` that have a solution.
+fn available_days(solutions: &[Box<dyn Runnable>], year: u32) -> Vec<u32> {
+    solutions
+        .iter()
+        .filter(|s| s.year() == year)
+        .map(|s| s.day())
+        .collect()
+}
+
+fn find_solution(solutions: &[Box<dyn Runnable>], year: u32, day: u32) -> &dyn Runnable {
+    solutions
+        .iter()
+        .find(|s| s.year() == year && s.day() == day)
+        .unwrap()
+        .as_ref()
+}
+
+/// Parses the days and flags following a command, `allowed` lists the flags the command accepts.
+/// `--year` is accepted by every command, the days are selected among the solutions of that year.
+fn parse_options(args: &[String], solutions: &[Box<dyn Runnable>], allowed: &[&str]) -> Options {
+    let mut options = Options {
+        year: FIRST_YEAR,
+        days: Vec::new(),
+        resolver: InputResolver::from_env(),
+        explicit_input: None,
+        answers: None,
+        examples: None,
+        session: None,
+        base_url: None,
+        jobs: pool::default_jobs(),
+        json: false,
+        runs: 3,
+        report: None,
+        baseline: None,
+        threshold: 0.2,
+        size: None,
+        seed: None,
+        output: None,
+        target: day01::TARGET,
+        explain: false,
+    };
+
+    let mut selections = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("--") && arg != "--year" && !allowed.contains(&arg.as_str()) {
+            eprintln!("{} can't be used with this command", arg);
+            usage();
+        }
+        let mut value = || iter.next().unwrap_or_else(|| usage()).clone();
+
+        match arg.as_str() {
+            "--year" => {
+                options.year = match value().parse::<u32>() {
+                    Ok(y) => y,
+                    _ => usage(),
+                }
+            }
+            "--input" => options.explicit_input = Some(value()),
+            "--input-dir" => options.resolver = options.resolver.with_input_dir(value()),
+            "--cache-dir" => options.resolver = options.resolver.with_cache_dir(value()),
+            "--session" => options.session = Some(value()),
+            "--base-url" => options.base_url = Some(value()),
+            "--answers" => options.answers = Some(value()),
+            "--examples" => options.examples = Some(value()),
+            "--jobs" => {
+                options.jobs = match value().parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => usage(),
+                }
+            }
+            "--json" => options.json = true,
+            "--runs" => {
+                options.runs = match value().parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => usage(),
+                }
+            }
+            "--report" => options.report = Some(value()),
+            "--baseline" => options.baseline = Some(value()),
+            "--threshold" => {
+                options.threshold = match value().parse::<f64>() {
+                    Ok(p) if p >= 0.0 => p / 100.0,
+                    _ => usage(),
+                }
+            }
+            "--size" => {
+                options.size = match value().parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => usage(),
+                }
+            }
+            "--seed" => {
+                options.seed = match value().parse::<u64>() {
+                    Ok(n) => Some(n),
+                    _ => usage(),
+                }
+            }
+            "--output" => options.output = Some(value()),
+            "--target" => {
+                options.target = match value().parse::<i64>() {
+                    Ok(n) => n,
+                    _ => usage(),
+                }
+            }
+            "--explain" => options.explain = true,
+            s => selections.push(s),
+        }
+    }
+
+    let available = available_days(solutions, options.year);
+    for s in selections {
+        match parse_day_selection(s, &available) {
+            Ok(mut d) => options.days.append(&mut d),
+            Err(e) => {
+                eprintln!("{} of {}", e, options.year);
+                usage();
+            }
+        }
+    }
+
+    if options.explicit_input.is_some() && options.days.len() > 1 {
+        eprintln!("--input can only be used when running a single day");
+        usage();
+    }
+
+    options
+}
+
+fn resolve_input(solution: &dyn Runnable, options: &Options) -> Result<InputSource, String> {
+    options
+        .resolver
+        .resolve(
+            solution.year(),
+            solution.day(),
+            options.explicit_input.as_deref(),
+        )
+        .map_err(|e| e.to_string())
+}
+
+fn solve_day(solution: &dyn Runnable, options: &Options) -> Result<(Answer, Answer), String> {
+    let source = resolve_input(solution, options)?;
+    solution.run(&source).map_err(|e| e.to_string())
+}
+
+/// Applies `f` to the selected days on the worker pool and hands the results to `done` in day
+/// order. A panicking day is reported as an error.
+fn for_each_day<R, F, D>(solutions: &[Box<dyn Runnable>], options: &Options, f: F, mut done: D)
+where
+    R: Send,
+    F: Fn(&dyn Runnable) -> Result<R, String> + Sync,
+    D: FnMut(u32, Result<R, String>),
+{
+    pool::map_ordered(
+        options.days.clone(),
+        options.jobs,
+        |day| f(find_solution(solutions, options.year, day)),
+        |i, result| {
+            let result = result.unwrap_or_else(|e| Err(format!("panicked: {}", e)));
+            done(options.days[i], result)
+        },
+    );
+}
+
+fn time_day(solution: &dyn Runnable, options: &Options) -> Result<Vec<TimedRun>, String> {
+    let source = resolve_input(solution, options)?;
+    (0..options.runs)
+        .map(|_| solution.run_timed(&source).map_err(|e| e.to_string()))
+        .collect()
+}
+
+fn join_days(days: &[u32]) -> String {
+    days.iter()
+        .map(|d| format!("{:02}", d))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn run(args: &[String]) {
+    let solutions = common::registry();
+    let options = parse_options(
+        args,
+        &solutions,
+        &["--input", "--input-dir", "--cache-dir", "--jobs", "--json"],
+    );
+
+    if options.days.is_empty() {
+        usage();
+    }
+
+    let mut failed = Vec::<u32>::new();
+    let solve = |solution: &dyn Runnable| {
+        resolve_input(solution, &options)
+            .and_then(|source| solution.run_timed(&source).map_err(|e| e.to_string()))
+    };
+    for_each_day(&solutions, &options, solve, |day, result| match result {
+        Ok(run) if options.json => {
+            for record in ResultRecord::from_run(options.year, day, &run) {
+                println!("{}", record.to_json());
+            }
+        }
+        Ok(run) => {
+            println!("Day {:02} part 1: {}", day, run.answers.0);
+            println!("Day {:02} part 2: {}", day, run.answers.1);
+        }
+        Err(e) => {
+            if options.json {
+                println!("{}", record::error_to_json(options.year, day, &e));
+            } else {
+                eprintln!("Day {:02} failed: {}", day, e);
+            }
+            failed.push(day);
+        }
+    });
+
+    if !failed.is_empty() {
+        eprintln!("{} day(s) failed: {}", failed.len(), join_days(&failed));
+        process::exit(1);
+    }
+}
+
+fn verify(args: &[String]) {
+    let solutions = common::registry();
+    let mut options = parse_options(
+        args,
+        &solutions,
+        &["--input-dir", "--cache-dir", "--jobs", "--answers"],
+    );
+
+    if options.days.is_empty() {
+        options.days = available_days(&solutions, options.year);
+    }
+
+    let source = match &options.answers {
+        Some(path) => InputSource::from_arg(path),
+        None => InputSource::File(answers::default_path()),
+    };
+    let expected = ExpectedAnswers::parse(&source).unwrap_or_else(|e| {
+        eprintln!("Can't read answers: {}", e);
+        process::exit(2);
+    });
+
+    println!(
+        "{:<4} {:<5} {:<8} {:<20} Actual",
+        "Day", "Part", "Result", "Expected"
+    );
+
+    let mut failed = Vec::<u32>::new();
+    let mut counts = [0; 4];
+    let solve = |solution: &dyn Runnable| solve_day(solution, &options);
+    for_each_day(&solutions, &options, solve, |day, result| {
+        let actual = match result {
+            Ok((part1, part2)) => vec![Ok(part1), Ok(part2)],
+            Err(e) => {
+                eprintln!("Day {:02} failed: {}", day, e);
+                vec![Err(()), Err(())]
+            }
+        };
+
+        for (part, actual) in (1..=2).zip(actual) {
+            let verdict = match &actual {
+                Ok(answer) => expected.check(options.year, day, part, answer),
+                Err(_) => Verdict::Error,
+            };
+            counts[verdict as usize] += 1;
+            let passed = verdict == Verdict::Pass || verdict == Verdict::Missing;
+            if !passed && !failed.contains(&day) {
+                failed.push(day);
+            }
+
+            println!(
+                "{:<4} {:<5} {:<8} {:<20} {}",
+                format!("{:02}", day),
+                part,
+                verdict,
+                expected
+                    .get(options.year, day, part)
+                    .map_or(String::from("-"), |a| a.to_string()),
+                actual.map_or(String::from("-"), |a| a.to_string())
+            );
+        }
+    });
+
+    println!();
+    println!(
+        "{} passed, {} failed, {} missing, {} errors",
+        counts[Verdict::Pass as usize],
+        counts[Verdict::Fail as usize],
+        counts[Verdict::Missing as usize],
+        counts[Verdict::Error as usize]
+    );
+
+    if !failed.is_empty() {
+        eprintln!(
+            "{} day(s) failed verification: {}",
+            failed.len(),
+            join_days(&failed)
+        );
+        process::exit(1);
+    }
+}
+
+fn millis(d: Duration) -> String {
+    format!("{:.3} ms", d.as_secs_f64() * 1000.0)
+}
+
+fn bench(args: &[String]) {
+    let solutions = common::registry();
+    let allowed = [
+        "--input-dir",
+        "--cache-dir",
+        "--runs",
+        "--report",
+        "--baseline",
+        "--threshold",
+    ];
+    let mut options = parse_options(args, &solutions, &allowed);
+
+    if options.days.is_empty() {
+        options.days = available_days(&solutions, options.year);
+    }
+
+    let baseline = options.baseline.as_ref().map(|path| {
+        Report::parse(&InputSource::from_arg(path)).unwrap_or_else(|e| {
+            eprintln!("Can't read baseline: {}", e);
+            process::exit(2);
+        })
+    });
+
+    println!(
+        "{:<4} {:<6} {:>12} {:>12} {:>12} {:>12} Change",
+        "Day", "Phase", "Median", "Min", "Max", "Baseline"
+    );
+
+    let mut report = Report::new();
+    let mut failed = Vec::<u32>::new();
+    let mut regressions = 0;
+    for day in &options.days {
+        let solution = find_solution(&solutions, options.year, *day);
+        let runs = match time_day(solution, &options) {
+            Ok(runs) => runs,
+            Err(e) => {
+                eprintln!("Day {:02} failed: {}", day, e);
+                failed.push(*day);
+                continue;
+            }
+        };
+
+        let mut measurements = bench::measure(options.year, *day, &runs);
+        for m in &measurements {
+            let current = Report {
+                measurements: vec![m.clone()],
+            };
+            let comparison = baseline
+                .as_ref()
+                .and_then(|b| bench::compare(b, &current).pop());
+            let (baseline_time, change) = match &comparison {
+                Some(c) => {
+                    let flag = if c.is_regression(options.threshold) {
+                        regressions += 1;
+                        "  REGRESSION"
+                    } else {
+                        ""
+                    };
+                    (
+                        millis(c.baseline),
+                        format!("{:+.1}%{}", c.change() * 100.0, flag),
+                    )
+                }
+                None => (String::from("-"), String::from("-")),
+            };
+
+            println!(
+                "{:<4} {:<6} {:>12} {:>12} {:>12} {:>12} {}",
+                format!("{:02}", day),
+                m.phase,
+                millis(m.median),
+                millis(m.min),
+                millis(m.max),
+                baseline_time,
+                change
+            );
+        }
+
+        report.measurements.append(&mut measurements);
+    }
+
+    if let Some(path) = &options.report {
+        let content = if path.ends_with(".json") {
+            report.to_json()
+        } else {
+            report.to_csv()
+        };
+        if let Err(e) = fs::write(path, content) {
+            eprintln!("Can't write report to {}: {}", path, e);
+            process::exit(2);
+        }
+    }
+
+    if !failed.is_empty() {
+        eprintln!("{} day(s) failed: {}", failed.len(), join_days(&failed));
+    }
+    if regressions > 0 {
+        eprintln!(
+            "{} phase(s) more than {}% slower than the baseline",
+            regressions,
+            options.threshold * 100.0
+        );
+    }
+    if !failed.is_empty() || regressions > 0 {
+        process::exit(1);
+    }
+}
+
+fn fetch(args: &[String]) {
+    let allowed = ["--cache-dir", "--session", "--base-url"];
+    let options = parse_options(args, &common::registry(), &allowed);
+
+    if options.days.is_empty() {
+        usage();
+    }
+
+    let client = Client::from_env(options.base_url.as_deref(), options.session.as_deref())
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(2);
+        });
+
+    let mut failed = Vec::<u32>::new();
+    for day in &options.days {
+        match client::fetch_input(&client, &options.resolver, options.year, *day) {
+            Ok(Fetched::Cached(path)) => {
+                println!("Day {:02} already cached at {}", day, path.display())
+            }
+            Ok(Fetched::Downloaded(path)) => {
+                println!("Day {:02} downloaded to {}", day, path.display())
+            }
+            Err(e) => {
+                eprintln!("Day {:02} failed: {}", day, e);
+                failed.push(*day);
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        eprintln!("{} day(s) failed: {}", failed.len(), join_days(&failed));
+        process::exit(1);
+    }
+}
+
+fn submit(args: &[String]) {
+    let solutions = common::registry();
+    if args.len() < 2 {
+        usage();
+    }
+    let part = match args[1].as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => {
+            eprintln!("part must be 1 or 2, got {}", args[1]);
+            usage();
+        }
+    };
+    let allowed = [
+        "--input",
+        "--input-dir",
+        "--cache-dir",
+        "--session",
+        "--base-url",
+    ];
+    let options = parse_options(&args[2..], &solutions, &allowed);
+    if !options.days.is_empty() {
+        usage();
+    }
+    let year = options.year;
+    let day = match args[0].parse::<u32>() {
+        Ok(d) if available_days(&solutions, year).contains(&d) => d,
+        _ => {
+            eprintln!("no solution available for day {} of {}", args[0], year);
+            usage();
+        }
+    };
+
+    let fail = |message: String| -> ! {
+        eprintln!("Day {:02} part {} failed: {}", day, part, message);
+        process::exit(1);
+    };
+
+    let log_path = match options.resolver.cache_dir() {
+        Some(dir) => dir.join(submit::LOG_FILE),
+        None => fail(String::from(
+            "no cache directory to keep submitted answers in",
+        )),
+    };
+    let mut log = GuessLog::open(&log_path).unwrap_or_else(|e| fail(e.to_string()));
+
+    let solution = find_solution(&solutions, year, day);
+    let (part1, part2) = solve_day(solution, &options).unwrap_or_else(|e| fail(e));
+    let answer = match if part == 1 { part1 } else { part2 } {
+        Answer::Empty => fail(String::from("there is no answer to submit")),
+        a => a.to_string(),
+    };
+
+    if let Some(outcome) = log.known_outcome(year, day, part, &answer) {
+        println!(
+            "Day {:02} part {}: {} was not submitted, it is known to be {}",
+            day, part, answer, outcome
+        );
+        if outcome != Outcome::Correct {
+            process::exit(1);
+        }
+        return;
+    }
+    if let Some(wait) = log.wait_time(submit::now()) {
+        fail(format!(
+            "submitted too recently, try again in {}s",
+            wait.as_secs()
+        ));
+    }
+
+    let client = Client::from_env(options.base_url.as_deref(), options.session.as_deref())
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(2);
+        });
+    let outcome = client
+        .submit(year, day, part, &answer)
+        .unwrap_or_else(|e| fail(e.to_string()));
+    let guess = Guess {
+        year,
+        day,
+        part,
+        answer: answer.clone(),
+        outcome,
+        time: submit::now(),
+    };
+    if let Err(e) = log.record(guess) {
+        eprintln!(
+            "could not record the answer in {}: {}",
+            log_path.display(),
+            e
+        );
+    }
+
+    println!("Day {:02} part {}: {} is {}", day, part, answer, outcome);
+    if outcome != Outcome::Correct {
+        process::exit(1);
+    }
+}
+
+fn extract(args: &[String]) {
+    if args.len() < 2 {
+        usage();
+    }
+    let day = match args[0].parse::<u32>() {
+        Ok(d) if (1..=25).contains(&d) => d,
+        _ => {
+            eprintln!("invalid day: {}", args[0]);
+            usage();
+        }
+    };
+    let options = parse_options(&args[2..], &common::registry(), &["--examples"]);
+    if !options.days.is_empty() {
+        usage();
+    }
+
+    let fail = |message: String| -> ! {
+        eprintln!("Day {:02} failed: {}", day, message);
+        process::exit(1);
+    };
+
+    let page = fs::read_to_string(&args[1])
+        .unwrap_or_else(|e| fail(format!("could not read {}: {}", args[1], e)));
+    let extracted = examples::extract(&page);
+    if extracted.answers.is_empty() {
+        fail(format!("no example answers found in {}", args[1]));
+    }
+
+    let path = options
+        .examples
+        .map_or_else(examples::default_path, PathBuf::from);
+    let root = path.parent().map_or_else(PathBuf::new, |p| p.to_path_buf());
+    let mut examples = Examples::open(&path).unwrap_or_else(|e| fail(e.to_string()));
+    let created = examples::save(&root, options.year, day, &extracted, &mut examples)
+        .unwrap_or_else(|e| fail(e.to_string()));
+    fs::write(&path, examples.to_toml())
+        .unwrap_or_else(|e| fail(format!("could not write {}: {}", path.display(), e)));
+
+    for fixture in &created {
+        println!("Created {}", fixture.display());
+    }
+    for answer in &extracted.answers {
+        println!(
+            "Part {}: {} for code block {} of {}",
+            answer.part,
+            answer.answer,
+            answer.block + 1,
+            extracted.blocks.len()
+        );
+    }
+    println!("Answers recorded in {}", path.display());
+}
+
+fn new(args: &[String]) {
+    let (year, day) = match args {
+        [year, day] => match (year.parse::<u32>(), day.parse::<u32>()) {
+            (Ok(year), Ok(day)) => (year, day),
+            _ => usage(),
+        },
+        _ => usage(),
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    match scaffold::scaffold(root, year, day) {
+        Ok(created) => {
+            for path in created {
+                println!("Created {}", path.display());
+            }
+            println!("Day {:02} registered in src/common/common.rs", day);
+        }
+        Err(e) => {
+            eprintln!("Day {:02} failed: {}", day, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn generate(args: &[String]) {
+    if args.is_empty() {
+        usage();
+    }
+    let options = parse_options(
+        &args[1..],
+        &common::registry(),
+        &["--size", "--seed", "--output"],
+    );
+    if !options.days.is_empty() {
+        usage();
+    }
+    let year = options.year;
+    let generators = common::generators();
+    let generator = match args[0].parse::<u32>() {
+        Ok(d) => generators.iter().find(|g| g.year() == year && g.day() == d),
+        _ => None,
+    };
+    let generator = match generator {
+        Some(g) => g,
+        None => {
+            eprintln!("no generator available for day {} of {}", args[0], year);
+            usage();
+        }
+    };
+    let day = generator.day();
+
+    let size = options.size.unwrap_or_else(|| generator.default_size());
+    let seed = options.seed.unwrap_or_else(generate::random_seed);
+    let generated = generator.generate(&mut generate::rng(seed), size);
+
+    match &options.output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, &generated.input) {
+                eprintln!("Day {:02} failed: could not write {}: {}", day, path, e);
+                process::exit(1);
+            }
+            eprintln!("Created {}", path);
+        }
+        None => print!("{}", generated.input),
+    }
+    eprintln!("Day {:02} with size {} and seed {}", day, size, seed);
+    for part in 1..=2 {
+        match generated.answer(part) {
+            Some(answer) => eprintln!("Part {}: {}", part, answer),
+            None => eprintln!("Part {}: unknown", part),
+        }
+    }
+}
+
+fn check(args: &[String]) {
+    if args.len() < 2 {
+        usage();
+    }
+    let options = parse_options(&args[2..], &common::registry(), &[]);
+    if !options.days.is_empty() {
+        usage();
+    }
+    let year = options.year;
+    let checkers = common::checkers();
+    let checker = match args[0].parse::<u32>() {
+        Ok(d) => checkers.iter().find(|c| c.year() == year && c.day() == d),
+        _ => None,
+    };
+    let checker = match checker {
+        Some(c) => c,
+        None => {
+            eprintln!("no checker available for day {} of {}", args[0], year);
+            usage();
+        }
+    };
+    let day = checker.day();
+
+    let source = InputSource::from_arg(&args[1]);
+    let input = match source.read_to_string() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {:02} failed: could not read {}: {}", day, source, e);
+            process::exit(1);
+        }
+    };
+
+    let problems = checker.check(&input);
+    for problem in &problems {
+        println!("{}:{}", source, problem);
+        println!("    fix: {}", problem.fix);
+    }
+    match problems.len() {
+        0 => println!("Day {:02}: {} is well formed", day, source),
+        n => {
+            println!("Day {:02}: {} problem(s) in {}", day, n, source);
+            process::exit(1);
+        }
+    }
+}
+
+fn audit(args: &[String]) {
+    let options = parse_options(
+        args,
+        &common::registry(),
+        &["--input", "--input-dir", "--cache-dir", "--target"],
+    );
+    if !options.days.is_empty() {
+        usage();
+    }
+
+    let fail = |message: String| -> ! {
+        eprintln!("Audit failed: {}", message);
+        process::exit(1);
+    };
+    let source = options
+        .resolver
+        .resolve(FIRST_YEAR, 1, options.explicit_input.as_deref())
+        .unwrap_or_else(|e| fail(e.to_string()));
+    let audit = day01::audit_file(&source, options.target).unwrap_or_else(|e| fail(e.to_string()));
+
+    println!("Expense report {}", source);
+    print!("{}", audit);
+}
+
+fn passwords(args: &[String]) {
+    if args.is_empty() {
+        usage();
+    }
+    let kind = policy::kind(&args[0]).unwrap_or_else(|| {
+        eprintln!("Unknown policy {:?}, the policies are:", args[0]);
+        for kind in policy::kinds() {
+            eprintln!("  {:<10} {}", kind.name(), kind.description());
+        }
+        process::exit(2);
+    });
+    let options = parse_options(
+        &args[1..],
+        &common::registry(),
+        &["--input", "--input-dir", "--cache-dir", "--explain"],
+    );
+    if !options.days.is_empty() {
+        usage();
+    }
+
+    let fail = |message: String| -> ! {
+        eprintln!("Password check failed: {}", message);
+        process::exit(1);
+    };
+    let source = options
+        .resolver
+        .resolve(FIRST_YEAR, 2, options.explicit_input.as_deref())
+        .unwrap_or_else(|e| fail(e.to_string()));
+    let entries = common::try_parse_file_linewise(&source, day02::parse_line)
+        .unwrap_or_else(|e| fail(e.to_string()));
+    let report = day02::report(&entries, kind.as_ref());
+
+    println!("Password database {}", source);
+    if options.explain {
+        print!("{:#}", report);
+    } else {
+        print!("{}", report);
+    }
+}
+
+fn leaderboard(args: &[String]) {
+    if args.is_empty() {
+        usage();
+    }
+    let allowed = ["--cache-dir", "--session", "--base-url"];
+    let options = parse_options(&args[1..], &common::registry(), &allowed);
+    if !options.days.is_empty() {
+        usage();
+    }
+
+    let fail = |message: String| -> ! {
+        eprintln!("Leaderboard failed: {}", message);
+        process::exit(1);
+    };
+
+    // A number is the id of a leaderboard, unless there is a file of that name
+    let source = match args[0].parse::<u64>() {
+        Ok(id) if !Path::new(&args[0]).exists() => {
+            let client = Client::from_env(options.base_url.as_deref(), options.session.as_deref())
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                });
+            match client::fetch_leaderboard(&client, &options.resolver, options.year, id) {
+                Ok(Fetched::Cached(path)) | Ok(Fetched::Downloaded(path)) => {
+                    InputSource::File(path)
+                }
+                Err(e) => fail(e.to_string()),
+            }
+        }
+        _ => InputSource::from_arg(&args[0]),
+    };
+    let board = Leaderboard::parse(&source).unwrap_or_else(|e| fail(e.to_string()));
+
+    let ranking = board.ranking();
+    let width = ranking
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Member".len());
+    println!(
+        "Leaderboard of {}, {} members",
+        board.event,
+        board.members.len()
+    );
+    println!();
+    println!("Rank  Score  Stars  Member");
+    for (i, member) in ranking.iter().enumerate() {
+        println!(
+            "{:>4}  {:>5}  {:>5}  {}",
+            i + 1,
+            member.local_score,
+            member.stars,
+            member.display_name()
+        );
+    }
+
+    let scores = board.day_scores();
+    let totals = board.totals();
+    for (day, (first, second)) in &totals.stars_per_day {
+        let unlock = leaderboard::unlock_time(board.event, *day);
+        let mut solvers = board
+            .members
+            .iter()
+            .filter_map(|m| m.days.get(day).map(|stars| (stars, m)))
+            .collect::<Vec<_>>();
+        solvers.sort_by_key(|(stars, m)| (stars.part1, m.id));
+
+        println!();
+        println!(
+            "Day {:02}: {} first and {} second star(s)",
+            day, first, second
+        );
+        println!(
+            "  {:<width$}  {:>11}  {:>12}  Points",
+            "Member",
+            "Part 1",
+            "Part 2 delta",
+            width = width
+        );
+        for (stars, member) in solvers {
+            let part2 = match stars.delta() {
+                Some(delta) => format!("+{}", leaderboard::format_duration(delta)),
+                None => String::from("-"),
+            };
+            println!(
+                "  {:<width$}  {:>11}  {:>12}  {:>6}",
+                member.display_name(),
+                leaderboard::format_duration(stars.part1.saturating_sub(unlock)),
+                part2,
+                scores[&member.id].get(day).unwrap_or(&0),
+                width = width
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "Team: {} members, {} stars, {} points",
+        totals.members, totals.stars, totals.local_score
+    );
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("extract") => extract(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("audit") => audit(&args[1..]),
+        Some("passwords") => passwords(&args[1..]),
+        Some("leaderboard") => leaderboard(&args[1..]),
+        _ => usage(),
+    }
+}
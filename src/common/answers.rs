@@ -0,0 +1,286 @@
+use crate::error::{Location, ParseError};
+use crate::input::InputSource;
+use crate::solution::{Answer, FIRST_YEAR};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Location of the answers recorded for the inputs checked into this repository.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+/// Known correct answers, keyed by year, day and part.
+///
+/// The answers are read from a TOML file with one table per day. Days of the first year are at
+/// the top level, days of later years in a table named after the year:
+///
+/// ```toml
+/// [day01]
+/// part1 = 514579
+/// part2 = 241861950
+///
+/// [2021.day01]
+/// part1 = 1521
+/// ```
+///
+/// Text answers are written as strings. Parts without an answer (like the second part of the
+/// last day) are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    answers: BTreeMap<(u32, u32, u32), Answer>,
+}
+
+/// Reads an answer written as a number or a string.
+pub(crate) fn answer_from_toml(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(n) => Some(Answer::from(*n)),
+        toml::Value::String(s) => Some(Answer::from(s.as_str())),
+        _ => None,
+    }
+}
+
+/// Writes an answer the way `answer_from_toml` reads it.
+pub(crate) fn answer_to_toml(answer: &Answer) -> Option<toml::Value> {
+    match answer {
+        Answer::Number(n) => Some(
+            i64::try_from(*n)
+                .map_or_else(|_| toml::Value::String(n.to_string()), toml::Value::Integer),
+        ),
+        Answer::Text(s) => Some(toml::Value::String(s.clone())),
+        Answer::Empty => None,
+    }
+}
+
+/// Entries of a TOML document with a table per day, keyed like `day01` for the first year and
+/// like `2021.day01` for later ones. Returns year, day, full key and value of every day.
+pub(crate) fn year_days(
+    table: &toml::value::Table,
+) -> Result<Vec<(u32, u32, String, &toml::Value)>, String> {
+    let mut ret = Vec::new();
+    for (key, value) in table {
+        let days = match key.parse::<u32>() {
+            Ok(year) => value
+                .as_table()
+                .ok_or_else(|| format!("{}: expected a table of days", key))?
+                .iter()
+                .map(|(day_key, value)| (year, format!("{}.{}", key, day_key), day_key, value))
+                .collect(),
+            Err(_) => vec![(FIRST_YEAR, key.clone(), key, value)],
+        };
+        for (year, full_key, day_key, value) in days {
+            let day = parse_key(day_key, "day", 25)
+                .ok_or_else(|| format!("{}: expected a key like day01", full_key))?;
+            ret.push((year, day, full_key, value));
+        }
+    }
+    Ok(ret)
+}
+
+/// Table of the days of `year` inside `table`, laid out as read by `year_days`.
+pub(crate) fn year_table(table: &mut toml::value::Table, year: u32) -> &mut toml::value::Table {
+    if year == FIRST_YEAR {
+        return table;
+    }
+    table
+        .entry(year.to_string())
+        .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+        .as_table_mut()
+        .unwrap()
+}
+
+pub(crate) fn parse_key(key: &str, prefix: &str, max: u32) -> Option<u32> {
+    if !key.starts_with(prefix) {
+        return None;
+    }
+    key[prefix.len()..]
+        .parse::<u32>()
+        .ok()
+        .filter(|n| *n >= 1 && *n <= max)
+}
+
+impl ExpectedAnswers {
+    pub fn new() -> ExpectedAnswers {
+        ExpectedAnswers {
+            answers: BTreeMap::new(),
+        }
+    }
+
+    pub fn parse(source: &InputSource) -> Result<ExpectedAnswers, ParseError> {
+        let text = source
+            .read_to_string()
+            .map_err(|e| ParseError::new(source, Location::Input, "", e))?;
+        ExpectedAnswers::from_toml(source, &text)
+    }
+
+    /// Parses the content of an answers file, `source` is only used for error messages.
+    pub fn from_toml(source: &InputSource, text: &str) -> Result<ExpectedAnswers, ParseError> {
+        let error = |message: String| ParseError::new(source, Location::Input, "", message);
+
+        let value = text
+            .parse::<toml::Value>()
+            .map_err(|e| match e.line_col() {
+                Some((line, _)) => ParseError::new(
+                    source,
+                    Location::Line(line + 1),
+                    text.lines().nth(line).unwrap_or("").trim(),
+                    e,
+                ),
+                None => error(e.to_string()),
+            })?;
+
+        let mut ret = ExpectedAnswers::new();
+        let empty = toml::value::Table::new();
+        let days = year_days(value.as_table().unwrap_or(&empty)).map_err(error)?;
+        for (year, day, day_key, parts) in days {
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| error(format!("{}: expected a table of parts", day_key)))?;
+
+            for (part_key, value) in parts {
+                let part = parse_key(part_key, "part", 2).ok_or_else(|| {
+                    error(format!("{}.{}: expected part1 or part2", day_key, part_key))
+                })?;
+                let answer = answer_from_toml(value).ok_or_else(|| {
+                    error(format!(
+                        "{}.{}: expected a number or a string, got {}",
+                        day_key,
+                        part_key,
+                        value.type_str()
+                    ))
+                })?;
+                ret.insert(year, day, part, answer);
+            }
+        }
+        Ok(ret)
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, part: u32, answer: Answer) {
+        self.answers.insert((year, day, part), answer);
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    /// Compares a computed answer against the recorded one.
+    pub fn check(&self, year: u32, day: u32, part: u32, actual: &Answer) -> Verdict {
+        match (self.get(year, day, part), actual) {
+            (None, Answer::Empty) => Verdict::Pass,
+            (None, _) => Verdict::Missing,
+            // Compare the printed form, a number may have been recorded as a string
+            (Some(expected), _) if expected.to_string() == actual.to_string() => Verdict::Pass,
+            (Some(_), _) => Verdict::Fail,
+        }
+    }
+}
+
+/// Outcome of comparing an answer with the recorded one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// No answer has been recorded yet
+    Missing,
+    /// The solver did not produce an answer
+    Error,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail => "FAIL",
+            Verdict::Missing => "missing",
+            Verdict::Error => "ERROR",
+        };
+        f.pad(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str) -> Result<ExpectedAnswers, ParseError> {
+        ExpectedAnswers::from_toml(&InputSource::from("answers.toml"), text)
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            parse("[day01]\npart1 = 514579\n\n[day21]\npart2 = \"mxmxvkd,sqjhc\"\n").unwrap();
+        assert_eq!(answers.get(2020, 1, 1), Some(&Answer::from(514579)));
+        assert_eq!(answers.get(2020, 1, 2), None);
+        assert_eq!(
+            answers.get(2020, 21, 2),
+            Some(&Answer::from("mxmxvkd,sqjhc"))
+        );
+    }
+
+    #[test]
+    fn test_parse_years() {
+        let answers = parse("[day01]\npart1 = 1\n\n[2021.day01]\npart1 = 2\n").unwrap();
+        assert_eq!(answers.get(2020, 1, 1), Some(&Answer::from(1)));
+        assert_eq!(answers.get(2021, 1, 1), Some(&Answer::from(2)));
+        assert_eq!(answers.get(2022, 1, 1), None);
+        assert_eq!(
+            parse("[2021.day30]\npart1 = 1\n").unwrap_err().message,
+            "2021.day30: expected a key like day01"
+        );
+        assert!(parse("2021 = 5\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("[day01]\npart1 = \n").unwrap_err().location,
+            Location::Line(2)
+        );
+        assert_eq!(
+            parse("[day26]\npart1 = 1\n").unwrap_err().message,
+            "day26: expected a key like day01"
+        );
+        assert_eq!(
+            parse("[day01]\npart3 = 1\n").unwrap_err().message,
+            "day01.part3: expected part1 or part2"
+        );
+        assert_eq!(
+            parse("[day01]\npart1 = 1.5\n").unwrap_err().message,
+            "day01.part1: expected a number or a string, got float"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = parse("[day01]\npart1 = 42\npart2 = \"1234567890123456789012\"\n").unwrap();
+        assert_eq!(answers.check(2020, 1, 1, &Answer::from(42)), Verdict::Pass);
+        assert_eq!(answers.check(2020, 1, 1, &Answer::from(43)), Verdict::Fail);
+        assert_eq!(
+            answers.check(2020, 1, 2, &Answer::from(1234567890123456789012i128)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2020, 2, 1, &Answer::from(1)),
+            Verdict::Missing
+        );
+        assert_eq!(
+            answers.check(2021, 1, 1, &Answer::from(42)),
+            Verdict::Missing
+        );
+        assert_eq!(answers.check(2020, 25, 2, &Answer::Empty), Verdict::Pass);
+    }
+
+    #[test]
+    fn test_recorded_answers() {
+        let answers = ExpectedAnswers::parse(&InputSource::File(default_path())).unwrap();
+        for day in 1..=25 {
+            assert!(
+                answers.get(FIRST_YEAR, day, 1).is_some(),
+                "day {} part 1",
+                day
+            );
+        }
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Cells that influence the next state of a cell.
+pub trait Neighborhood<C> {
+    fn neighbors(&self, cell: &C) -> Vec<C>;
+}
+
+impl<C, F> Neighborhood<C> for F
+where
+    F: Fn(&C) -> Vec<C>,
+{
+    fn neighbors(&self, cell: &C) -> Vec<C> {
+        self(cell)
+    }
+}
+
+/// All cells of an `N` dimensional integer lattice that differ by at most one in every coordinate.
+pub struct Moore<const N: usize> {
+    offsets: Vec<[i32; N]>,
+}
+
+impl<const N: usize> Moore<N> {
+    pub fn new() -> Moore<N> {
+        let mut offsets = vec![[0; N]];
+        for i in 0..N {
+            offsets = offsets
+                .iter()
+                .flat_map(|o| {
+                    (-1..=1).map(move |d| {
+                        let mut o = *o;
+                        o[i] = d;
+                        o
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|o| o.iter().any(|d| *d != 0));
+        Moore { offsets }
+    }
+}
+
+impl<const N: usize> Default for Moore<N> {
+    fn default() -> Moore<N> {
+        Moore::new()
+    }
+}
+
+impl<const N: usize> Neighborhood<[i32; N]> for Moore<N> {
+    fn neighbors(&self, cell: &[i32; N]) -> Vec<[i32; N]> {
+        self.offsets
+            .iter()
+            .map(|o| {
+                let mut p = *cell;
+                for i in 0..N {
+                    p[i] += o[i];
+                }
+                p
+            })
+            .collect()
+    }
+}
+
+/// The six surrounding tiles of a hexagonal lattice in axial coordinates `[q, r]`. Moving east
+/// increases `q`, moving south east increases `r`.
+pub struct Hex;
+
+impl Hex {
+    pub const EAST: [i32; 2] = [1, 0];
+    pub const SOUTH_EAST: [i32; 2] = [0, 1];
+    pub const SOUTH_WEST: [i32; 2] = [-1, 1];
+    pub const WEST: [i32; 2] = [-1, 0];
+    pub const NORTH_WEST: [i32; 2] = [0, -1];
+    pub const NORTH_EAST: [i32; 2] = [1, -1];
+
+    pub const OFFSETS: [[i32; 2]; 6] = [
+        Hex::EAST,
+        Hex::SOUTH_EAST,
+        Hex::SOUTH_WEST,
+        Hex::WEST,
+        Hex::NORTH_WEST,
+        Hex::NORTH_EAST,
+    ];
+}
+
+impl Neighborhood<[i32; 2]> for Hex {
+    fn neighbors(&self, cell: &[i32; 2]) -> Vec<[i32; 2]> {
+        Hex::OFFSETS
+            .iter()
+            .map(|o| [cell[0] + o[0], cell[1] + o[1]])
+            .collect()
+    }
+}
+
+/// Decides whether a cell is alive in the next generation.
+pub trait Rule {
+    fn next(&self, alive: bool, live_neighbors: usize) -> bool;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(bool, usize) -> bool,
+{
+    fn next(&self, alive: bool, live_neighbors: usize) -> bool {
+        self(alive, live_neighbors)
+    }
+}
+
+/// Rule in birth/survival notation: dead cells come alive with one of the `birth` counts of live
+/// neighbors, live cells stay alive with one of the `survival` counts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifeRule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl LifeRule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> LifeRule {
+        LifeRule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+}
+
+impl Rule for LifeRule {
+    fn next(&self, alive: bool, live_neighbors: usize) -> bool {
+        if alive {
+            self.survival.contains(&live_neighbors)
+        } else {
+            self.birth.contains(&live_neighbors)
+        }
+    }
+}
+
+/// Parses rules like `B3/S23`.
+impl FromStr for LifeRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<LifeRule, String> {
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>, String> {
+            part.strip_prefix(prefix)
+                .ok_or_else(|| format!("expected {:?} to start with '{}'", part, prefix))?
+                .chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|d| d as usize)
+                        .ok_or_else(|| format!("invalid neighbor count {:?}", c))
+                })
+                .collect()
+        };
+
+        match s.split('/').collect::<Vec<&str>>().as_slice() {
+            [birth, survival] => Ok(LifeRule {
+                birth: counts(birth, 'B')?,
+                survival: counts(survival, 'S')?,
+            }),
+            _ => Err(format!("expected a rule like \"B3/S23\", got {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for LifeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |counts: &[usize]| counts.iter().map(|c| c.to_string()).collect::<String>();
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+/// A cellular automaton, independent of how its cells are stored.
+pub trait Automaton {
+    type Cell;
+
+    /// Advances by one generation, returns whether any cell changed.
+    fn step(&mut self) -> bool;
+
+    fn is_alive(&self, cell: &Self::Cell) -> bool;
+
+    /// Number of live cells.
+    fn population(&self) -> usize;
+
+    fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Steps until nothing changes anymore, returns the number of generations that changed cells.
+    fn run_until_stable(&mut self) -> usize {
+        let mut generations = 0;
+        while self.step() {
+            generations += 1;
+        }
+        generations
+    }
+}
+
+/// Only stores the live cells of an unbounded lattice. Cells without live neighbors never come
+/// alive, so only live cells and their neighbors need to be visited.
+pub struct SparseAutomaton<C, N, R> {
+    live: HashSet<C>,
+    neighborhood: N,
+    rule: R,
+}
+
+impl<C, N, R> SparseAutomaton<C, N, R>
+where
+    C: Copy + Eq + Hash,
+    N: Neighborhood<C>,
+    R: Rule,
+{
+    /// Panics if `rule` brings cells without live neighbors to life, which would fill the whole
+    /// lattice.
+    pub fn new<I: IntoIterator<Item = C>>(live: I, neighborhood: N, rule: R) -> Self {
+        assert!(
+            !rule.next(false, 0),
+            "a sparse automaton can't give birth to cells without live neighbors"
+        );
+        SparseAutomaton {
+            live: live.into_iter().collect(),
+            neighborhood,
+            rule,
+        }
+    }
+
+    pub fn live_cells(&self) -> impl Iterator<Item = &C> {
+        self.live.iter()
+    }
+}
+
+impl<C, N, R> Automaton for SparseAutomaton<C, N, R>
+where
+    C: Copy + Eq + Hash,
+    N: Neighborhood<C>,
+    R: Rule,
+{
+    type Cell = C;
+
+    fn step(&mut self) -> bool {
+        let mut counts = HashMap::<C, usize>::new();
+        for cell in &self.live {
+            for n in self.neighborhood.neighbors(cell) {
+                *counts.entry(n).or_insert(0) += 1;
+            }
+        }
+
+        let mut next = counts
+            .iter()
+            .filter(|(cell, n)| self.rule.next(self.live.contains(cell), **n))
+            .map(|(cell, _)| *cell)
+            .collect::<HashSet<C>>();
+
+        if self.rule.next(true, 0) {
+            next.extend(self.live.iter().filter(|c| !counts.contains_key(c)));
+        }
+
+        let changed = next != self.live;
+        self.live = next;
+        changed
+    }
+
+    fn is_alive(&self, cell: &C) -> bool {
+        self.live.contains(cell)
+    }
+
+    fn population(&self) -> usize {
+        self.live.len()
+    }
+}
+
+/// Stores the state of every cell of a fixed, finite set. Neighbors outside the set are treated as
+/// permanently dead, so rules may give birth to cells without live neighbors.
+pub struct DenseAutomaton<C, R> {
+    index: HashMap<C, usize>,
+    neighbors: Vec<Vec<usize>>,
+    alive: Vec<bool>,
+    rule: R,
+}
+
+impl<C, R> DenseAutomaton<C, R>
+where
+    C: Copy + Eq + Hash,
+    R: Rule,
+{
+    /// `cells` lists every cell of the automaton together with its initial state.
+    pub fn new<I, N>(cells: I, neighborhood: &N, rule: R) -> Self
+    where
+        I: IntoIterator<Item = (C, bool)>,
+        N: Neighborhood<C>,
+    {
+        let (cells, alive): (Vec<C>, Vec<bool>) = cells.into_iter().unzip();
+        let index = cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (*c, i))
+            .collect::<HashMap<C, usize>>();
+        let neighbors = cells
+            .iter()
+            .map(|c| {
+                neighborhood
+                    .neighbors(c)
+                    .iter()
+                    .filter_map(|n| index.get(n).copied())
+                    .collect()
+            })
+            .collect();
+
+        DenseAutomaton {
+            index,
+            neighbors,
+            alive,
+            rule,
+        }
+    }
+}
+
+impl<C, R> Automaton for DenseAutomaton<C, R>
+where
+    C: Copy + Eq + Hash,
+    R: Rule,
+{
+    type Cell = C;
+
+    fn step(&mut self) -> bool {
+        let next = self
+            .neighbors
+            .iter()
+            .enumerate()
+            .map(|(i, neighbors)| {
+                let n = neighbors.iter().filter(|j| self.alive[**j]).count();
+                self.rule.next(self.alive[i], n)
+            })
+            .collect::<Vec<bool>>();
+
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    fn is_alive(&self, cell: &C) -> bool {
+        self.index.get(cell).is_some_and(|i| self.alive[*i])
+    }
+
+    fn population(&self) -> usize {
+        self.alive.iter().filter(|a| **a).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn glider() -> Vec<[i32; 2]> {
+        vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]]
+    }
+
+    #[test]
+    fn test_moore() {
+        assert_eq!(Moore::<1>::new().neighbors(&[5]), vec![[4], [6]]);
+        assert_eq!(Moore::<2>::new().neighbors(&[0, 0]).len(), 8);
+        assert_eq!(Moore::<4>::new().neighbors(&[0, 0, 0, 0]).len(), 80);
+    }
+
+    #[test]
+    fn test_life_rule() {
+        let rule = "B3/S23".parse::<LifeRule>().unwrap();
+        assert_eq!(rule, LifeRule::new(&[3], &[2, 3]));
+        assert_eq!(rule.to_string(), "B3/S23");
+        assert!(rule.next(false, 3));
+        assert!(!rule.next(false, 2));
+        assert!(rule.next(true, 2));
+        assert!(!rule.next(true, 4));
+
+        assert!("B3".parse::<LifeRule>().is_err());
+        assert!("B3/Sx".parse::<LifeRule>().is_err());
+    }
+
+    #[test]
+    fn test_sparse_glider() {
+        let mut life =
+            SparseAutomaton::new(glider(), Moore::<2>::new(), LifeRule::new(&[3], &[2, 3]));
+        life.run(4);
+        assert_eq!(life.population(), 5);
+        for [x, y] in glider() {
+            assert!(life.is_alive(&[x + 1, y + 1]));
+        }
+    }
+
+    #[test]
+    fn test_dense_matches_sparse() {
+        let rule = LifeRule::new(&[3], &[2, 3]);
+        let mut sparse = SparseAutomaton::new(glider(), Moore::<2>::new(), rule.clone());
+
+        let live = glider();
+        let cells = (0..8)
+            .flat_map(|y| (0..8).map(move |x| [x, y]))
+            .map(|c| (c, live.contains(&c)));
+        let mut dense = DenseAutomaton::new(cells, &Moore::<2>::new(), rule);
+
+        for _ in 0..8 {
+            sparse.step();
+            dense.step();
+            assert!(sparse.live_cells().all(|c| dense.is_alive(c)));
+            assert_eq!(sparse.population(), dense.population());
+        }
+    }
+
+    #[test]
+    fn test_dense_birth_without_neighbors() {
+        // A row of three cells where every dead cell without live neighbors comes alive
+        let line = |c: &[i32; 1]| vec![[c[0] - 1], [c[0] + 1]];
+        let rule = |alive: bool, n: usize| if alive { n < 2 } else { n == 0 };
+        let mut automaton =
+            DenseAutomaton::new(vec![([0], false), ([1], false), ([2], false)], &line, rule);
+        assert!(automaton.step());
+        assert_eq!(automaton.population(), 3);
+        assert!(automaton.step());
+        assert_eq!(automaton.population(), 2);
+        assert_eq!(automaton.run_until_stable(), 0);
+    }
+
+    #[test]
+    fn test_hex() {
+        let mut n = Hex.neighbors(&[0, 0]);
+        n.sort();
+        let mut expected = Hex::OFFSETS.to_vec();
+        expected.sort();
+        assert_eq!(n, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sparse_rejects_birth_without_neighbors() {
+        SparseAutomaton::new(glider(), Hex, LifeRule::new(&[0], &[]));
+    }
+}
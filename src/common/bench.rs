@@ -0,0 +1,383 @@
+use crate::error::{Location, ParseError};
+use crate::input::InputSource;
+use crate::solution::{TimedRun, FIRST_YEAR};
+use std::fmt;
+use std::time::Duration;
+
+/// Slowdowns smaller than this are treated as noise when comparing against a baseline.
+pub const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+/// Part of a day that is timed separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.iter().copied().find(|p| p.name() == name)
+    }
+
+    fn duration(&self, run: &TimedRun) -> Duration {
+        match self {
+            Phase::Parse => run.parse,
+            Phase::Part1 => run.part1,
+            Phase::Part2 => run.part2,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Timing of one phase of a day over several runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub phase: Phase,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+/// Summarizes repeated runs of a day, one measurement per phase.
+pub fn measure(year: u32, day: u32, runs: &[TimedRun]) -> Vec<Measurement> {
+    if runs.is_empty() {
+        return Vec::new();
+    }
+
+    Phase::ALL
+        .iter()
+        .map(|phase| {
+            let mut durations = runs
+                .iter()
+                .map(|r| phase.duration(r))
+                .collect::<Vec<Duration>>();
+            durations.sort();
+
+            let n = durations.len();
+            let median = if n % 2 == 0 {
+                (durations[n / 2 - 1] + durations[n / 2]) / 2
+            } else {
+                durations[n / 2]
+            };
+
+            Measurement {
+                year,
+                day,
+                phase: *phase,
+                runs: n,
+                min: durations[0],
+                median,
+                max: durations[n - 1],
+            }
+        })
+        .collect()
+}
+
+/// Timings of a benchmark run, stored as CSV or JSON with durations in microseconds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub measurements: Vec<Measurement>,
+}
+
+const CSV_HEADER: &str = "year,day,phase,runs,min_us,median_us,max_us";
+
+/// Header of reports written before other years were supported, their rows have no year.
+const LEGACY_CSV_HEADER: &str = "day,phase,runs,min_us,median_us,max_us";
+
+fn micros(d: Duration) -> u64 {
+    d.as_micros() as u64
+}
+
+impl Report {
+    pub fn new() -> Report {
+        Report {
+            measurements: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, year: u32, day: u32, phase: Phase) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|m| m.year == year && m.day == day && m.phase == phase)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut ret = String::from(CSV_HEADER);
+        ret.push('\n');
+        for m in &self.measurements {
+            ret += &format!(
+                "{},{},{},{},{},{},{}\n",
+                m.year,
+                m.day,
+                m.phase,
+                m.runs,
+                micros(m.min),
+                micros(m.median),
+                micros(m.max)
+            );
+        }
+        ret
+    }
+
+    pub fn to_json(&self) -> String {
+        let measurements = self
+            .measurements
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "year": m.year,
+                    "day": m.day,
+                    "phase": m.phase.name(),
+                    "runs": m.runs,
+                    "min_us": micros(m.min),
+                    "median_us": micros(m.median),
+                    "max_us": micros(m.max),
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
+        serde_json::to_string_pretty(&measurements).unwrap() + "\n"
+    }
+
+    /// Reads a report written by `to_csv` or `to_json`, the format is detected from the content.
+    pub fn parse(source: &InputSource) -> Result<Report, ParseError> {
+        let text = source
+            .read_to_string()
+            .map_err(|e| ParseError::new(source, Location::Input, "", e))?;
+        if text.trim_start().starts_with('[') {
+            Report::from_json(source, &text)
+        } else {
+            Report::from_csv(source, &text)
+        }
+    }
+
+    fn from_csv(source: &InputSource, text: &str) -> Result<Report, ParseError> {
+        let mut ret = Report::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line == CSV_HEADER || line == LEGACY_CSV_HEADER {
+                continue;
+            }
+            let error =
+                |message: &str| ParseError::new(source, Location::Line(i + 1), line, message);
+
+            let mut fields = line.split(',').collect::<Vec<&str>>();
+            let number = |s: &str| s.parse::<u64>().map_err(|_| error("invalid number"));
+            let year = match fields.len() {
+                7 => number(fields.remove(0))? as u32,
+                6 => FIRST_YEAR,
+                _ => return Err(error("expected 7 fields")),
+            };
+            ret.measurements.push(Measurement {
+                year,
+                day: number(fields[0])? as u32,
+                phase: Phase::from_name(fields[1]).ok_or_else(|| error("unknown phase"))?,
+                runs: number(fields[2])? as usize,
+                min: Duration::from_micros(number(fields[3])?),
+                median: Duration::from_micros(number(fields[4])?),
+                max: Duration::from_micros(number(fields[5])?),
+            });
+        }
+
+        Ok(ret)
+    }
+
+    fn from_json(source: &InputSource, text: &str) -> Result<Report, ParseError> {
+        let value = serde_json::from_str::<serde_json::Value>(text).map_err(|e| {
+            ParseError::new(
+                source,
+                Location::Line(e.line()),
+                text.lines().nth(e.line().max(1) - 1).unwrap_or("").trim(),
+                e,
+            )
+        })?;
+        let entries = value.as_array().ok_or_else(|| {
+            ParseError::new(
+                source,
+                Location::Input,
+                "",
+                "expected a list of measurements",
+            )
+        })?;
+
+        let mut ret = Report::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let error = |message: String| {
+                ParseError::new(
+                    source,
+                    Location::Input,
+                    "",
+                    format!("measurement {}: {}", i + 1, message),
+                )
+            };
+            let number = |key: &str| {
+                entry[key]
+                    .as_u64()
+                    .ok_or_else(|| error(format!("expected a number for {:?}", key)))
+            };
+            let phase = entry["phase"]
+                .as_str()
+                .and_then(Phase::from_name)
+                .ok_or_else(|| error(String::from("unknown phase")))?;
+
+            ret.measurements.push(Measurement {
+                year: entry["year"].as_u64().map_or(FIRST_YEAR, |y| y as u32),
+                day: number("day")? as u32,
+                phase,
+                runs: number("runs")? as usize,
+                min: Duration::from_micros(number("min_us")?),
+                median: Duration::from_micros(number("median_us")?),
+                max: Duration::from_micros(number("max_us")?),
+            });
+        }
+
+        Ok(ret)
+    }
+}
+
+/// Median time of a phase in a baseline and in the current run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub year: u32,
+    pub day: u32,
+    pub phase: Phase,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// Relative change of the current time, `0.5` means 50% slower than the baseline.
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        self.current.as_secs_f64() / baseline - 1.0
+    }
+
+    /// Whether the current run is slower by more than `threshold` (relative) and the noise floor.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold && self.current >= self.baseline + NOISE_FLOOR
+    }
+}
+
+/// Pairs every measurement of `current` with the same phase of the same day in `baseline`, if
+/// present.
+pub fn compare(baseline: &Report, current: &Report) -> Vec<Comparison> {
+    current
+        .measurements
+        .iter()
+        .filter_map(|m| {
+            baseline.get(m.year, m.day, m.phase).map(|b| Comparison {
+                year: m.year,
+                day: m.day,
+                phase: m.phase,
+                baseline: b.median,
+                current: m.median,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::Answer;
+
+    fn run(parse: u64, part1: u64, part2: u64) -> TimedRun {
+        TimedRun {
+            answers: (Answer::Empty, Answer::Empty),
+            parse: Duration::from_micros(parse),
+            part1: Duration::from_micros(part1),
+            part2: Duration::from_micros(part2),
+        }
+    }
+
+    fn report() -> Report {
+        Report {
+            measurements: measure(
+                2020,
+                3,
+                &[run(10, 500, 9000), run(30, 400, 7000), run(20, 600, 8000)],
+            ),
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let m = report().measurements;
+        assert_eq!(m.len(), 3);
+        assert_eq!(m[0].phase, Phase::Parse);
+        assert_eq!(m[0].runs, 3);
+        assert_eq!(m[0].min, Duration::from_micros(10));
+        assert_eq!(m[0].median, Duration::from_micros(20));
+        assert_eq!(m[2].max, Duration::from_micros(9000));
+
+        let m = measure(2020, 1, &[run(10, 0, 0), run(20, 0, 0)]);
+        assert_eq!(m[0].median, Duration::from_micros(15));
+        assert!(measure(2020, 1, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_formats() {
+        let source = InputSource::from("report");
+        let r = report();
+
+        let csv = r.to_csv();
+        assert!(csv
+            .starts_with("year,day,phase,runs,min_us,median_us,max_us\n2020,3,parse,3,10,20,30\n"));
+        assert_eq!(Report::from_csv(&source, &csv), Ok(r.clone()));
+        assert_eq!(Report::from_json(&source, &r.to_json()), Ok(r.clone()));
+
+        // Reports without years are of the first year
+        let legacy = "day,phase,runs,min_us,median_us,max_us\n3,parse,3,10,20,30\n";
+        let legacy = Report::from_csv(&source, legacy).unwrap();
+        assert_eq!(legacy.measurements[0], r.measurements[0]);
+        let legacy = Report::from_json(&source, "[{\"day\": 3, \"phase\": \"parse\", \"runs\": 3, \"min_us\": 10, \"median_us\": 20, \"max_us\": 30}]").unwrap();
+        assert_eq!(legacy.measurements[0], r.measurements[0]);
+
+        let e = Report::from_csv(&source, "3,parse,3,10,20\n").unwrap_err();
+        assert_eq!(e.location, Location::Line(1));
+        let e = Report::from_json(&source, "[{\"day\": 3}]").unwrap_err();
+        assert_eq!(e.message, "measurement 1: unknown phase");
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = report();
+        let mut current = report();
+        current.measurements[1].median = Duration::from_micros(5000);
+        current.measurements[2].median = Duration::from_micros(8500);
+        current.measurements[2].day = 4;
+        current.measurements[0].year = 2021;
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert!(comparisons[0].is_regression(0.1));
+        assert!((comparisons[0].change() - 9.0).abs() < 1e-9);
+
+        // Large relative changes of very short phases are noise
+        let mut current = report();
+        current.measurements[0].median = Duration::from_micros(200);
+        assert!(!compare(&baseline, &current)[0].is_regression(0.1));
+    }
+}
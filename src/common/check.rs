@@ -0,0 +1,460 @@
+//! Format checks of puzzle inputs that report every problem instead of stopping at the first.
+//!
+//! Every day has a `check` module with a [`Checker`] validating the grammar of its input, like
+//! the parsers do, without solving anything. Lines are trimmed and blank lines between records
+//! ignored as by the readers in [`crate::reader`], but columns refer to the untrimmed line.
+
+use crate::solution::FIRST_YEAR;
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed part of an input together with a suggestion how to fix it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    pub message: String,
+    pub fix: String,
+}
+
+impl Problem {
+    pub fn new<M: Into<String>, F: Into<String>>(
+        line: usize,
+        column: usize,
+        message: M,
+        fix: F,
+    ) -> Problem {
+        Problem {
+            line,
+            column,
+            message: message.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+pub trait Checker: Send + Sync {
+    fn year(&self) -> u32 {
+        FIRST_YEAR
+    }
+
+    fn day(&self) -> u32;
+
+    /// All problems of `input`, ordered by position. An empty list means the input is well formed.
+    fn check(&self, input: &str) -> Vec<Problem>;
+}
+
+/// Non-empty line of an input, trimmed like the readers do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+    indent: usize,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, raw: &'a str) -> Line<'a> {
+        let text = raw.trim();
+        let indent = raw[..raw.len() - raw.trim_start().len()].chars().count();
+        Line {
+            number,
+            text,
+            indent,
+        }
+    }
+
+    /// Number of characters of the trimmed line.
+    pub fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Problem at the 1-based `column` of the trimmed line.
+    pub fn problem<M: Into<String>, F: Into<String>>(
+        &self,
+        column: usize,
+        message: M,
+        fix: F,
+    ) -> Problem {
+        Problem::new(self.number, self.indent + column, message, fix)
+    }
+
+    pub fn scanner(&self) -> Scanner<'a> {
+        Scanner {
+            line: *self,
+            pos: 0,
+        }
+    }
+}
+
+/// Non-empty lines of an input.
+pub fn lines(input: &str) -> Vec<Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, raw)| Line::new(i + 1, raw))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Blocks of non-empty lines separated by blank lines.
+pub fn groups(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    for (i, raw) in input.lines().enumerate() {
+        let line = Line::new(i + 1, raw);
+        if !line.is_empty() {
+            group.push(line);
+        } else if !group.is_empty() {
+            groups.push(std::mem::take(&mut group));
+        }
+    }
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    groups
+}
+
+/// Problem reported for an input without any non-empty line.
+pub fn empty_input() -> Problem {
+    Problem::new(
+        1,
+        1,
+        "input is empty",
+        "paste the puzzle input into the file",
+    )
+}
+
+/// Problem reported below the last line of `lines` when something is missing at the end.
+pub fn missing<M: Into<String>, F: Into<String>>(lines: &[Line], message: M, fix: F) -> Problem {
+    let line = lines.last().map_or(1, |l| l.number + 1);
+    Problem::new(line, 1, message, fix)
+}
+
+/// Problem reported for every line after the expected ones.
+pub fn unexpected_lines(lines: &[Line], what: &str) -> Vec<Problem> {
+    lines
+        .iter()
+        .map(|line| {
+            line.problem(
+                1,
+                format!("unexpected line after {}", what),
+                "remove the line",
+            )
+        })
+        .collect()
+}
+
+/// Checks every non-empty line of an input with `f`, which is expected to consume the whole line.
+pub fn check_lines<F>(input: &str, f: F) -> Vec<Problem>
+where
+    F: Fn(&mut Scanner) -> Result<(), Problem>,
+{
+    let lines = lines(input);
+    if lines.is_empty() {
+        return vec![empty_input()];
+    }
+    check_each(&lines, f)
+}
+
+/// Checks each of `lines` with `f`, which is expected to consume the whole line.
+pub fn check_each<F>(lines: &[Line], f: F) -> Vec<Problem>
+where
+    F: Fn(&mut Scanner) -> Result<(), Problem>,
+{
+    lines
+        .iter()
+        .filter_map(|line| {
+            let mut scanner = line.scanner();
+            f(&mut scanner).and_then(|_| scanner.end()).err()
+        })
+        .collect()
+}
+
+/// Checks that `lines` form a rectangular grid of the characters in `cells`.
+pub fn check_grid(lines: &[Line], cells: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let width = match lines.first() {
+        Some(line) => line.len(),
+        None => return problems,
+    };
+    let allowed = cells
+        .chars()
+        .map(|c| format!("'{}'", c))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    for line in lines {
+        if let Some((i, c)) = line
+            .text
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !cells.contains(*c))
+        {
+            problems.push(line.problem(
+                i + 1,
+                format!("unexpected character {:?} in grid", c),
+                format!("use one of {}", allowed),
+            ));
+        } else if line.len() != width {
+            problems.push(line.problem(
+                line.len().min(width) + 1,
+                format!("row is {} wide, expected {}", line.len(), width),
+                format!("make every row {} characters wide", width),
+            ));
+        }
+    }
+    problems
+}
+
+/// Cursor over a line consuming the tokens of its grammar. Every method failing to match returns
+/// a [`Problem`] at the current position and leaves the position unchanged.
+pub struct Scanner<'a> {
+    line: Line<'a>,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn line(&self) -> &Line<'a> {
+        &self.line
+    }
+
+    /// 1-based column of the current position in the trimmed line.
+    pub fn column(&self) -> usize {
+        self.line.text[..self.pos].chars().count() + 1
+    }
+
+    /// Unconsumed rest of the line.
+    pub fn remaining(&self) -> &'a str {
+        &self.line.text[self.pos..]
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    /// Problem at the current position.
+    pub fn problem<M: Into<String>, F: Into<String>>(&self, message: M, fix: F) -> Problem {
+        self.line.problem(self.column(), message, fix)
+    }
+
+    /// Problem at a column returned by [`Scanner::column`] earlier.
+    pub fn problem_at<M: Into<String>, F: Into<String>>(
+        &self,
+        column: usize,
+        message: M,
+        fix: F,
+    ) -> Problem {
+        self.line.problem(column, message, fix)
+    }
+
+    /// Description of the next token for messages, like `found "x"` or `found end of line`.
+    pub fn found(&self) -> String {
+        match self.remaining().split_whitespace().next() {
+            Some(token) => format!("found {:?}", token),
+            None if self.at_end() => String::from("found end of line"),
+            None => String::from("found whitespace"),
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let token = &self.remaining()[..len];
+        self.pos += len;
+        token
+    }
+
+    /// Consumes `s` if the rest of the line starts with it.
+    pub fn eat(&mut self, s: &str) -> bool {
+        let matches = self.remaining().starts_with(s);
+        if matches {
+            self.advance(s.len());
+        }
+        matches
+    }
+
+    /// Consumes any number of spaces.
+    pub fn skip_spaces(&mut self) {
+        while self.eat(" ") {}
+    }
+
+    /// Consumes exactly `s`.
+    pub fn literal(&mut self, s: &str) -> Result<(), Problem> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.problem(
+                format!("expected {:?}, {}", s, self.found()),
+                format!("write {:?} here", s),
+            ))
+        }
+    }
+
+    /// Consumes the first of `options` the rest of the line starts with.
+    pub fn one_of<'b>(&mut self, options: &[&'b str]) -> Result<&'b str, Problem> {
+        match options.iter().find(|o| self.remaining().starts_with(*o)) {
+            Some(o) => {
+                self.advance(o.len());
+                Ok(o)
+            }
+            None => {
+                let options = options
+                    .iter()
+                    .map(|o| format!("{:?}", o))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                Err(self.problem(
+                    format!("expected one of {}, {}", options, self.found()),
+                    format!("use one of {}", options),
+                ))
+            }
+        }
+    }
+
+    /// Consumes the longest non-empty run of characters matching `f`, described as `what`.
+    pub fn word<F: Fn(char) -> bool>(&mut self, what: &str, f: F) -> Result<&'a str, Problem> {
+        let len = self
+            .remaining()
+            .find(|c| !f(c))
+            .unwrap_or_else(|| self.remaining().len());
+        if len == 0 {
+            Err(self.problem(
+                format!("expected {}, {}", what, self.found()),
+                format!("write {} here", what),
+            ))
+        } else {
+            Ok(self.advance(len))
+        }
+    }
+
+    /// Consumes a non-negative decimal number fitting into `T`.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, Problem> {
+        let start = self.pos;
+        self.word("a number", |c| c.is_ascii_digit())?;
+        self.parse_from(start)
+    }
+
+    /// Consumes a decimal number with an optional sign fitting into `T`.
+    pub fn signed<T: FromStr>(&mut self) -> Result<T, Problem> {
+        let start = self.pos;
+        let _ = self.eat("+") || self.eat("-");
+        if let Err(problem) = self.word("a number", |c| c.is_ascii_digit()) {
+            self.pos = start;
+            return Err(problem);
+        }
+        self.parse_from(start)
+    }
+
+    fn parse_from<T: FromStr>(&mut self, start: usize) -> Result<T, Problem> {
+        let text = &self.line.text[start..self.pos];
+        text.parse::<T>().map_err(|_| {
+            self.pos = start;
+            self.problem(
+                format!("number {} is out of range", text),
+                "use a smaller number",
+            )
+        })
+    }
+
+    /// Consumes a list of at least one item separated by `separator`, checking each with `f`.
+    pub fn list<T, F>(&mut self, separator: &str, mut f: F) -> Result<Vec<T>, Problem>
+    where
+        F: FnMut(&mut Scanner<'a>) -> Result<T, Problem>,
+    {
+        let mut items = vec![f(self)?];
+        while self.eat(separator) {
+            items.push(f(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Succeeds if the whole line has been consumed.
+    pub fn end(&self) -> Result<(), Problem> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(self.problem(
+                format!("unexpected {:?} at end of line", self.remaining()),
+                "remove it",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines_and_groups() {
+        let input = "  a\n\nb\nc \n\n\nd\n";
+        let lines = lines(input);
+        assert_eq!(lines.len(), 4);
+        assert_eq!((lines[0].number, lines[0].text), (1, "a"));
+        assert_eq!(lines[0].problem(1, "", "").column, 3);
+
+        let groups = groups(input);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[1].iter().map(|l| l.number).collect::<Vec<_>>(),
+            [3, 4]
+        );
+    }
+
+    #[test]
+    fn test_scanner() {
+        let line = lines("mem[8] = -12x").remove(0);
+        let mut s = line.scanner();
+        assert_eq!(s.one_of(&["mask", "mem"]), Ok("mem"));
+        s.literal("[").unwrap();
+        assert_eq!(s.number::<u8>(), Ok(8));
+        s.literal("] = ").unwrap();
+        assert_eq!(s.number::<i32>().unwrap_err().column, 10);
+        assert_eq!(s.signed::<i32>(), Ok(-12));
+        let problem = s.end().unwrap_err();
+        assert_eq!((problem.line, problem.column), (1, 13));
+        assert_eq!(problem.message, "unexpected \"x\" at end of line");
+    }
+
+    #[test]
+    fn test_number_out_of_range() {
+        let line = lines("x 300").remove(0);
+        let mut s = line.scanner();
+        s.literal("x ").unwrap();
+        let problem = s.number::<u8>().unwrap_err();
+        assert_eq!(problem.column, 3);
+        assert_eq!(problem.message, "number 300 is out of range");
+    }
+
+    #[test]
+    fn test_check_grid() {
+        let problems = check_grid(&lines(".#.\n.x.\n..\n"), ".#");
+        assert_eq!(problems.len(), 2);
+        assert_eq!((problems[0].line, problems[0].column), (2, 2));
+        assert_eq!((problems[1].line, problems[1].column), (3, 3));
+    }
+
+    #[test]
+    fn test_check_lines() {
+        assert_eq!(check_lines("\n\n", |_| Ok(())), [empty_input()]);
+        let problems = check_lines("1\nx\n3 4\n", |s| s.number::<i32>().map(|_| ()));
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].message, "expected a number, found \"x\"");
+        assert_eq!((problems[1].line, problems[1].column), (3, 2));
+    }
+}
@@ -0,0 +1,459 @@
+use crate::input::InputResolver;
+use crate::submit::{self, Outcome};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable overriding the server the client talks to.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable holding the value of the `session` cookie of a logged in user.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Time a downloaded leaderboard is reused for, the website asks not to fetch it more often.
+pub const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+const USER_AGENT: &str = "github.com/Andurian/AdventOfCode2020";
+
+/// Error talking to the puzzle server or storing what it sent.
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token was configured
+    MissingSession,
+    /// The server answered with an error status
+    Status(u16, String),
+    /// The server could not be reached
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token, set ${} or pass --session",
+                SESSION_VAR
+            ),
+            ClientError::Status(status, url) => write!(f, "{} returned status {}", url, status),
+            ClientError::Transport(message) => write!(f, "{}", message),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> ClientError {
+        ClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> ClientError {
+        match e {
+            ureq::Error::Status(status, response) => {
+                ClientError::Status(status, String::from(response.get_url()))
+            }
+            ureq::Error::Transport(t) => ClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// Client for the puzzle server, authenticated with the session cookie of a user.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session: String::from(session.trim()),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Uses `$AOC_BASE_URL` and `$AOC_SESSION` unless given explicitly.
+    pub fn from_env(base_url: Option<&str>, session: Option<&str>) -> Result<Client, ClientError> {
+        let session = match session {
+            Some(s) => String::from(s),
+            None => env::var(SESSION_VAR).map_err(|_| ClientError::MissingSession)?,
+        };
+        if session.trim().is_empty() {
+            return Err(ClientError::MissingSession);
+        }
+        let base_url = match base_url {
+            Some(url) => String::from(url),
+            None => env::var(BASE_URL_VAR).unwrap_or_else(|_| String::from(DEFAULT_BASE_URL)),
+        };
+        Ok(Client::new(&base_url, &session))
+    }
+
+    fn day_url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}", self.base_url, year, day)
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_string()?)
+    }
+
+    fn post(&self, url: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        Ok(self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(form)?
+            .into_string()?)
+    }
+
+    /// Submits `answer` for a part of a day and returns what the server said about it.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<Outcome, ClientError> {
+        let level = part.to_string();
+        let page = self.post(
+            &format!("{}/answer", self.day_url(year, day)),
+            &[("level", &level), ("answer", answer)],
+        )?;
+        Ok(submit::parse_response(&page))
+    }
+
+    /// Downloads the personal input of a day.
+    pub fn input(&self, year: u32, day: u32) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", self.day_url(year, day)))
+    }
+
+    /// Downloads the JSON document of a private leaderboard.
+    pub fn leaderboard(&self, year: u32, id: u64) -> Result<String, ClientError> {
+        self.get(&format!(
+            "{}/{}/leaderboard/private/view/{}.json",
+            self.base_url, year, id
+        ))
+    }
+}
+
+/// Where an input returned by `fetch_input` came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input of a day is in the cache of `resolver`, downloading it only if it isn't.
+pub fn fetch_input(
+    client: &Client,
+    resolver: &InputResolver,
+    year: u32,
+    day: u32,
+) -> Result<Fetched, ClientError> {
+    let path = resolver
+        .cached_path(year, day)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory configured"))?;
+    if path.is_file() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.input(year, day)?;
+    if input.trim().is_empty() {
+        return Err(ClientError::Transport(format!(
+            "received an empty input for day {}",
+            day
+        )));
+    }
+
+    store(&path, &input)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Makes sure a copy of a private leaderboard younger than [`LEADERBOARD_MAX_AGE`] is in the
+/// cache of `resolver`, downloading it only if there is none.
+pub fn fetch_leaderboard(
+    client: &Client,
+    resolver: &InputResolver,
+    year: u32,
+    id: u64,
+) -> Result<Fetched, ClientError> {
+    let path = resolver
+        .cache_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory configured"))?
+        .join(format!("leaderboard_{}_{}.json", year, id));
+    let fresh = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age < LEADERBOARD_MAX_AGE);
+    if fresh {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let json = client.leaderboard(year, id)?;
+    // Without a valid session the server answers with the login page instead
+    if !json.trim_start().starts_with('{') {
+        return Err(ClientError::Transport(format!(
+            "received no leaderboard for {}, is the session valid?",
+            id
+        )));
+    }
+
+    store(&path, &json)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Writes a download to `path` through a temporary file, so that an interrupted download never
+/// counts as cached.
+fn store(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let partial = path.with_extension("part");
+    fs::write(&partial, text)?;
+    fs::rename(&partial, path)
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Request received by the mock server.
+    #[derive(Clone, Debug, Default)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// HTTP server on a random local port that answers every request using `respond`, which
+    /// returns the status and body. All requests are recorded.
+    pub fn serve<F>(respond: F) -> (String, Arc<Mutex<Vec<Request>>>)
+    where
+        F: Fn(&Request) -> (u16, String) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(s) => s,
+                    Err(_) => break,
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let mut request = Request {
+                    method: String::from(parts.next().unwrap_or("")),
+                    path: String::from(parts.next().unwrap_or("")),
+                    ..Request::default()
+                };
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(i) = line.find(':') {
+                        request
+                            .headers
+                            .push((String::from(&line[..i]), String::from(line[i + 1..].trim())));
+                    }
+                }
+
+                let length = request
+                    .header("Content-Length")
+                    .and_then(|l| l.parse::<usize>().ok())
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.body = String::from_utf8_lossy(&body).into_owned();
+
+                let (status, body) = respond(&request);
+                recorded.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_submit() {
+        let (url, requests) = mock::serve(|r| {
+            if r.body.contains("answer=514579") {
+                (200, String::from("<p>That's the right answer!</p>"))
+            } else {
+                (
+                    200,
+                    String::from("<p>That's not the right answer; your answer is too low.</p>"),
+                )
+            }
+        });
+        let client = Client::new(&url, "secret");
+
+        assert_eq!(
+            client.submit(2020, 1, 1, "514579").unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(client.submit(2021, 1, 2, "12").unwrap(), Outcome::TooLow);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2020/day/1/answer");
+        assert_eq!(requests[0].body, "level=1&answer=514579");
+        assert_eq!(requests[1].path, "/2021/day/1/answer");
+        assert_eq!(requests[1].header("Cookie"), Some("session=secret"));
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_client_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_input_is_cached() {
+        let (url, requests) = mock::serve(|r| match r.path.as_str() {
+            "/2020/day/1/input" => (200, String::from("1721\n979\n")),
+            _ => (404, String::new()),
+        });
+        let cache = temp_dir("cached");
+        let resolver = InputResolver::new().with_cache_dir(&cache);
+        let client = Client::new(&url, "secret");
+
+        let path = cache.join("day01.txt");
+        assert_eq!(
+            fetch_input(&client, &resolver, 2020, 1).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n");
+        assert_eq!(
+            fetch_input(&client, &resolver, 2020, 1).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+        assert_eq!(
+            resolver.resolve(2020, 1, None).unwrap(),
+            crate::InputSource::File(path)
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_leaderboard_is_cached() {
+        let (url, requests) = mock::serve(|r| match r.path.as_str() {
+            "/2020/leaderboard/private/view/1001.json" => {
+                (200, String::from("{\"event\": \"2020\"}"))
+            }
+            _ => (200, String::from("<html>Log in</html>")),
+        });
+        let cache = temp_dir("leaderboard");
+        let resolver = InputResolver::new().with_cache_dir(&cache);
+        let client = Client::new(&url, "secret");
+
+        let path = cache.join("leaderboard_2020_1001.json");
+        assert_eq!(
+            fetch_leaderboard(&client, &resolver, 2020, 1001).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(
+            fetch_leaderboard(&client, &resolver, 2020, 1001).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+        assert!(fetch_leaderboard(&client, &resolver, 2020, 7).is_err());
+        assert!(!cache.join("leaderboard_2020_7.json").exists());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (url, _) = mock::serve(|r| match r.path.as_str() {
+            "/2020/day/2/input" => (200, String::from("\n")),
+            _ => (404, String::from("Not found")),
+        });
+        let cache = temp_dir("errors");
+        let resolver = InputResolver::new().with_cache_dir(&cache);
+        let client = Client::new(&format!("{}/", url), "secret");
+
+        match fetch_input(&client, &resolver, 2020, 3) {
+            Err(ClientError::Status(404, u)) => assert!(u.ends_with("/2020/day/3/input")),
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert!(fetch_input(&client, &resolver, 2020, 2).is_err());
+        assert!(!cache.join("day02.txt").exists());
+        assert!(!cache.join("day03.txt").exists());
+
+        let (url, _) = mock::serve(|_| (500, String::new()));
+        let client = Client::new(&url, "secret");
+        assert!(matches!(
+            client.submit(2020, 1, 1, "42"),
+            Err(ClientError::Status(500, _))
+        ));
+
+        assert!(matches!(
+            Client::from_env(Some(&url), Some(" ")),
+            Err(ClientError::MissingSession)
+        ));
+    }
+}
@@ -1,54 +1,312 @@
-use std::fmt::Debug;
-use std::fs;
-use std::fs::File;
-use std::io::{BufReader, BufRead};
+// Lets the day modules refer to this crate as `common`, like any other user of the library
+extern crate self as common;
+
+use std::convert::Infallible;
+use std::fmt::{Debug, Display};
 use std::str::FromStr;
 
-pub fn parse_file_linewise<TRet, F>(filename: &str, f: F) -> Vec<TRet>
+pub mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod check;
+pub mod client;
+pub mod error;
+pub mod examples;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod leaderboard;
+pub mod matching;
+pub mod pool;
+pub mod reader;
+pub mod record;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+
+#[path = "../day01/day01.rs"]
+pub mod day01;
+#[path = "../day02/day02.rs"]
+pub mod day02;
+#[path = "../day03/day03.rs"]
+pub mod day03;
+#[path = "../day04/day04.rs"]
+pub mod day04;
+#[path = "../day05/day05.rs"]
+pub mod day05;
+#[path = "../day06/day06.rs"]
+pub mod day06;
+#[path = "../day07/day07.rs"]
+pub mod day07;
+#[path = "../day08/day08.rs"]
+pub mod day08;
+#[path = "../day09/day09.rs"]
+pub mod day09;
+#[path = "../day10/day10.rs"]
+pub mod day10;
+#[path = "../day11/day11.rs"]
+pub mod day11;
+#[path = "../day12/day12.rs"]
+pub mod day12;
+#[path = "../day13/day13.rs"]
+pub mod day13;
+#[path = "../day14/day14.rs"]
+pub mod day14;
+#[path = "../day15/day15.rs"]
+pub mod day15;
+#[path = "../day16/day16.rs"]
+pub mod day16;
+#[path = "../day17/day17.rs"]
+pub mod day17;
+#[path = "../day18/day18.rs"]
+pub mod day18;
+#[path = "../day19/day19.rs"]
+pub mod day19;
+#[path = "../day20/day20.rs"]
+pub mod day20;
+#[path = "../day21/day21.rs"]
+pub mod day21;
+#[path = "../day22/day22.rs"]
+pub mod day22;
+#[path = "../day23/day23.rs"]
+pub mod day23;
+#[path = "../day24/day24.rs"]
+pub mod day24;
+#[path = "../day25/day25.rs"]
+pub mod day25;
+
+pub use error::{Error, Location, ParseError};
+pub use grid::Grid;
+pub use input::{InputResolver, InputSource};
+pub use solution::{Answer, Solution};
+
+/// All days solved so far, ordered by year and day. Days of other years than the first are
+/// registered as `Box::new(y2021::day01::Day01)`.
+pub fn registry() -> Vec<Box<dyn solution::Runnable>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day02::Day02),
+        Box::new(day03::Day03),
+        Box::new(day04::Day04),
+        Box::new(day05::Day05),
+        Box::new(day06::Day06),
+        Box::new(day07::Day07),
+        Box::new(day08::Day08),
+        Box::new(day09::Day09),
+        Box::new(day10::Day10),
+        Box::new(day11::Day11),
+        Box::new(day12::Day12),
+        Box::new(day13::Day13),
+        Box::new(day14::Day14),
+        Box::new(day15::Day15),
+        Box::new(day16::Day16),
+        Box::new(day17::Day17),
+        Box::new(day18::Day18),
+        Box::new(day19::Day19),
+        Box::new(day20::Day20),
+        Box::new(day21::Day21),
+        Box::new(day22::Day22),
+        Box::new(day23::Day23),
+        Box::new(day24::Day24),
+        Box::new(day25::Day25),
+    ]
+}
+
+/// Input generators of the days in [`registry`], in the same order. Days added by `aoc new` have
+/// none until one is written.
+pub fn generators() -> Vec<Box<dyn generate::Generator>> {
+    vec![
+        Box::new(day01::generate::Day01Generator),
+        Box::new(day02::generate::Day02Generator),
+        Box::new(day03::generate::Day03Generator),
+        Box::new(day04::generate::Day04Generator),
+        Box::new(day05::generate::Day05Generator),
+        Box::new(day06::generate::Day06Generator),
+        Box::new(day07::generate::Day07Generator),
+        Box::new(day08::generate::Day08Generator),
+        Box::new(day09::generate::Day09Generator),
+        Box::new(day10::generate::Day10Generator),
+        Box::new(day11::generate::Day11Generator),
+        Box::new(day12::generate::Day12Generator),
+        Box::new(day13::generate::Day13Generator),
+        Box::new(day14::generate::Day14Generator),
+        Box::new(day15::generate::Day15Generator),
+        Box::new(day16::generate::Day16Generator),
+        Box::new(day17::generate::Day17Generator),
+        Box::new(day18::generate::Day18Generator),
+        Box::new(day19::generate::Day19Generator),
+        Box::new(day20::generate::Day20Generator),
+        Box::new(day21::generate::Day21Generator),
+        Box::new(day22::generate::Day22Generator),
+        Box::new(day23::generate::Day23Generator),
+        Box::new(day24::generate::Day24Generator),
+        Box::new(day25::generate::Day25Generator),
+    ]
+}
+
+/// Input checkers of the days in [`registry`], in the same order. Days added by `aoc new` have
+/// none until one is written.
+pub fn checkers() -> Vec<Box<dyn check::Checker>> {
+    vec![
+        Box::new(day01::check::Day01Checker),
+        Box::new(day02::check::Day02Checker),
+        Box::new(day03::check::Day03Checker),
+        Box::new(day04::check::Day04Checker),
+        Box::new(day05::check::Day05Checker),
+        Box::new(day06::check::Day06Checker),
+        Box::new(day07::check::Day07Checker),
+        Box::new(day08::check::Day08Checker),
+        Box::new(day09::check::Day09Checker),
+        Box::new(day10::check::Day10Checker),
+        Box::new(day11::check::Day11Checker),
+        Box::new(day12::check::Day12Checker),
+        Box::new(day13::check::Day13Checker),
+        Box::new(day14::check::Day14Checker),
+        Box::new(day15::check::Day15Checker),
+        Box::new(day16::check::Day16Checker),
+        Box::new(day17::check::Day17Checker),
+        Box::new(day18::check::Day18Checker),
+        Box::new(day19::check::Day19Checker),
+        Box::new(day20::check::Day20Checker),
+        Box::new(day21::check::Day21Checker),
+        Box::new(day22::check::Day22Checker),
+        Box::new(day23::check::Day23Checker),
+        Box::new(day24::check::Day24Checker),
+        Box::new(day25::check::Day25Checker),
+    ]
+}
+
+/// Applies `f` to every non-empty line. Errors carry the line number and text of the offending line.
+pub fn try_parse_file_linewise<TRet, E, F>(
+    source: impl Into<InputSource>,
+    f: F,
+) -> Result<Vec<TRet>, ParseError>
+where
+    F: Fn(&str) -> Result<TRet, E>,
+    E: Display,
+{
+    let source = source.into();
+    reader::lines(&source)?.parse(&source, f).collect()
+}
+
+pub fn try_parse_file_linewise_as<T: FromStr>(
+    source: impl Into<InputSource>,
+) -> Result<Vec<T>, ParseError>
+where
+    <T as FromStr>::Err: Display,
+{
+    try_parse_file_linewise(source, |line| line.parse::<T>())
+}
+
+pub fn try_read_file_linewise(source: impl Into<InputSource>) -> Result<Vec<String>, ParseError> {
+    try_parse_file_linewise(source, |line| Ok::<String, Infallible>(String::from(line)))
+}
+
+/// Applies `f` to every group of lines. Groups are separated by blank lines, errors carry the
+/// index and text of the offending group.
+pub fn try_parse_grouped_file<TRet, E, F>(
+    source: impl Into<InputSource>,
+    f: F,
+) -> Result<Vec<TRet>, ParseError>
+where
+    F: Fn(&str) -> Result<TRet, E>,
+    E: Display,
+{
+    let source = source.into();
+    reader::groups(&source)?.parse(&source, f).collect()
+}
+
+pub fn try_read_grouped_file(source: impl Into<InputSource>) -> Result<Vec<String>, ParseError> {
+    try_parse_grouped_file(source, |group| {
+        Ok::<String, Infallible>(String::from(group))
+    })
+}
+
+pub fn parse_file_linewise<TRet, F>(source: impl Into<InputSource>, f: F) -> Vec<TRet>
 where
     F: Fn(&str) -> TRet,
 {
-    fs::read_to_string(filename)
-        .unwrap()
-        .split("\n")
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| f(line))
-        .collect::<Vec<TRet>>()
+    try_parse_file_linewise(source, |line| Ok::<TRet, Infallible>(f(line)))
+        .unwrap_or_else(|e| panic!("{}", e))
 }
 
-pub fn parse_file_linewise_as<T: FromStr>(filename: &str) -> Vec<T>
+pub fn parse_file_linewise_as<T: FromStr>(source: impl Into<InputSource>) -> Vec<T>
 where
     <T as FromStr>::Err: Debug,
 {
-    parse_file_linewise(filename, |line| line.parse::<T>().unwrap())
+    parse_file_linewise(source, |line| line.parse::<T>().unwrap())
 }
 
-pub fn read_file_linewise(filename: &str) -> Vec<String> {
-    parse_file_linewise(filename, |s| String::from(s))
+pub fn read_file_linewise(source: impl Into<InputSource>) -> Vec<String> {
+    try_read_file_linewise(source).unwrap_or_else(|e| panic!("{}", e))
 }
 
-pub fn read_grouped_file(filename: &str) -> Vec<String> {
-    let reader = BufReader::new(File::open(filename).unwrap());
+pub fn read_grouped_file(source: impl Into<InputSource>) -> Vec<String> {
+    try_read_grouped_file(source).unwrap_or_else(|e| panic!("{}", e))
+}
 
-    let mut ret = Vec::<String>::new();
-    let mut current = String::new();
-    for line in reader.lines() {
-        let line = String::from(line.unwrap().trim());
-        if line.is_empty() {
-            if !current.is_empty() {
-                ret.push(current.trim().to_string());
-            }
-            current = String::new();
-        } else {
-            current.push('\n');
-            current.push_str(&line);
-        }
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn write_temp(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc_common_{}_{}", name, std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
     }
 
-    if !current.is_empty() {
-        ret.push(current.trim().to_string());
+    #[test]
+    fn test_try_parse_file_linewise_as() {
+        let path = write_temp("numbers", "1\n2\n\n  3  \n");
+        let source = InputSource::File(path.clone());
+        assert_eq!(
+            try_parse_file_linewise_as::<i32>(&source),
+            Ok(vec![1, 2, 3])
+        );
+
+        fs::write(&path, "1\n\nx2\n3").unwrap();
+        let err = try_parse_file_linewise_as::<i32>(&source).unwrap_err();
+        assert_eq!(err.location, Location::Line(3));
+        assert_eq!(err.text, "x2");
+        assert_eq!(err.input, path.display().to_string());
+
+        fs::remove_file(&path).unwrap();
     }
 
-    ret
+    #[test]
+    fn test_try_parse_grouped_file() {
+        let path = write_temp("groups", "a\nb\n\n\nc\n\nd\ne");
+        let source = InputSource::File(path.clone());
+        assert_eq!(
+            try_read_grouped_file(&source),
+            Ok(vec![
+                String::from("a\nb"),
+                String::from("c"),
+                String::from("d\ne")
+            ])
+        );
+
+        let err = try_parse_grouped_file(&source, |g| {
+            if g.contains('d') {
+                Err("no d allowed")
+            } else {
+                Ok(g.len())
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.location, Location::Group(3));
+        assert_eq!(err.text, "d\ne");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let err = try_read_file_linewise("does/not/exist.txt").unwrap_err();
+        assert_eq!(err.location, Location::Input);
+        assert_eq!(err.input, "does/not/exist.txt");
+    }
 }
@@ -0,0 +1,105 @@
+use crate::input::InputSource;
+use std::error;
+use std::fmt;
+
+/// Position inside an input at which parsing failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    /// The input as a whole, e.g. when it could not be read at all
+    Input,
+    /// 1-based line number
+    Line(usize),
+    /// 1-based index of a block of lines separated by blank lines
+    Group(usize),
+}
+
+/// Error raised when an input can't be read or contains malformed data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub input: String,
+    pub location: Location,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new<M: fmt::Display>(
+        input: &InputSource,
+        location: Location,
+        text: &str,
+        message: M,
+    ) -> ParseError {
+        ParseError {
+            input: input.to_string(),
+            location,
+            text: String::from(text),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Location::Input => write!(f, "{}: {}", self.input, self.message),
+            Location::Line(n) => write!(
+                f,
+                "{}:{}: {} in {:?}",
+                self.input, n, self.message, self.text
+            ),
+            Location::Group(n) => write!(
+                f,
+                "{}, group {}: {} in {:?}",
+                self.input, n, self.message, self.text
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Error returned by a solver.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input could not be read or parsed
+    Parse(ParseError),
+    /// The input is well formed but violates an assumption of the puzzle
+    InvalidInput(String),
+    /// The puzzle has no solution for the given input
+    NoSolution(String),
+}
+
+impl Error {
+    pub fn invalid_input<M: Into<String>>(message: M) -> Error {
+        Error::InvalidInput(message.into())
+    }
+
+    pub fn no_solution<M: Into<String>>(message: M) -> Error {
+        Error::NoSolution(message.into())
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
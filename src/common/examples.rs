@@ -0,0 +1,384 @@
+use crate::answers::{answer_from_toml, answer_to_toml, parse_key, year_days, year_table};
+use crate::error::{Location, ParseError};
+use crate::input::{source_dir, InputSource};
+use crate::solution::Answer;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Location of the example answers for the fixtures checked into this repository.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples.toml")
+}
+
+/// Example answer found in a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedAnswer {
+    pub part: u32,
+    /// Index of the code block the answer belongs to
+    pub block: usize,
+    pub answer: String,
+}
+
+/// Code blocks and example answers of a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    pub blocks: Vec<String>,
+    pub answers: Vec<ExtractedAnswer>,
+}
+
+fn decode_html(html: &str) -> String {
+    lazy_static! {
+        static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    }
+    TAG.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extracts the examples from a saved puzzle page.
+///
+/// Every part is described in its own `<article>`. The last emphasized code (`<code><em>`) of an
+/// article is taken as the answer of that part for the first code block of the same article, or
+/// for the last block of an earlier part if the article has none, as the second part usually
+/// reuses the example of the first.
+pub fn extract(html: &str) -> Extracted {
+    lazy_static! {
+        static ref ARTICLE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+        static ref BLOCK: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+        static ref ANSWER: Regex =
+            Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+    }
+
+    let mut articles = ARTICLE
+        .captures_iter(html)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect::<Vec<&str>>();
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let mut ret = Extracted::default();
+    for (i, article) in articles.iter().enumerate() {
+        let first_block = ret.blocks.len();
+        for c in BLOCK.captures_iter(article) {
+            ret.blocks.push(decode_html(&c[1]));
+        }
+
+        let block = if ret.blocks.len() > first_block {
+            first_block
+        } else if first_block > 0 {
+            first_block - 1
+        } else {
+            continue;
+        };
+        let answer = ANSWER
+            .captures_iter(article)
+            .last()
+            .map(|c| decode_html(c.get(1).or_else(|| c.get(2)).unwrap().as_str()));
+        if let Some(answer) = answer {
+            ret.answers.push(ExtractedAnswer {
+                part: i as u32 + 1,
+                block,
+                answer: String::from(answer.trim()),
+            });
+        }
+    }
+    ret
+}
+
+/// Fixture of a day together with the answers of the parts it is an example for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub year: u32,
+    pub day: u32,
+    /// File name of the fixture without the `.txt` extension
+    pub name: String,
+    pub answers: BTreeMap<u32, Answer>,
+}
+
+impl Example {
+    /// Path of the fixture, `root` is the directory of the examples file.
+    pub fn path(&self, root: &Path) -> PathBuf {
+        source_dir(root, self.year, self.day).join(format!("{}.txt", self.name))
+    }
+}
+
+/// Expected answers of the example fixtures, read from a TOML file with a table per fixture:
+///
+/// ```toml
+/// [day01.input_example_01]
+/// part1 = 514579
+/// part2 = 241861950
+/// ```
+///
+/// The fixture of this entry is `src/day01/input_example_01.txt` next to the file. Days of later
+/// years are nested in a table named after the year, like in the answers file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    examples: BTreeMap<(u32, u32, String), BTreeMap<u32, Answer>>,
+}
+
+impl Examples {
+    pub fn new() -> Examples {
+        Examples {
+            examples: BTreeMap::new(),
+        }
+    }
+
+    /// Reads the examples file at `path`, a missing file has no examples.
+    pub fn open(path: &Path) -> Result<Examples, ParseError> {
+        let source = InputSource::File(path.to_path_buf());
+        match fs::read_to_string(path) {
+            Ok(text) => Examples::from_toml(&source, &text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Examples::new()),
+            Err(e) => Err(ParseError::new(&source, Location::Input, "", e)),
+        }
+    }
+
+    /// Parses the content of an examples file, `source` is only used for error messages.
+    pub fn from_toml(source: &InputSource, text: &str) -> Result<Examples, ParseError> {
+        let error = |message: String| ParseError::new(source, Location::Input, "", message);
+
+        let value = text
+            .parse::<toml::Value>()
+            .map_err(|e| error(e.to_string()))?;
+
+        let mut ret = Examples::new();
+        let empty = toml::value::Table::new();
+        let days = year_days(value.as_table().unwrap_or(&empty)).map_err(error)?;
+        for (year, day, day_key, fixtures) in days {
+            let fixtures = fixtures
+                .as_table()
+                .ok_or_else(|| error(format!("{}: expected a table of fixtures", day_key)))?;
+
+            for (name, parts) in fixtures {
+                let parts = parts.as_table().ok_or_else(|| {
+                    error(format!("{}.{}: expected a table of parts", day_key, name))
+                })?;
+                for (part_key, value) in parts {
+                    let key = format!("{}.{}.{}", day_key, name, part_key);
+                    let part = parse_key(part_key, "part", 2)
+                        .ok_or_else(|| error(format!("{}: expected part1 or part2", key)))?;
+                    let answer = answer_from_toml(value)
+                        .ok_or_else(|| error(format!("{}: expected a number or a string", key)))?;
+                    ret.insert(year, day, name, part, answer);
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut days = toml::value::Table::new();
+        for ((year, day, name), answers) in &self.examples {
+            let parts = answers
+                .iter()
+                .filter_map(|(part, a)| Some((format!("part{}", part), answer_to_toml(a)?)))
+                .collect::<toml::value::Table>();
+            year_table(&mut days, *year)
+                .entry(format!("day{:02}", day))
+                .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+                .as_table_mut()
+                .unwrap()
+                .insert(name.clone(), toml::Value::Table(parts));
+        }
+        toml::to_string(&toml::Value::Table(days)).unwrap()
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, name: &str, part: u32, answer: Answer) {
+        self.examples
+            .entry((year, day, String::from(name)))
+            .or_default()
+            .insert(part, answer);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Example> + '_ {
+        self.examples
+            .iter()
+            .map(|((year, day, name), answers)| Example {
+                year: *year,
+                day: *day,
+                name: name.clone(),
+                answers: answers.clone(),
+            })
+    }
+}
+
+/// Reads an answer as a number if it is one.
+fn parse_answer(answer: &str) -> Answer {
+    answer
+        .parse::<i128>()
+        .map_or_else(|_| Answer::from(answer), Answer::from)
+}
+
+/// Writes the code blocks of `extracted` that have an answer as fixtures of a day below `root`
+/// and records their answers in `examples`. A block with the same content as an existing fixture
+/// reuses that file. Returns the fixtures that were created.
+pub fn save(
+    root: &Path,
+    year: u32,
+    day: u32,
+    extracted: &Extracted,
+    examples: &mut Examples,
+) -> io::Result<Vec<PathBuf>> {
+    let dir = source_dir(root, year, day);
+    fs::create_dir_all(&dir)?;
+
+    let mut fixtures = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let is_fixture = path.extension().is_some_and(|e| e == "txt")
+            && path.file_name().is_some_and(|n| n != "input.txt");
+        if is_fixture {
+            fixtures.push((path.clone(), fs::read_to_string(&path)?));
+        }
+    }
+    fixtures.sort();
+
+    let mut created = Vec::new();
+    for answer in &extracted.answers {
+        let content = &extracted.blocks[answer.block];
+        let path = match fixtures.iter().find(|(_, c)| c == content) {
+            Some((path, _)) => path.clone(),
+            None => {
+                let path = (1..)
+                    .map(|i| dir.join(format!("input_example_{:02}.txt", i)))
+                    .find(|p| !p.exists())
+                    .unwrap();
+                fs::write(&path, content)?;
+                fixtures.push((path.clone(), content.clone()));
+                created.push(path.clone());
+                path
+            }
+        };
+
+        let name = path.file_stem().unwrap().to_string_lossy();
+        examples.insert(year, day, &name, answer.part, parse_answer(&answer.answer));
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Report Repair ---</h2>
+<p>For example, suppose your expense report contained the following:</p>
+<pre><code>1721
+979
+366
+299
+675
+1456
+</code></pre>
+<p>In this list, the two entries that sum to <code>2020</code> are <code>1721</code> and
+<code>299</code>. Multiplying them together produces <code>1721 * 299 = 514579</code>, so the
+correct answer is <code><em>514579</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>319531</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the above example again, the three entries that sum to <code>2020</code> are
+<code>979</code>, <code>366</code>, and <code>675</code>. Multiplying them together produces the
+answer, <code><em>241861950</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_examples_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_extract() {
+        let extracted = extract(PAGE);
+        assert_eq!(extracted.blocks, vec!["1721\n979\n366\n299\n675\n1456\n"]);
+        assert_eq!(
+            extracted.answers,
+            vec![
+                ExtractedAnswer {
+                    part: 1,
+                    block: 0,
+                    answer: String::from("514579")
+                },
+                ExtractedAnswer {
+                    part: 2,
+                    block: 0,
+                    answer: String::from("241861950")
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_markup() {
+        let page = "<article><pre><code>a &lt;<em>b</em>&gt; &amp;c</code></pre>\
+                    <em><code>x,y</code></em></article>\
+                    <article><pre><code>1</code></pre><pre><code>2</code></pre></article>";
+        let extracted = extract(page);
+        assert_eq!(extracted.blocks, vec!["a <b> &c", "1", "2"]);
+        assert_eq!(
+            extracted.answers,
+            vec![ExtractedAnswer {
+                part: 1,
+                block: 0,
+                answer: String::from("x,y")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_save() {
+        let root = temp_dir("save");
+        let mut examples = Examples::new();
+
+        let extracted = extract(PAGE);
+        let created = save(&root, 2020, 1, &extracted, &mut examples).unwrap();
+        let path = root.join("src/day01/input_example_01.txt");
+        assert_eq!(created, vec![path.clone()]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), extracted.blocks[0]);
+
+        // Saving again reuses the fixture
+        assert!(save(&root, 2020, 1, &extracted, &mut examples)
+            .unwrap()
+            .is_empty());
+
+        let example = examples.iter().next().unwrap();
+        assert_eq!(example.path(&root), path);
+        assert_eq!(example.answers[&1], Answer::from(514579));
+        assert_eq!(example.answers[&2], Answer::from(241861950));
+
+        let text = examples.to_toml();
+        assert_eq!(
+            text,
+            "[day01.input_example_01]\npart1 = 514579\npart2 = 241861950\n"
+        );
+        let source = InputSource::from("examples.toml");
+        assert_eq!(Examples::from_toml(&source, &text).unwrap(), examples);
+
+        save(&root, 2021, 1, &extracted, &mut examples).unwrap();
+        assert!(root.join("src/y2021/day01/input_example_01.txt").exists());
+        let text = examples.to_toml();
+        assert!(text.contains("[2021.day01.input_example_01]\n"));
+        assert_eq!(Examples::from_toml(&source, &text).unwrap(), examples);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_examples() {
+        let source = InputSource::from("examples.toml");
+        assert!(Examples::from_toml(&source, "[day01.a]\npart3 = 1\n").is_err());
+        assert!(Examples::from_toml(&source, "[day01]\na = 1\n").is_err());
+        assert!(Examples::from_toml(&source, "[dayXX.a]\npart1 = 1\n").is_err());
+    }
+}
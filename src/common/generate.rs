@@ -0,0 +1,119 @@
+//! Random puzzle inputs of a requested size, used for scale tests and for cross-checking solvers.
+//!
+//! Every day has a `generate` module with a [`Generator`]. Where an input can be constructed
+//! around its answer (like a list with exactly one pair summing to 2020), the answer is returned
+//! along with the input. Parts without a known answer can still be compared between solvers.
+
+use crate::solution::{Answer, FIRST_YEAR};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+pub use rand::rngs::StdRng;
+
+/// A generated input together with the answers known from its construction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Generated {
+    pub fn new(input: String, part1: Option<Answer>, part2: Option<Answer>) -> Generated {
+        Generated {
+            input,
+            part1,
+            part2,
+        }
+    }
+
+    /// Known answer of a part, `None` if it is unknown or the part does not exist.
+    pub fn answer(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+pub trait Generator: Send + Sync {
+    fn year(&self) -> u32 {
+        FIRST_YEAR
+    }
+
+    fn day(&self) -> u32;
+
+    /// Size used when none is requested, comparable to the real puzzle input.
+    fn default_size(&self) -> usize;
+
+    /// Generates an input of about `size` elements. What an element is depends on the day, like
+    /// the lines of a list or the side length of a grid. Sizes the day cannot produce are clamped.
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated;
+}
+
+/// Random number generator producing the same inputs for the same seed.
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Seed for a new input, to be reported so that the same input can be generated again.
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+/// Random word of lowercase letters.
+pub fn word(rng: &mut StdRng, len: usize) -> String {
+    (0..len)
+        .map(|_| rng.gen_range(b'a'..=b'z') as char)
+        .collect()
+}
+
+/// `n` distinct random words of `len` letters. There must be enough of them: 26^len >= n.
+pub fn distinct_words(rng: &mut StdRng, n: usize, len: usize) -> Vec<String> {
+    let mut words = std::collections::HashSet::new();
+    while words.len() < n {
+        words.insert(word(rng, len));
+    }
+    let mut words = words.into_iter().collect::<Vec<String>>();
+    words.sort();
+    words.shuffle(rng);
+    words
+}
+
+/// Joins lines into the text of an input file.
+pub fn lines<I: IntoIterator<Item = S>, S: AsRef<str>>(lines: I) -> String {
+    lines.into_iter().fold(String::new(), |mut text, line| {
+        text.push_str(line.as_ref());
+        text.push('\n');
+        text
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rng_is_reproducible() {
+        let a = word(&mut rng(7), 10);
+        let b = word(&mut rng(7), 10);
+        assert_eq!(a, b);
+        assert!(a.chars().all(|c| c.is_ascii_lowercase()));
+    }
+
+    #[test]
+    fn test_distinct_words() {
+        let words = distinct_words(&mut rng(1), 20, 2);
+        let unique = words.iter().collect::<std::collections::HashSet<_>>();
+        assert_eq!(words.len(), 20);
+        assert_eq!(unique.len(), 20);
+        assert!(words.iter().all(|w| w.len() == 2));
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines(vec!["a", "b"]), "a\nb\n");
+        assert_eq!(lines(Vec::<String>::new()), "");
+    }
+}
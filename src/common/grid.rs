@@ -0,0 +1,372 @@
+use crate::error::{Location, ParseError};
+use crate::input::InputSource;
+use crate::reader;
+use nalgebra::{Point2, Vector2};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the eight cells surrounding a cell, row by row starting at the top left.
+pub fn directions() -> [Vector2<i32>; 8] {
+    [
+        Vector2::new(-1, -1),
+        Vector2::new(0, -1),
+        Vector2::new(1, -1),
+        Vector2::new(-1, 0),
+        Vector2::new(1, 0),
+        Vector2::new(-1, 1),
+        Vector2::new(0, 1),
+        Vector2::new(1, 1),
+    ]
+}
+
+/// Rectangular grid of cells stored row by row. `x` grows to the right, `y` downwards.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: i32,
+    height: i32,
+    cells: Vec<T>,
+}
+
+fn parse_row<T, E, F>(line: &str, width: Option<usize>, f: &F) -> Result<Vec<T>, String>
+where
+    F: Fn(char) -> Result<T, E>,
+    E: fmt::Display,
+{
+    let row = line
+        .chars()
+        .enumerate()
+        .map(|(i, c)| f(c).map_err(|e| format!("column {}: {}", i + 1, e)))
+        .collect::<Result<Vec<T>, String>>()?;
+
+    match width {
+        Some(w) if w != row.len() => Err(format!("expected {} cells, got {}", w, row.len())),
+        _ => Ok(row),
+    }
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` doesn't hold exactly `width * height` elements.
+    pub fn new(width: i32, height: i32, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            (width * height) as usize,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from equally long lines, `f` maps every character to a cell.
+    pub fn from_lines<S, E, F>(lines: &[S], f: F) -> Result<Grid<T>, String>
+    where
+        S: AsRef<str>,
+        F: Fn(char) -> Result<T, E>,
+        E: fmt::Display,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+
+        for (i, line) in lines.iter().enumerate() {
+            let mut row =
+                parse_row(line.as_ref(), width, &f).map_err(|e| format!("row {}, {}", i + 1, e))?;
+            width = Some(row.len());
+            cells.append(&mut row);
+        }
+
+        match width {
+            Some(w) if w > 0 => Ok(Grid::new(w as i32, lines.len() as i32, cells)),
+            _ => Err(String::from("grid is empty")),
+        }
+    }
+
+    /// Reads a grid from the non-empty lines of `source`, `f` maps every character to a cell.
+    pub fn parse<E, F>(source: &InputSource, f: F) -> Result<Grid<T>, ParseError>
+    where
+        F: Fn(char) -> Result<T, E>,
+        E: fmt::Display,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for line in reader::lines(source)? {
+            let (n, line) = line.map_err(|e| ParseError::new(source, Location::Input, "", e))?;
+            let mut row = parse_row(&line, width, &f)
+                .map_err(|e| ParseError::new(source, Location::Line(n), &line, e))?;
+            width = Some(row.len());
+            height += 1;
+            cells.append(&mut row);
+        }
+
+        match width {
+            Some(w) => Ok(Grid::new(w as i32, height, cells)),
+            None => Err(ParseError::new(
+                source,
+                Location::Input,
+                "",
+                "grid is empty",
+            )),
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn index_of(&self, p: Point2<i32>) -> usize {
+        (p[1] * self.width + p[0]) as usize
+    }
+
+    pub fn is_inside(&self, p: Point2<i32>) -> bool {
+        p[0] >= 0 && p[0] < self.width && p[1] >= 0 && p[1] < self.height
+    }
+
+    pub fn get(&self, p: Point2<i32>) -> Option<&T> {
+        if self.is_inside(p) {
+            Some(&self.cells[self.index_of(p)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point2<i32>) -> Option<&mut T> {
+        if self.is_inside(p) {
+            let i = self.index_of(p);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// Treats the grid as repeating endlessly in every direction.
+    pub fn get_wrapping(&self, p: Point2<i32>) -> &T {
+        let q = Point2::new(p[0].rem_euclid(self.width), p[1].rem_euclid(self.height));
+        &self.cells[self.index_of(q)]
+    }
+
+    /// All positions of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point2<i32>> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| Point2::new(i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.cells.iter().filter(|c| *c == value).count()
+    }
+
+    /// The up to eight cells surrounding `p` that lie inside the grid.
+    pub fn neighbors(&self, p: Point2<i32>) -> impl Iterator<Item = (Point2<i32>, &T)> {
+        directions()
+            .iter()
+            .map(|d| p + d)
+            .filter_map(|q| self.get(q).map(|c| (q, c)))
+            .collect::<Vec<(Point2<i32>, &T)>>()
+            .into_iter()
+    }
+
+    /// The cells seen when walking from `p` (exclusive) in steps of `dir` until leaving the grid.
+    pub fn ray(
+        &self,
+        p: Point2<i32>,
+        dir: Vector2<i32>,
+    ) -> impl Iterator<Item = (Point2<i32>, &T)> {
+        let mut q = p;
+        std::iter::from_fn(move || {
+            q += dir;
+            self.get(q).map(|c| (q, c))
+        })
+    }
+
+    /// Applies `f` to every cell, keeping the layout.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(Point2<i32>, &T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.positions().map(|p| f(p, &self[p])).collect(),
+        }
+    }
+
+    fn rearranged<F>(&self, width: i32, height: i32, source: F) -> Grid<T>
+    where
+        T: Clone,
+        F: Fn(i32, i32) -> Point2<i32>,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(self[source(x, y)].clone());
+            }
+        }
+        Grid::new(width, height, cells)
+    }
+
+    /// Rotates the grid clockwise by 90 degrees.
+    pub fn rotate_right(&mut self)
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        *self = self.rearranged(self.height, self.width, |x, y| Point2::new(y, h - 1 - x));
+    }
+
+    /// Rotates the grid counterclockwise by 90 degrees.
+    pub fn rotate_left(&mut self)
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        *self = self.rearranged(self.height, self.width, |x, y| Point2::new(w - 1 - y, x));
+    }
+
+    /// Mirrors the grid along the vertical axis, i.e. swaps left and right.
+    pub fn flip_x(&mut self)
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        *self = self.rearranged(self.width, self.height, |x, y| Point2::new(w - 1 - x, y));
+    }
+
+    /// Mirrors the grid along the horizontal axis, i.e. swaps top and bottom.
+    pub fn flip_y(&mut self)
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        *self = self.rearranged(self.width, self.height, |x, y| Point2::new(x, h - 1 - y));
+    }
+}
+
+impl<T> Index<Point2<i32>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<i32>) -> &T {
+        match self.get(p) {
+            Some(c) => c,
+            None => panic!(
+                "({}, {}) is outside of the {}x{} grid",
+                p[0], p[1], self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2<i32>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2<i32>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(c) => c,
+            None => panic!(
+                "({}, {}) is outside of the {}x{} grid",
+                p[0], p[1], width, height
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", self[Point2::new(x, y)])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::Infallible;
+
+    fn grid(lines: &[&str]) -> Grid<char> {
+        Grid::from_lines(lines, Ok::<char, Infallible>).unwrap()
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[Point2::new(2, 1)], 'f');
+        assert_eq!(g.to_string(), "abc\ndef");
+
+        assert_eq!(
+            Grid::<char>::from_lines(&["abc", "de"], Ok::<char, Infallible>),
+            Err(String::from("row 2, expected 3 cells, got 2"))
+        );
+        assert_eq!(
+            Grid::from_lines(&["..", ".x"], |c| match c {
+                '.' => Ok(false),
+                _ => Err(format!("unknown cell {:?}", c)),
+            }),
+            Err(String::from("row 2, column 2: unknown cell 'x'"))
+        );
+        assert!(Grid::<char>::from_lines(&[] as &[&str], Ok::<char, Infallible>).is_err());
+    }
+
+    #[test]
+    fn test_access() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!(g.get(Point2::new(0, 0)), Some(&'a'));
+        assert_eq!(g.get(Point2::new(3, 0)), None);
+        assert_eq!(g.get(Point2::new(0, -1)), None);
+        assert_eq!(*g.get_wrapping(Point2::new(4, 0)), 'b');
+        assert_eq!(*g.get_wrapping(Point2::new(-1, -1)), 'f');
+        assert_eq!(g.count(&'a'), 1);
+    }
+
+    #[test]
+    fn test_neighbors_and_rays() {
+        let g = grid(&["abc", "def", "ghi"]);
+        let corner = g
+            .neighbors(Point2::new(0, 0))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(corner, "bde");
+        assert_eq!(g.neighbors(Point2::new(1, 1)).count(), 8);
+
+        let diagonal = g
+            .ray(Point2::new(0, 0), Vector2::new(1, 1))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(diagonal, "ei");
+        assert_eq!(g.ray(Point2::new(2, 2), Vector2::new(1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let mut g = grid(&["abc", "def"]);
+        g.rotate_right();
+        assert_eq!(g.to_string(), "da\neb\nfc");
+        g.rotate_left();
+        assert_eq!(g.to_string(), "abc\ndef");
+        g.flip_x();
+        assert_eq!(g.to_string(), "cba\nfed");
+        g.flip_y();
+        assert_eq!(g.to_string(), "fed\ncba");
+    }
+}
@@ -0,0 +1,309 @@
+use crate::solution::FIRST_YEAR;
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing to a directory with puzzle inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Environment variable overriding the directory downloaded inputs are cached in.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Directory downloaded inputs are cached in: `$AOC_CACHE_DIR`, or `aoc` inside the user's cache
+/// directory (`$XDG_CACHE_HOME` or `~/.cache`).
+pub fn default_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(CACHE_DIR_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .map(|dir| dir.join("aoc"))
+}
+
+/// Directory of the sources and fixtures of a day below `root`, the directory of `Cargo.toml`:
+/// `src/dayNN` for the first year and `src/yYYYY/dayNN` for later ones.
+pub fn source_dir(root: &Path, year: u32, day: u32) -> PathBuf {
+    let src = root.join("src");
+    let src = if year == FIRST_YEAR {
+        src
+    } else {
+        src.join(format!("y{}", year))
+    };
+    src.join(format!("day{:02}", day))
+}
+
+/// Name of a day inside input and cache directories without extension: `dayNN` for the first
+/// year and `YYYY/dayNN` for later ones.
+fn input_name(year: u32, day: u32) -> PathBuf {
+    let name = PathBuf::from(format!("day{:02}", day));
+    if year == FIRST_YEAR {
+        name
+    } else {
+        Path::new(&year.to_string()).join(name)
+    }
+}
+
+/// Where the input of a puzzle is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// `-` selects stdin, everything else is treated as a path.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut ret = String::new();
+        self.open()?.read_to_string(&mut ret)?;
+        Ok(ret)
+    }
+}
+
+impl From<&str> for InputSource {
+    fn from(filename: &str) -> InputSource {
+        InputSource::File(PathBuf::from(filename))
+    }
+}
+
+impl From<&InputSource> for InputSource {
+    fn from(source: &InputSource) -> InputSource {
+        source.clone()
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Finds the input file of a day.
+///
+/// An input directory (from `AOC_INPUT_DIR` or set explicitly) is searched for `dayNN.txt`
+/// and `dayNN/input.txt`, followed by the cache of downloaded inputs. If none of them exists, the
+/// input checked into this repository is used, independent of the current working directory.
+/// Days of years after the first are looked for in a subdirectory named after the year, like
+/// `2021/day01.txt`.
+#[derive(Clone, Debug, Default)]
+pub struct InputResolver {
+    input_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl InputResolver {
+    pub fn new() -> InputResolver {
+        InputResolver {
+            input_dir: None,
+            cache_dir: None,
+        }
+    }
+
+    pub fn from_env() -> InputResolver {
+        InputResolver {
+            input_dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            cache_dir: default_cache_dir(),
+        }
+    }
+
+    pub fn with_input_dir<P: AsRef<Path>>(mut self, dir: P) -> InputResolver {
+        self.input_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn with_cache_dir<P: AsRef<Path>>(mut self, dir: P) -> InputResolver {
+        self.cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Where the downloaded input of a day is stored, if there is a cache directory.
+    pub fn cached_path(&self, year: u32, day: u32) -> Option<PathBuf> {
+        self.cache_dir
+            .as_ref()
+            .map(|dir| dir.join(input_name(year, day)).with_extension("txt"))
+    }
+
+    fn candidates(&self, year: u32, day: u32) -> Vec<PathBuf> {
+        let mut ret = Vec::new();
+
+        if let Some(dir) = &self.input_dir {
+            let name = dir.join(input_name(year, day));
+            ret.push(name.with_extension("txt"));
+            ret.push(name.join("input.txt"));
+        }
+
+        if let Some(path) = self.cached_path(year, day) {
+            ret.push(path);
+        }
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        ret.push(source_dir(root, year, day).join("input.txt"));
+
+        ret
+    }
+
+    /// Returns the input of a day. An explicitly given argument (a path or `-` for stdin)
+    /// always takes precedence over the search.
+    pub fn resolve(&self, year: u32, day: u32, explicit: Option<&str>) -> io::Result<InputSource> {
+        if let Some(arg) = explicit {
+            return Ok(InputSource::from_arg(arg));
+        }
+
+        let candidates = self.candidates(year, day);
+        match candidates.iter().find(|p| p.is_file()) {
+            Some(p) => Ok(InputSource::File(p.clone())),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "no input found for day {} of {}, looked at: {}",
+                    day,
+                    year,
+                    candidates
+                        .iter()
+                        .map(|p| p.display().to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_input_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_explicit_input() {
+        let resolver = InputResolver::new();
+        assert_eq!(
+            resolver
+                .resolve(FIRST_YEAR, 7, Some("my_input.txt"))
+                .unwrap(),
+            InputSource::File(PathBuf::from("my_input.txt"))
+        );
+        assert_eq!(
+            resolver.resolve(FIRST_YEAR, 7, Some("-")).unwrap(),
+            InputSource::Stdin
+        );
+    }
+
+    #[test]
+    fn test_input_dir() {
+        let dir = temp_dir("dir");
+        fs::write(dir.join("day03.txt"), "..#\n").unwrap();
+        fs::create_dir_all(dir.join("day04")).unwrap();
+        fs::write(dir.join("day04").join("input.txt"), "a\n").unwrap();
+
+        let resolver = InputResolver::new().with_input_dir(&dir);
+        assert_eq!(
+            resolver.resolve(FIRST_YEAR, 3, None).unwrap(),
+            InputSource::File(dir.join("day03.txt"))
+        );
+        assert_eq!(
+            resolver.resolve(FIRST_YEAR, 4, None).unwrap(),
+            InputSource::File(dir.join("day04").join("input.txt"))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let (dir, cache) = (temp_dir("precedence"), temp_dir("cache"));
+        fs::write(cache.join("day01.txt"), "1\n").unwrap();
+        fs::write(cache.join("day02.txt"), "2\n").unwrap();
+        fs::write(dir.join("day02.txt"), "2\n").unwrap();
+
+        let resolver = InputResolver::new()
+            .with_input_dir(&dir)
+            .with_cache_dir(&cache);
+        assert_eq!(
+            resolver.cached_path(FIRST_YEAR, 1),
+            Some(cache.join("day01.txt"))
+        );
+        assert_eq!(
+            resolver.resolve(FIRST_YEAR, 1, None).unwrap(),
+            InputSource::File(cache.join("day01.txt"))
+        );
+        assert_eq!(
+            resolver.resolve(FIRST_YEAR, 2, None).unwrap(),
+            InputSource::File(dir.join("day02.txt"))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_later_year() {
+        let (dir, cache) = (temp_dir("year"), temp_dir("year_cache"));
+        fs::create_dir_all(dir.join("2021")).unwrap();
+        fs::write(dir.join("2021").join("day01.txt"), "1\n").unwrap();
+        fs::write(dir.join("day01.txt"), "1\n").unwrap();
+
+        let resolver = InputResolver::new()
+            .with_input_dir(&dir)
+            .with_cache_dir(&cache);
+        assert_eq!(
+            resolver.resolve(2021, 1, None).unwrap(),
+            InputSource::File(dir.join("2021").join("day01.txt"))
+        );
+        assert_eq!(
+            resolver.cached_path(2021, 2),
+            Some(cache.join("2021").join("day02.txt"))
+        );
+        assert!(resolver.resolve(2021, 2, None).is_err());
+
+        let root = Path::new("aoc");
+        assert_eq!(source_dir(root, FIRST_YEAR, 3), root.join("src/day03"));
+        assert_eq!(source_dir(root, 2021, 3), root.join("src/y2021/day03"));
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_repository_fallback() {
+        let dir = temp_dir("empty");
+        let resolver = InputResolver::new().with_input_dir(&dir);
+        let source = resolver.resolve(FIRST_YEAR, 1, None).unwrap();
+        assert!(source.to_string().ends_with("input.txt"));
+        assert!(!source.read_to_string().unwrap().is_empty());
+
+        assert!(resolver.resolve(FIRST_YEAR, 26, None).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
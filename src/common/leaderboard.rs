@@ -0,0 +1,402 @@
+use crate::error::{Location, ParseError};
+use crate::input::InputSource;
+use crate::scaffold::FIRST_EVENT;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// Number of days of an event.
+pub const DAYS: u32 = 25;
+
+/// Stars of a member on one day, as seconds since the epoch at which they were earned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub part1: u64,
+    pub part2: Option<u64>,
+}
+
+impl DayStars {
+    /// Time of the star of a part, if it was earned.
+    pub fn get(&self, part: u32) -> Option<u64> {
+        match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None,
+        }
+    }
+
+    /// Seconds between the first and the second star.
+    pub fn delta(&self) -> Option<u64> {
+        self.part2.map(|t| t.saturating_sub(self.part1))
+    }
+}
+
+/// Member of a private leaderboard with the scores computed by the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub global_score: u64,
+    /// Time of the last star, 0 without any star
+    pub last_star_ts: u64,
+    pub days: BTreeMap<u32, DayStars>,
+}
+
+impl Member {
+    /// Name as shown on the website, which numbers anonymous users by their id.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Time of the star of a part of a day, if it was earned.
+    pub fn star(&self, day: u32, part: u32) -> Option<u64> {
+        self.days.get(&day).and_then(|stars| stars.get(part))
+    }
+}
+
+/// Sums over all members of a leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Totals {
+    pub members: usize,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Number of first and second stars of every day with at least one star
+    pub stars_per_day: BTreeMap<u32, (u32, u32)>,
+}
+
+/// Private leaderboard as served as JSON by `/<year>/leaderboard/private/view/<id>.json`:
+///
+/// ```json
+/// {
+///   "event": "2020",
+///   "owner_id": 1001,
+///   "members": {
+///     "1001": {
+///       "id": 1001, "name": "Andurian", "stars": 2, "local_score": 4, "global_score": 0,
+///       "last_star_ts": 1606799400,
+///       "completion_day_level": {
+///         "1": { "1": { "get_star_ts": 1606799100 }, "2": { "get_star_ts": 1606799400 } }
+///       }
+///     }
+///   }
+/// }
+/// ```
+///
+/// Older documents wrote ids and timestamps as strings, which are accepted as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub event: u32,
+    pub owner_id: u64,
+    /// Ordered by id
+    pub members: Vec<Member>,
+}
+
+/// Reads a number written as a JSON number or as a string.
+fn number(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn parse_member(value: &Value) -> Result<Member, String> {
+    let field =
+        |key: &str| number(&value[key]).ok_or_else(|| format!("expected a number for {:?}", key));
+
+    let mut days = BTreeMap::new();
+    let levels = match &value["completion_day_level"] {
+        Value::Object(levels) => levels,
+        _ => {
+            return Err(String::from(
+                "expected an object for \"completion_day_level\"",
+            ))
+        }
+    };
+    for (day_key, parts) in levels {
+        let day = day_key
+            .parse::<u32>()
+            .ok()
+            .filter(|d| (1..=DAYS).contains(d))
+            .ok_or_else(|| format!("invalid day {:?}", day_key))?;
+        let star = |part: &str| match &parts[part] {
+            Value::Null => Ok(None),
+            level => number(&level["get_star_ts"]).map(Some).ok_or_else(|| {
+                format!(
+                    "day {} part {}: expected a number for \"get_star_ts\"",
+                    day, part
+                )
+            }),
+        };
+        let part1 =
+            star("1")?.ok_or_else(|| format!("day {}: second star without the first", day))?;
+        days.insert(
+            day,
+            DayStars {
+                part1,
+                part2: star("2")?,
+            },
+        );
+    }
+
+    Ok(Member {
+        id: field("id")?,
+        name: value["name"].as_str().map(String::from),
+        stars: u32::try_from(field("stars")?).map_err(|_| String::from("too many \"stars\""))?,
+        local_score: field("local_score")?,
+        global_score: number(&value["global_score"]).unwrap_or(0),
+        last_star_ts: field("last_star_ts")?,
+        days,
+    })
+}
+
+impl Leaderboard {
+    pub fn parse(source: &InputSource) -> Result<Leaderboard, ParseError> {
+        let text = source
+            .read_to_string()
+            .map_err(|e| ParseError::new(source, Location::Input, "", e))?;
+        Leaderboard::from_json(source, &text)
+    }
+
+    /// Parses a leaderboard document, `source` is only used for error messages.
+    pub fn from_json(source: &InputSource, text: &str) -> Result<Leaderboard, ParseError> {
+        let error = |message: String| ParseError::new(source, Location::Input, "", message);
+
+        let value = serde_json::from_str::<Value>(text).map_err(|e| {
+            ParseError::new(
+                source,
+                Location::Line(e.line()),
+                text.lines().nth(e.line().max(1) - 1).unwrap_or("").trim(),
+                e,
+            )
+        })?;
+
+        let members = match &value["members"] {
+            Value::Object(members) => members,
+            _ => return Err(error(String::from("expected an object of members"))),
+        };
+        let event = number(&value["event"])
+            .ok_or_else(|| error(String::from("expected the year as \"event\"")))?;
+        let event = u32::try_from(event)
+            .ok()
+            .filter(|e| *e >= FIRST_EVENT)
+            .ok_or_else(|| error(format!("there are no puzzles of {}", event)))?;
+        let mut ret = Leaderboard {
+            event,
+            owner_id: number(&value["owner_id"]).unwrap_or(0),
+            members: members
+                .iter()
+                .map(|(key, member)| {
+                    parse_member(member).map_err(|e| error(format!("member {}: {}", key, e)))
+                })
+                .collect::<Result<Vec<Member>, ParseError>>()?,
+        };
+        ret.members.sort_by_key(|m| m.id);
+        Ok(ret)
+    }
+
+    pub fn member(&self, id: u64) -> Option<&Member> {
+        self.members.iter().find(|m| m.id == id)
+    }
+
+    /// Members in the order of the website: by local score, then by stars, then by who got
+    /// their last star first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut ret = self.members.iter().collect::<Vec<&Member>>();
+        ret.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        ret
+    }
+
+    /// Points every member earned per day. Every star is worth as many points as there are
+    /// members for the first to get it, one less for the second and so on, which adds up to the
+    /// local score.
+    pub fn day_scores(&self) -> BTreeMap<u64, BTreeMap<u32, u64>> {
+        let mut ret = self
+            .members
+            .iter()
+            .map(|m| (m.id, BTreeMap::new()))
+            .collect::<BTreeMap<u64, BTreeMap<u32, u64>>>();
+
+        for day in 1..=DAYS {
+            for part in 1..=2 {
+                let mut solvers = self
+                    .members
+                    .iter()
+                    .filter_map(|m| m.star(day, part).map(|t| (t, m.id)))
+                    .collect::<Vec<(u64, u64)>>();
+                solvers.sort();
+                for (rank, (_, id)) in solvers.into_iter().enumerate() {
+                    let points = (self.members.len() - rank) as u64;
+                    *ret.get_mut(&id).unwrap().entry(day).or_insert(0) += points;
+                }
+            }
+        }
+        ret
+    }
+
+    pub fn totals(&self) -> Totals {
+        let mut stars_per_day = BTreeMap::new();
+        for stars in self.members.iter().flat_map(|m| m.days.iter()) {
+            let entry = stars_per_day.entry(*stars.0).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += stars.1.part2.is_some() as u32;
+        }
+        Totals {
+            members: self.members.len(),
+            stars: self.members.iter().map(|m| m.stars).sum(),
+            local_score: self.members.iter().map(|m| m.local_score).sum(),
+            stars_per_day,
+        }
+    }
+}
+
+/// Time a puzzle is unlocked: midnight in UTC-5 on the day in December. The year must not be
+/// before 1970.
+pub fn unlock_time(year: u32, day: u32) -> u64 {
+    // Days since the epoch of December 1st, following the proleptic Gregorian calendar
+    let y = year as u64 - 1;
+    let days_before_year = y * 365 + y / 4 - y / 100 + y / 400 - 719_162;
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let december = 334 + leap as u64;
+    (days_before_year + december + day as u64 - 1) * 86_400 + 5 * 3_600
+}
+
+/// Formats a number of seconds like `1:02:03`, with days in front when needed: `2d 01:02:03`.
+pub fn format_duration(seconds: u64) -> String {
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let (h, m, s) = (rest / 3_600, rest % 3_600 / 60, rest % 60);
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, h, m, s)
+    } else {
+        format!("{}:{:02}:{:02}", h, m, s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fixture() -> Leaderboard {
+        Leaderboard::parse(&InputSource::from("src/common/leaderboard_test.json")).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let board = fixture();
+        assert_eq!(board.event, 2020);
+        assert_eq!(board.owner_id, 1001);
+        assert_eq!(board.members.len(), 4);
+
+        let andurian = board.member(1001).unwrap();
+        assert_eq!(andurian.display_name(), "Andurian");
+        assert_eq!((andurian.stars, andurian.local_score), (4, 14));
+        assert_eq!(andurian.global_score, 12);
+        assert_eq!(andurian.star(1, 2), Some(1606799400));
+        assert_eq!(andurian.days[&1].delta(), Some(300));
+
+        let anonymous = board.member(1003).unwrap();
+        assert_eq!(anonymous.display_name(), "(anonymous user #1003)");
+        assert_eq!(anonymous.days[&1].part2, None);
+        assert_eq!(anonymous.days[&1].delta(), None);
+        assert!(board.member(1004).unwrap().days.is_empty());
+    }
+
+    #[test]
+    fn test_parse_legacy_strings() {
+        let text = r#"{"event": "2018", "owner_id": "7", "members": {"7": {
+            "id": "7", "name": "x", "stars": 1, "local_score": 1, "global_score": 0,
+            "last_star_ts": "1543640500",
+            "completion_day_level": {"1": {"1": {"get_star_ts": "1543640500"}}}}}}"#;
+        let board = Leaderboard::from_json(&InputSource::Stdin, text).unwrap();
+        assert_eq!(board.event, 2018);
+        assert_eq!(board.members[0].star(1, 1), Some(1543640500));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let source = InputSource::from("board.json");
+        let error = Leaderboard::from_json(&source, "{\n\"event\": }").unwrap_err();
+        assert_eq!(error.location, Location::Line(2));
+
+        let text = r#"{"event": 2020, "members": {"3": {"id": 3, "name": null,
+            "local_score": 0, "last_star_ts": 0, "completion_day_level": {}}}}"#;
+        let error = Leaderboard::from_json(&source, text).unwrap_err();
+        assert_eq!(error.message, "member 3: expected a number for \"stars\"");
+
+        let text = r#"{"event": 2020, "members": {"3": {"id": 3, "stars": 1,
+            "local_score": 0, "last_star_ts": 0,
+            "completion_day_level": {"26": {"1": {"get_star_ts": 1}}}}}}"#;
+        let error = Leaderboard::from_json(&source, text).unwrap_err();
+        assert_eq!(error.message, "member 3: invalid day \"26\"");
+
+        let text = r#"{"event": 1969, "members": {}}"#;
+        let error = Leaderboard::from_json(&source, text).unwrap_err();
+        assert_eq!(error.message, "there are no puzzles of 1969");
+        let text = r#"{"event": 4294969311, "members": {}}"#;
+        let error = Leaderboard::from_json(&source, text).unwrap_err();
+        assert_eq!(error.message, "there are no puzzles of 4294969311");
+
+        let text = r#"{"event": 2020, "members": {"3": {"id": 3, "stars": 4294967297,
+            "local_score": 0, "last_star_ts": 0, "completion_day_level": {}}}}"#;
+        let error = Leaderboard::from_json(&source, text).unwrap_err();
+        assert_eq!(error.message, "member 3: too many \"stars\"");
+    }
+
+    #[test]
+    fn test_ranking() {
+        let board = fixture();
+        let ranking = board.ranking().iter().map(|m| m.id).collect::<Vec<u64>>();
+        // Andurian and Bob are tied, Andurian got the last star first
+        assert_eq!(ranking, [1001, 1002, 1003, 1004]);
+    }
+
+    #[test]
+    fn test_day_scores_add_up_to_local_score() {
+        let board = fixture();
+        let scores = board.day_scores();
+        for member in &board.members {
+            assert_eq!(scores[&member.id].values().sum::<u64>(), member.local_score);
+        }
+        assert_eq!(scores[&1002][&1], 7);
+        assert_eq!(scores[&1003][&1], 2);
+        assert_eq!(scores[&1003][&3], 8);
+    }
+
+    #[test]
+    fn test_totals() {
+        let totals = fixture().totals();
+        assert_eq!(totals.members, 4);
+        assert_eq!(totals.stars, 11);
+        assert_eq!(totals.local_score, 38);
+        assert_eq!(totals.stars_per_day[&1], (3, 2));
+        assert_eq!(totals.stars_per_day[&3], (1, 1));
+        assert!(!totals.stars_per_day.contains_key(&4));
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2020-12-01T05:00:00Z and 2016-12-25T05:00:00Z
+        assert_eq!(unlock_time(2020, 1), 1606798800);
+        assert_eq!(unlock_time(2016, 25), 1482642000);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0:00:00");
+        assert_eq!(format_duration(3_723), "1:02:03");
+        assert_eq!(format_duration(2 * 86_400 + 3_723), "2d 01:02:03");
+    }
+}
@@ -0,0 +1,234 @@
+use crate::error::Error;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+/// Reason why candidate sets don't determine a unique assignment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssignmentError<K> {
+    /// The listed keys compete for fewer values than there are keys
+    Impossible(Vec<K>),
+    /// The listed keys can be assigned in more than one way
+    Ambiguous(Vec<K>),
+}
+
+impl<K: fmt::Display> fmt::Display for AssignmentError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |keys: &[K]| {
+            keys.iter()
+                .map(|k| k.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        match self {
+            AssignmentError::Impossible(keys) => write!(
+                f,
+                "no assignment possible, not enough values for {}",
+                join(keys)
+            ),
+            AssignmentError::Ambiguous(keys) => {
+                write!(f, "assignment is ambiguous for {}", join(keys))
+            }
+        }
+    }
+}
+
+impl<K: fmt::Display> From<AssignmentError<K>> for Error {
+    fn from(e: AssignmentError<K>) -> Error {
+        Error::no_solution(e.to_string())
+    }
+}
+
+struct Matcher {
+    candidates: Vec<Vec<usize>>,
+    key_of_value: Vec<Option<usize>>,
+    value_of_key: Vec<Option<usize>>,
+}
+
+impl Matcher {
+    /// Looks for an augmenting path starting at key `k` that doesn't use the edge `forbidden`.
+    fn augment(&mut self, k: usize, visited: &mut Vec<bool>, forbidden: (usize, usize)) -> bool {
+        for i in 0..self.candidates[k].len() {
+            let v = self.candidates[k][i];
+            if visited[v] || (k, v) == forbidden {
+                continue;
+            }
+            visited[v] = true;
+
+            let free = match self.key_of_value[v] {
+                None => true,
+                Some(other) => self.augment(other, visited, forbidden),
+            };
+            if free {
+                self.key_of_value[v] = Some(k);
+                self.value_of_key[k] = Some(v);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Keys reachable from `start` by alternating between candidate values and their current keys.
+    fn alternating_reach(&self, start: &[usize]) -> Vec<usize> {
+        let mut seen = start.iter().copied().collect::<HashSet<usize>>();
+        let mut open = start.to_vec();
+        while let Some(k) = open.pop() {
+            for v in &self.candidates[k] {
+                if let Some(other) = self.key_of_value[*v] {
+                    if seen.insert(other) {
+                        open.push(other);
+                    }
+                }
+            }
+        }
+        seen.into_iter().collect()
+    }
+}
+
+/// Assigns a different value to every key, each value taken from the key's candidates. Succeeds
+/// only if exactly one such assignment exists.
+pub fn assign_unique<K, V>(
+    candidates: &HashMap<K, HashSet<V>>,
+) -> Result<HashMap<K, V>, AssignmentError<K>>
+where
+    K: Clone + Ord + Hash,
+    V: Clone + Eq + Hash,
+{
+    let mut keys = candidates.keys().cloned().collect::<Vec<K>>();
+    keys.sort();
+
+    let mut values = Vec::<V>::new();
+    let mut value_index = HashMap::<V, usize>::new();
+    let mut matcher = Matcher {
+        candidates: Vec::with_capacity(keys.len()),
+        key_of_value: Vec::new(),
+        value_of_key: vec![None; keys.len()],
+    };
+    for k in &keys {
+        let mut indices = candidates[k]
+            .iter()
+            .map(|v| {
+                *value_index.entry(v.clone()).or_insert_with(|| {
+                    values.push(v.clone());
+                    values.len() - 1
+                })
+            })
+            .collect::<Vec<usize>>();
+        indices.sort_unstable();
+        matcher.candidates.push(indices);
+    }
+    matcher.key_of_value = vec![None; values.len()];
+
+    let none = (usize::MAX, usize::MAX);
+    let unmatched = (0..keys.len())
+        .filter(|k| !matcher.augment(*k, &mut vec![false; values.len()], none))
+        .collect::<Vec<usize>>();
+
+    let to_keys = |mut indices: Vec<usize>| {
+        indices.sort_unstable();
+        indices.into_iter().map(|i| keys[i].clone()).collect()
+    };
+
+    if !unmatched.is_empty() {
+        return Err(AssignmentError::Impossible(to_keys(
+            matcher.alternating_reach(&unmatched),
+        )));
+    }
+
+    // A key is ambiguous if the others can be rearranged so that it gets a different value
+    let mut ambiguous = Vec::new();
+    for k in 0..keys.len() {
+        let v = matcher.value_of_key[k].unwrap();
+        let (key_of_value, value_of_key) =
+            (matcher.key_of_value.clone(), matcher.value_of_key.clone());
+
+        matcher.key_of_value[v] = None;
+        if matcher.augment(k, &mut vec![false; values.len()], (k, v)) {
+            ambiguous.push(k);
+        }
+
+        matcher.key_of_value = key_of_value;
+        matcher.value_of_key = value_of_key;
+    }
+
+    if !ambiguous.is_empty() {
+        return Err(AssignmentError::Ambiguous(to_keys(ambiguous)));
+    }
+
+    Ok(keys
+        .iter()
+        .enumerate()
+        .map(|(k, key)| {
+            (
+                key.clone(),
+                values[matcher.value_of_key[k].unwrap()].clone(),
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn candidates(entries: &[(&'static str, &[i32])]) -> HashMap<&'static str, HashSet<i32>> {
+        entries
+            .iter()
+            .map(|(k, v)| (*k, v.iter().copied().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_unique() {
+        let c = candidates(&[("a", &[1, 2, 3]), ("b", &[1]), ("c", &[1, 2])]);
+        let assignment = assign_unique(&c).unwrap();
+        assert_eq!(assignment["a"], 3);
+        assert_eq!(assignment["b"], 1);
+        assert_eq!(assignment["c"], 2);
+    }
+
+    #[test]
+    fn test_stalled_elimination() {
+        // No key has a single candidate, so stripping singletons would never make progress
+        let c = candidates(&[
+            ("a", &[1, 2]),
+            ("b", &[2, 3]),
+            ("c", &[1, 2, 3]),
+            ("d", &[1, 2, 3, 4]),
+        ]);
+        let err = assign_unique(&c).unwrap_err();
+        assert_eq!(err, AssignmentError::Ambiguous(vec!["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        let c = candidates(&[("a", &[1, 2]), ("b", &[1, 2]), ("c", &[3])]);
+        let err = assign_unique(&c).unwrap_err();
+        assert_eq!(err, AssignmentError::Ambiguous(vec!["a", "b"]));
+        assert_eq!(err.to_string(), "assignment is ambiguous for a, b");
+
+        // A value nobody else needs makes a key ambiguous as well
+        let c = candidates(&[("a", &[1, 2])]);
+        assert_eq!(
+            assign_unique(&c).unwrap_err(),
+            AssignmentError::Ambiguous(vec!["a"])
+        );
+    }
+
+    #[test]
+    fn test_impossible() {
+        let c = candidates(&[("a", &[1]), ("b", &[1]), ("c", &[1, 2]), ("d", &[3])]);
+        let err = assign_unique(&c).unwrap_err();
+        assert_eq!(err, AssignmentError::Impossible(vec!["a", "b"]));
+        assert_eq!(
+            err.to_string(),
+            "no assignment possible, not enough values for a, b"
+        );
+
+        let c = candidates(&[("a", &[]), ("b", &[1])]);
+        assert_eq!(
+            assign_unique(&c).unwrap_err(),
+            AssignmentError::Impossible(vec!["a"])
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// Number of jobs to use when none is given: one per available CPU.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        String::from(*s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+/// Applies `f` to all `items` on up to `jobs` worker threads.
+///
+/// `done` is called on the calling thread with the index and result of every item in the order of
+/// `items`, as soon as a result and all results before it are available. A panic inside `f` only
+/// affects its own item and is passed on as `Err` with the panic message.
+pub fn map_ordered<T, R, F, D>(items: Vec<T>, jobs: usize, f: F, mut done: D)
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+    D: FnMut(usize, Result<R, String>),
+{
+    let jobs = jobs.max(1).min(items.len());
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || loop {
+                // Nothing can panic while the lock is held, so it is never poisoned
+                let next = queue.lock().unwrap().next();
+                let (i, item) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)));
+                if sender.send((i, result.map_err(panic_message))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next) {
+                done(next, result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_order() {
+        for jobs in &[1, 3, 10] {
+            let mut results = Vec::new();
+            map_ordered(
+                vec![30, 0, 20, 10, 0],
+                *jobs,
+                |ms| {
+                    thread::sleep(Duration::from_millis(ms));
+                    ms * 2
+                },
+                |i, r| results.push((i, r)),
+            );
+            assert_eq!(
+                results,
+                vec![
+                    (0, Ok(60)),
+                    (1, Ok(0)),
+                    (2, Ok(40)),
+                    (3, Ok(20)),
+                    (4, Ok(0))
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_panic_is_isolated() {
+        let mut results = Vec::new();
+        map_ordered(
+            vec![1, 2, 3],
+            2,
+            |x| {
+                if x == 2 {
+                    panic!("day {} exploded", x);
+                }
+                x
+            },
+            |_, r| results.push(r),
+        );
+        assert_eq!(
+            results,
+            vec![Ok(1), Err(String::from("day 2 exploded")), Ok(3)]
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut called = false;
+        map_ordered(Vec::<u32>::new(), 4, |x| x, |_, _| called = true);
+        assert!(!called);
+    }
+}
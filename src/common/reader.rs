@@ -0,0 +1,199 @@
+use crate::error::{Location, ParseError};
+use crate::input::InputSource;
+use std::fmt::Display;
+use std::io::{self, BufRead};
+
+/// Iterator over the non-empty lines of a reader. Lines are trimmed and paired with their 1-based
+/// line number.
+pub struct Lines<R> {
+    reader: R,
+    line_number: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Lines<R> {
+        Lines {
+            reader,
+            line_number: 0,
+        }
+    }
+
+    fn next_raw(&mut self) -> Option<io::Result<(usize, String)>> {
+        let mut buf = String::new();
+        match self.reader.read_line(&mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line_number += 1;
+                Some(Ok((self.line_number, String::from(buf.trim()))))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Applies `f` to every line. Errors carry the line number and text of the offending line.
+    pub fn parse<'a, T, E, F>(
+        self,
+        source: &'a InputSource,
+        f: F,
+    ) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+    where
+        R: 'a,
+        F: Fn(&str) -> Result<T, E> + 'a,
+        E: Display,
+    {
+        self.map(move |line| match line {
+            Ok((n, line)) => {
+                f(&line).map_err(|e| ParseError::new(source, Location::Line(n), &line, e))
+            }
+            Err(e) => Err(ParseError::new(source, Location::Input, "", e)),
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_raw()? {
+                Ok((_, line)) if line.is_empty() => continue,
+                line => return Some(line),
+            }
+        }
+    }
+}
+
+/// Iterator over the groups of a reader. A group is a block of lines separated by blank lines,
+/// returned with its lines trimmed and joined by `\n` and paired with its 1-based index.
+pub struct Groups<R> {
+    lines: Lines<R>,
+    group_index: usize,
+}
+
+impl<R: BufRead> Groups<R> {
+    pub fn new(reader: R) -> Groups<R> {
+        Groups {
+            lines: Lines::new(reader),
+            group_index: 0,
+        }
+    }
+
+    /// Applies `f` to every group. Errors carry the index and text of the offending group.
+    pub fn parse<'a, T, E, F>(
+        self,
+        source: &'a InputSource,
+        f: F,
+    ) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+    where
+        R: 'a,
+        F: Fn(&str) -> Result<T, E> + 'a,
+        E: Display,
+    {
+        self.map(move |group| match group {
+            Ok((n, group)) => {
+                f(&group).map_err(|e| ParseError::new(source, Location::Group(n), &group, e))
+            }
+            Err(e) => Err(ParseError::new(source, Location::Input, "", e)),
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for Groups<R> {
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group = String::new();
+        loop {
+            match self.lines.next_raw() {
+                Some(Ok((_, line))) if line.is_empty() => {
+                    if !group.is_empty() {
+                        break;
+                    }
+                }
+                Some(Ok((_, line))) => {
+                    if !group.is_empty() {
+                        group.push('\n');
+                    }
+                    group.push_str(&line);
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => break,
+            }
+        }
+
+        if group.is_empty() {
+            return None;
+        }
+
+        self.group_index += 1;
+        Some(Ok((self.group_index, group)))
+    }
+}
+
+/// Streams the lines of `source`.
+pub fn lines(source: &InputSource) -> Result<Lines<Box<dyn BufRead>>, ParseError> {
+    source
+        .open()
+        .map(Lines::new)
+        .map_err(|e| ParseError::new(source, Location::Input, "", e))
+}
+
+/// Streams the groups of `source`.
+pub fn groups(source: &InputSource) -> Result<Groups<Box<dyn BufRead>>, ParseError> {
+    source
+        .open()
+        .map(Groups::new)
+        .map_err(|e| ParseError::new(source, Location::Input, "", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_lines() {
+        let lines = Lines::new(Cursor::new("a\n\n  b \r\nc"))
+            .map(Result::unwrap)
+            .collect::<Vec<(usize, String)>>();
+        assert_eq!(
+            lines,
+            vec![
+                (1, String::from("a")),
+                (3, String::from("b")),
+                (4, String::from("c"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        let groups = Groups::new(Cursor::new("\na\nb\n\n\n c\n\nd\ne\n"))
+            .map(Result::unwrap)
+            .collect::<Vec<(usize, String)>>();
+        assert_eq!(
+            groups,
+            vec![
+                (1, String::from("a\nb")),
+                (2, String::from("c")),
+                (3, String::from("d\ne"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_is_lazy() {
+        let source = InputSource::Stdin;
+        let mut numbers =
+            Lines::new(Cursor::new("1\n2\nx\n4")).parse(&source, |line| line.parse::<i32>());
+        assert_eq!(numbers.next(), Some(Ok(1)));
+        assert_eq!(numbers.next(), Some(Ok(2)));
+
+        let err = numbers.next().unwrap().unwrap_err();
+        assert_eq!(err.location, Location::Line(3));
+        assert_eq!(err.text, "x");
+
+        assert_eq!(numbers.next(), Some(Ok(4)));
+        assert_eq!(numbers.next(), None);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::solution::{Answer, TimedRun};
+use serde_json::{json, Value};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Result of one part of a day, written as a single line of JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResultRecord {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    /// Time spent parsing the input, shared by both parts
+    pub parse_time: Duration,
+    pub time: Duration,
+}
+
+impl ResultRecord {
+    /// Both records of a run, part 1 first.
+    pub fn from_run(year: u32, day: u32, run: &TimedRun) -> Vec<ResultRecord> {
+        let (part1, part2) = run.answers.clone();
+        vec![
+            ResultRecord {
+                year,
+                day,
+                part: 1,
+                answer: part1,
+                parse_time: run.parse,
+                time: run.part1,
+            },
+            ResultRecord {
+                year,
+                day,
+                part: 2,
+                answer: part2,
+                parse_time: run.parse,
+                time: run.part2,
+            },
+        ]
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "answer": answer_to_json(&self.answer),
+            "type": self.answer.kind(),
+            "parse_us": self.parse_time.as_micros() as u64,
+            "time_us": self.time.as_micros() as u64,
+        })
+    }
+}
+
+/// Numbers are written as JSON numbers as long as common parsers read them without loss.
+fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(n) => match i64::try_from(*n) {
+            Ok(n) => json!(n),
+            Err(_) => json!(n.to_string()),
+        },
+        Answer::Text(s) => json!(s),
+        Answer::Empty => Value::Null,
+    }
+}
+
+/// Record for a day that did not produce any answers.
+pub fn error_to_json(year: u32, day: u32, message: &str) -> Value {
+    json!({
+        "year": year,
+        "day": day,
+        "error": message,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let run = TimedRun {
+            answers: (Answer::from(42), Answer::from("a,b")),
+            parse: Duration::from_micros(5),
+            part1: Duration::from_micros(1500),
+            part2: Duration::from_millis(2),
+        };
+        let records = ResultRecord::from_run(2020, 7, &run);
+        assert_eq!(
+            records[0].to_json().to_string(),
+            r#"{"year":2020,"day":7,"part":1,"answer":42,"type":"number","parse_us":5,"time_us":1500}"#
+        );
+        assert_eq!(records[1].to_json()["answer"], "a,b");
+        assert_eq!(records[1].to_json()["type"], "text");
+        assert_eq!(records[1].to_json()["time_us"], 2000);
+
+        assert_eq!(answer_to_json(&Answer::Empty), Value::Null);
+        assert_eq!(
+            answer_to_json(&Answer::from(u64::MAX)),
+            json!("18446744073709551615")
+        );
+        assert_eq!(
+            error_to_json(2020, 9, "no solution").to_string(),
+            r#"{"year":2020,"day":9,"error":"no solution"}"#
+        );
+    }
+}
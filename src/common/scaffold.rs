@@ -0,0 +1,470 @@
+use crate::input::source_dir;
+use crate::solution::FIRST_YEAR;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Year of the first puzzles.
+pub(crate) const FIRST_EVENT: u32 = 2015;
+
+/// Source of a new day module with a `Solution` that parses lines and solves nothing yet.
+pub fn module_source(year: u32, day: u32) -> String {
+    let year_fn = if year == FIRST_YEAR {
+        String::new()
+    } else {
+        format!("    fn year(&self) -> u32 {{\n        {}\n    }}\n\n", year)
+    };
+    let fixture = source_dir(Path::new(""), year, day).join("input_test_01.txt");
+
+    format!(
+        r#"use common::{{Answer, Error, InputSource, ParseError, Solution}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = Vec<String>;
+
+{year_fn}    fn day(&self) -> u32 {{
+        {day}
+    }}
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {{
+        common::try_read_file_linewise(input)
+    }}
+
+    fn part1(&self, _lines: &Vec<String>) -> Result<Answer, Error> {{
+        Ok(Answer::Empty)
+    }}
+
+    fn part2(&self, _lines: &Vec<String>) -> Result<Answer, Error> {{
+        Ok(Answer::Empty)
+    }}
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+
+    #[test]
+    fn test_example() {{
+        let input = Day{day:02}
+            .parse(&InputSource::from("{fixture}"))
+            .unwrap();
+        assert_eq!(Day{day:02}.part1(&input).unwrap(), Answer::Empty);
+        assert_eq!(Day{day:02}.part2(&input).unwrap(), Answer::Empty);
+    }}
+}}
+"#,
+        day = day,
+        year_fn = year_fn,
+        fixture = fixture.display()
+    )
+}
+
+/// Source of a new year module, the days are declared in it by `register_in_year`.
+fn year_source(year: u32) -> String {
+    format!("//! Puzzles of {}.\n", year)
+}
+
+/// Number of `digits` digits following `prefix` at the start of `s`, and the rest of `s`.
+fn number_after<'a>(s: &'a str, prefix: &str, digits: usize) -> Option<(u32, &'a str)> {
+    let rest = s.strip_prefix(prefix)?;
+    let number = rest.get(..digits)?.parse::<u32>().ok()?;
+    Some((number, &rest[digits..]))
+}
+
+/// Year and day of a module declaration: `pub mod day07;` is a day of the first year,
+/// `pub mod y2021;` the module of a year, sorted before its days.
+fn module_key(line: &str) -> Option<(u32, u32)> {
+    let line = line.trim();
+    if let Some((year, _)) = number_after(line, "pub mod y", 4) {
+        return Some((year, 0));
+    }
+    number_after(line, "pub mod day", 2).map(|(day, _)| (FIRST_YEAR, day))
+}
+
+/// Year and day of a registry entry like `Box::new(day07::Day07),` or
+/// `Box::new(y2021::day07::Day07),`.
+fn entry_key(line: &str) -> Option<(u32, u32)> {
+    let line = line.trim();
+    if let Some((year, rest)) = number_after(line, "Box::new(y", 4) {
+        let (day, _) = number_after(rest, "::day", 2)?;
+        return Some((year, day));
+    }
+    number_after(line, "Box::new(day", 2).map(|(day, _)| (FIRST_YEAR, day))
+}
+
+/// Inserts the lines `new` after the last line with a key smaller than `key`, or before the first
+/// keyed line if there is none. Attributes above a keyed line stay together with it.
+fn insert_sorted<K>(lines: &mut Vec<String>, key_of: K, key: (u32, u32), new: Vec<String>) -> bool
+where
+    K: Fn(&str) -> Option<(u32, u32)>,
+{
+    let keyed = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| key_of(l).map(|k| (i, k)))
+        .collect::<Vec<(usize, (u32, u32))>>();
+    let at = match keyed.iter().rev().find(|(_, k)| *k < key) {
+        Some((i, _)) => i + 1,
+        None => match keyed.first() {
+            Some((i, _)) if *i > 0 && lines[i - 1].trim_start().starts_with("#[") => i - 1,
+            Some((i, _)) => *i,
+            None => return false,
+        },
+    };
+    for (offset, line) in new.into_iter().enumerate() {
+        lines.insert(at + offset, line);
+    }
+    true
+}
+
+/// Lines of the body of `fn registry()`. Generators and checkers are listed the same way in other
+/// functions, so entries are only looked for and added here.
+fn registry_body(lines: &[String]) -> Option<Range<usize>> {
+    let start = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("pub fn registry("))?
+        + 1;
+    let len = lines[start..].iter().position(|l| l == "}")?;
+    Some(start..start + len)
+}
+
+fn to_lines(source: &str) -> Vec<String> {
+    source.lines().map(String::from).collect()
+}
+
+fn from_lines(lines: Vec<String>) -> String {
+    let mut ret = lines.join("\n");
+    ret.push('\n');
+    ret
+}
+
+fn already_registered(what: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("{} is already registered", what),
+    )
+}
+
+/// Adds the registry entry of a day to the source of the library root, together with the
+/// declaration of its module for the first year, or of the year module for later years. The day
+/// is left out of `generators()` and `checkers()` until it has a generator and a checker.
+pub fn register(root_source: &str, year: u32, day: u32) -> io::Result<String> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut lines = to_lines(root_source);
+    let body = registry_body(&lines).ok_or_else(|| invalid("no registry found"))?;
+    if lines[body]
+        .iter()
+        .any(|l| entry_key(l) == Some((year, day)))
+    {
+        return Err(already_registered(format!("day {} of {}", day, year)));
+    }
+
+    let (key, module) = if year == FIRST_YEAR {
+        let day_module = (FIRST_YEAR, day);
+        if lines.iter().any(|l| module_key(l) == Some(day_module)) {
+            return Err(already_registered(format!("day {}", day)));
+        }
+        (day_module, format!("day{:02}", day))
+    } else {
+        ((year, 0), format!("y{}", year))
+    };
+    if !lines.iter().any(|l| module_key(l) == Some(key)) {
+        let declaration = vec![
+            format!("#[path = \"../{}/{}.rs\"]", module, module),
+            format!("pub mod {};", module),
+        ];
+        if !insert_sorted(&mut lines, module_key, key, declaration) {
+            return Err(invalid("no day modules found"));
+        }
+    }
+
+    // Entries of the registry are indented like the existing ones. The declaration above moved
+    // the registry down.
+    let body = registry_body(&lines).unwrap();
+    let mut entries = lines.drain(body.clone()).collect::<Vec<String>>();
+    let indent = entries
+        .iter()
+        .find(|l| entry_key(l).is_some())
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .ok_or_else(|| invalid("no registry found"))?
+        .to_string();
+    let path = if year == FIRST_YEAR {
+        format!("day{:02}::Day{:02}", day, day)
+    } else {
+        format!("y{}::day{:02}::Day{:02}", year, day, day)
+    };
+    let entry = vec![format!("{}Box::new({}),", indent, path)];
+    insert_sorted(&mut entries, entry_key, (year, day), entry);
+    lines.splice(body.start..body.start, entries);
+
+    Ok(from_lines(lines))
+}
+
+/// Adds the module declaration of `day` to the source of a year module.
+pub fn register_in_year(year_source: &str, day: u32) -> io::Result<String> {
+    let mut lines = to_lines(year_source);
+    let key = (FIRST_YEAR, day);
+    if lines.iter().any(|l| module_key(l) == Some(key)) {
+        return Err(already_registered(format!("day {}", day)));
+    }
+
+    let declaration = vec![
+        format!("#[path = \"day{:02}/day{:02}.rs\"]", day, day),
+        format!("pub mod day{:02};", day),
+    ];
+    if !insert_sorted(&mut lines, module_key, key, declaration.clone()) {
+        lines.extend(declaration);
+    }
+    Ok(from_lines(lines))
+}
+
+/// Creates the module and an empty fixture of a day below `root`, the directory of `Cargo.toml`,
+/// and registers the day with the runner. Days of years other than the first go to `src/yYYYY`,
+/// whose module is created along with its first day. Returns the files that were created.
+pub fn scaffold(root: &Path, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    if !(FIRST_EVENT..=9999).contains(&year) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("there are no puzzles of {}", year),
+        ));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("there is no day {}", day),
+        ));
+    }
+
+    let dir = source_dir(root, year, day);
+    let module = dir.join(format!("day{:02}.rs", day));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    // Update all sources in memory first, so that nothing is left behind if one can't be updated
+    let root_path = root.join("src").join("common").join("common.rs");
+    let registered = register(&fs::read_to_string(&root_path)?, year, day)?;
+    let year_module = if year == FIRST_YEAR {
+        None
+    } else {
+        let path = dir.parent().unwrap().join(format!("y{}.rs", year));
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => year_source(year),
+            Err(e) => return Err(e),
+        };
+        Some((path, register_in_year(&source, day)?))
+    };
+
+    let mut created = Vec::new();
+    fs::create_dir_all(&dir)?;
+    if let Some((path, source)) = year_module {
+        if !path.exists() {
+            created.push(path.clone());
+        }
+        fs::write(&path, source)?;
+    }
+    fs::write(&module, module_source(year, day))?;
+    created.push(module);
+    let fixture = dir.join("input_test_01.txt");
+    if !fixture.exists() {
+        fs::write(&fixture, "")?;
+        created.push(fixture);
+    }
+    fs::write(&root_path, registered)?;
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    const ROOT: &str = "\
+pub mod grid;
+#[path = \"../day01/day01.rs\"]
+pub mod day01;
+#[path = \"../day03/day03.rs\"]
+pub mod day03;
+
+pub fn registry() -> Vec<Box<dyn solution::Runnable>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day03::Day03),
+    ]
+}
+
+pub fn generators() -> Vec<Box<dyn generate::Generator>> {
+    vec![
+        Box::new(day01::generate::Day01Generator),
+        Box::new(day03::generate::Day03Generator),
+    ]
+}
+";
+
+    #[test]
+    fn test_register() {
+        let registered = register(ROOT, FIRST_YEAR, 2).unwrap();
+        assert_eq!(
+            registered,
+            "\
+pub mod grid;
+#[path = \"../day01/day01.rs\"]
+pub mod day01;
+#[path = \"../day02/day02.rs\"]
+pub mod day02;
+#[path = \"../day03/day03.rs\"]
+pub mod day03;
+
+pub fn registry() -> Vec<Box<dyn solution::Runnable>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day02::Day02),
+        Box::new(day03::Day03),
+    ]
+}
+
+pub fn generators() -> Vec<Box<dyn generate::Generator>> {
+    vec![
+        Box::new(day01::generate::Day01Generator),
+        Box::new(day03::generate::Day03Generator),
+    ]
+}
+"
+        );
+
+        let first = register(ROOT, FIRST_YEAR, 1).unwrap_err();
+        assert_eq!(first.kind(), io::ErrorKind::AlreadyExists);
+
+        let last = register(ROOT, FIRST_YEAR, 25).unwrap();
+        assert!(last.contains("pub mod day03;\n#[path = \"../day25/day25.rs\"]\npub mod day25;\n"));
+        assert!(last.contains("Box::new(day03::Day03),\n        Box::new(day25::Day25),\n"));
+
+        // Generators are not registries of days
+        let later = register(ROOT, 2021, 3).unwrap();
+        assert!(later.contains("Box::new(y2021::day03::Day03),\n    ]\n}\n\npub fn generators()"));
+        assert_eq!(later.matches("y2021::day03").count(), 1);
+
+        assert!(register("pub mod grid;\n", FIRST_YEAR, 4).is_err());
+    }
+
+    #[test]
+    fn test_register_later_year() {
+        let registered = register(ROOT, 2021, 5).unwrap();
+        assert!(registered
+            .contains("pub mod day03;\n#[path = \"../y2021/y2021.rs\"]\npub mod y2021;\n"));
+        assert!(registered
+            .contains("Box::new(day03::Day03),\n        Box::new(y2021::day05::Day05),\n"));
+
+        // The year module is only declared once, days stay ordered by year
+        let registered = register(&registered, 2021, 2).unwrap();
+        assert_eq!(registered.matches("pub mod y2021;").count(), 1);
+        assert!(registered
+            .contains("Box::new(y2021::day02::Day02),\n        Box::new(y2021::day05::Day05),\n"));
+        let registered = register(&registered, FIRST_YEAR, 4).unwrap();
+        assert!(registered.contains("pub mod day04;\n#[path = \"../y2021/y2021.rs\"]"));
+        assert!(registered.contains("Box::new(day04::Day04),\n        Box::new(y2021::day02"));
+
+        assert!(register(&registered, 2021, 5).is_err());
+
+        let year = register_in_year(&year_source(2021), 5).unwrap();
+        let year = register_in_year(&year, 2).unwrap();
+        assert_eq!(
+            year,
+            "//! Puzzles of 2021.\n\
+             #[path = \"day02/day02.rs\"]\npub mod day02;\n\
+             #[path = \"day05/day05.rs\"]\npub mod day05;\n"
+        );
+        assert!(register_in_year(&year, 2).is_err());
+    }
+
+    /// Body of `fn registry()` in `source`.
+    fn registry_of(source: &str) -> &str {
+        let registry = &source[source.find("pub fn registry(").unwrap()..];
+        &registry[..registry.find("\n}\n").unwrap()]
+    }
+
+    #[test]
+    fn test_register_real_root() {
+        let root = include_str!("common.rs");
+        let registered_year = |l: &str| entry_key(l).map(|(year, _)| year);
+        let year = root.lines().filter_map(registered_year).max().unwrap() + 1;
+        let registered = register(root, year, 1).unwrap();
+
+        // The new entry closes the registry, every other list of days stays as it was
+        let entry = format!("Box::new(y{}::day01::Day01),", year);
+        let last = registry_of(&registered)
+            .lines()
+            .rfind(|l| entry_key(l).is_some())
+            .unwrap();
+        assert_eq!(last.trim(), entry);
+        assert_eq!(registered.matches(&entry).count(), 1);
+        let outside = |source: &str| {
+            let registry = registry_of(source);
+            source
+                .replace(registry, "")
+                .lines()
+                .filter(|l| entry_key(l).is_some())
+                .map(String::from)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(outside(&registered), outside(root));
+
+        assert!(register(root, FIRST_YEAR, 1).is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src").join("common")).unwrap();
+        let root_path = root.join("src").join("common").join("common.rs");
+        fs::write(&root_path, ROOT).unwrap();
+
+        let created = scaffold(&root, FIRST_YEAR, 2).unwrap();
+        assert_eq!(
+            created,
+            vec![
+                root.join("src/day02/day02.rs"),
+                root.join("src/day02/input_test_01.txt")
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(&created[0]).unwrap(),
+            module_source(FIRST_YEAR, 2)
+        );
+        assert_eq!(fs::read_to_string(&created[1]).unwrap(), "");
+        assert!(fs::read_to_string(&root_path)
+            .unwrap()
+            .contains("Box::new(day02::Day02)"));
+
+        let created = scaffold(&root, 2021, 1).unwrap();
+        assert_eq!(
+            created,
+            vec![
+                root.join("src/y2021/y2021.rs"),
+                root.join("src/y2021/day01/day01.rs"),
+                root.join("src/y2021/day01/input_test_01.txt")
+            ]
+        );
+        let source = fs::read_to_string(&created[1]).unwrap();
+        assert!(source.contains("fn year(&self) -> u32 {\n        2021\n    }"));
+        assert!(source.contains("\"src/y2021/day01/input_test_01.txt\""));
+        assert_eq!(scaffold(&root, 2021, 3).unwrap().len(), 2);
+
+        assert!(scaffold(&root, FIRST_YEAR, 2).is_err());
+        assert!(scaffold(&root, 2021, 1).is_err());
+        assert!(scaffold(&root, FIRST_YEAR, 26).is_err());
+        assert!(scaffold(&root, 2014, 4).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use crate::error::{Error, ParseError};
+use crate::input::InputSource;
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Year of the puzzles this repository started with. Its days live directly in `src`, later years
+/// each get their own directory.
+pub const FIRST_YEAR: u32 = 2020;
+
+/// Answer to a single part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Used for puzzles that don't have a second part
+    Empty,
+}
+
+impl Answer {
+    /// Name of the kind of answer, as used in machine readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Number(_) => "number",
+            Answer::Text(_) => "text",
+            Answer::Empty => "empty",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Empty => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, i128, u8, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Answer {
+        Answer::Number(i128::try_from(n).expect("answer does not fit into i128"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(String::from(s))
+    }
+}
+
+/// A puzzle solution split into parsing the input and solving both parts on it.
+pub trait Solution {
+    type Input;
+
+    fn year(&self) -> u32 {
+        FIRST_YEAR
+    }
+    fn day(&self) -> u32;
+    fn parse(&self, input: &InputSource) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer, Error>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer, Error>;
+}
+
+/// Answers of a single run together with the time spent in each phase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimedRun {
+    pub answers: (Answer, Answer),
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let ret = f();
+    (ret, start.elapsed())
+}
+
+/// Object safe view on a `Solution` so that all days can be kept in one registry and run on
+/// worker threads.
+pub trait Runnable: Send + Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn run(&self, input: &InputSource) -> Result<(Answer, Answer), Error>;
+    /// Solves only one part, for inputs like examples that don't fit the other one.
+    fn run_part(&self, input: &InputSource, part: u32) -> Result<Answer, Error>;
+    fn run_timed(&self, input: &InputSource) -> Result<TimedRun, Error>;
+}
+
+impl<S: Solution + Send + Sync> Runnable for S {
+    fn year(&self) -> u32 {
+        Solution::year(self)
+    }
+
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn run(&self, input: &InputSource) -> Result<(Answer, Answer), Error> {
+        let input = self.parse(input)?;
+        Ok((self.part1(&input)?, self.part2(&input)?))
+    }
+
+    fn run_part(&self, input: &InputSource, part: u32) -> Result<Answer, Error> {
+        let input = self.parse(input)?;
+        match part {
+            1 => self.part1(&input),
+            2 => self.part2(&input),
+            _ => Err(Error::invalid_input(format!("there is no part {}", part))),
+        }
+    }
+
+    fn run_timed(&self, input: &InputSource) -> Result<TimedRun, Error> {
+        let (input, parse) = timed(|| self.parse(input));
+        let input = input?;
+        let (part1_answer, part1) = timed(|| self.part1(&input));
+        let part1_answer = part1_answer?;
+        let (part2_answer, part2) = timed(|| self.part2(&input));
+        Ok(TimedRun {
+            answers: (part1_answer, part2_answer?),
+            parse,
+            part1,
+            part2,
+        })
+    }
+}
+
+/// Parses a day selection as given on the command line: a single day (`7`),
+/// an inclusive range (`3-9`) or `all`.
+pub fn parse_day_selection(s: &str, available: &[u32]) -> Result<Vec<u32>, String> {
+    if s == "all" {
+        return Ok(available.to_vec());
+    }
+
+    let parse_day = |d: &str| {
+        d.trim()
+            .parse::<u32>()
+            .map_err(|_| format!("invalid day: {}", d))
+    };
+
+    let (first, last) = match s.find('-') {
+        Some(i) => (parse_day(&s[..i])?, parse_day(&s[i + 1..])?),
+        None => {
+            let d = parse_day(s)?;
+            (d, d)
+        }
+    };
+
+    if first > last {
+        return Err(format!("empty day range: {}", s));
+    }
+
+    let days: Vec<u32> = (first..=last).filter(|d| available.contains(d)).collect();
+    if days.is_empty() {
+        return Err(format!("no solution available for day(s) {}", s));
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(42).to_string(), "42");
+        assert_eq!(
+            Answer::from(453551299002368u64).to_string(),
+            "453551299002368"
+        );
+        assert_eq!(Answer::from("a,b,c").to_string(), "a,b,c");
+    }
+
+    #[test]
+    fn test_parse_day_selection() {
+        let available = (1..26).collect::<Vec<u32>>();
+        assert_eq!(parse_day_selection("7", &available), Ok(vec![7]));
+        assert_eq!(parse_day_selection("3-5", &available), Ok(vec![3, 4, 5]));
+        assert_eq!(
+            parse_day_selection("all", &available),
+            Ok(available.clone())
+        );
+        assert_eq!(parse_day_selection("24-30", &available), Ok(vec![24, 25]));
+        assert!(parse_day_selection("26", &available).is_err());
+        assert!(parse_day_selection("5-3", &available).is_err());
+        assert!(parse_day_selection("x", &available).is_err());
+    }
+}
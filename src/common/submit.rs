@@ -0,0 +1,404 @@
+use crate::error::{Location, ParseError};
+use crate::input::InputSource;
+use crate::solution::FIRST_YEAR;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::json;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the guess log inside the cache directory.
+pub const LOG_FILE: &str = "guesses.jsonl";
+
+/// Time to wait after a wrong answer before the server accepts the next one.
+pub const WRONG_ANSWER_DELAY: Duration = Duration::from_secs(60);
+
+/// What the server said about a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, with the time left to wait if the server said so
+    RateLimited(Option<Duration>),
+    /// The part has already been solved or isn't unlocked yet
+    WrongLevel,
+    /// The response could not be understood
+    Unknown,
+}
+
+impl Outcome {
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::RateLimited(_) => "rate_limited",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Outcome::RateLimited(None) => write!(f, "rate limited"),
+            Outcome::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Outcome::Unknown => write!(f, "unknown response"),
+        }
+    }
+}
+
+/// Reads the outcome from the page the server returns after submitting an answer.
+pub fn parse_response(html: &str) -> Outcome {
+    lazy_static! {
+        static ref WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    }
+
+    if html.contains("That's the right answer") {
+        Outcome::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if html.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        // A wait that doesn't fit is as good as no wait given
+        Outcome::RateLimited(WAIT.captures(html).and_then(|c| {
+            let minutes = match c.get(1) {
+                Some(m) => m.as_str().parse::<u64>().ok()?,
+                None => 0,
+            };
+            let seconds = c[2].parse::<u64>().ok()?;
+            let total = minutes.checked_mul(60)?.checked_add(seconds)?;
+            Some(Duration::from_secs(total))
+        }))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    }
+}
+
+/// Seconds since the epoch, the time format of the guess log.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// A submitted answer and what the server said about it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guess {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the epoch
+    pub time: u64,
+}
+
+/// Record of all submitted answers, stored as JSON lines so that it can only grow.
+#[derive(Clone, Debug)]
+pub struct GuessLog {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl GuessLog {
+    /// Reads the log at `path`, a missing file is an empty log.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<GuessLog, ParseError> {
+        let path = path.as_ref().to_path_buf();
+        let source = InputSource::File(path.clone());
+        let mut guesses = Vec::new();
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(ParseError::new(&source, Location::Input, "", e)),
+        };
+
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let error =
+                |message: &str| ParseError::new(&source, Location::Line(i + 1), line, message);
+            let value = serde_json::from_str::<serde_json::Value>(line)
+                .map_err(|e| error(&e.to_string()))?;
+            let number = |key: &str| value[key].as_u64().ok_or_else(|| error("missing number"));
+
+            let wait = value["wait_s"].as_u64().map(Duration::from_secs);
+            let outcome = match value["outcome"].as_str() {
+                Some("rate_limited") => Outcome::RateLimited(wait),
+                Some(name) => [
+                    Outcome::Correct,
+                    Outcome::Wrong,
+                    Outcome::TooHigh,
+                    Outcome::TooLow,
+                    Outcome::WrongLevel,
+                    Outcome::Unknown,
+                ]
+                .iter()
+                .copied()
+                .find(|o| o.name() == name)
+                .ok_or_else(|| error("unknown outcome"))?,
+                None => return Err(error("missing outcome")),
+            };
+
+            guesses.push(Guess {
+                // Logs written before other years were supported have no year
+                year: value["year"].as_u64().map_or(FIRST_YEAR, |y| y as u32),
+                day: number("day")? as u32,
+                part: number("part")? as u32,
+                answer: String::from(
+                    value["answer"]
+                        .as_str()
+                        .ok_or_else(|| error("missing answer"))?,
+                ),
+                outcome,
+                time: number("time")?,
+            });
+        }
+
+        Ok(GuessLog { path, guesses })
+    }
+
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    /// Appends `guess` to the log file.
+    pub fn record(&mut self, guess: Guess) -> io::Result<()> {
+        let mut line = json!({
+            "year": guess.year,
+            "day": guess.day,
+            "part": guess.part,
+            "answer": guess.answer,
+            "outcome": guess.outcome.name(),
+            "time": guess.time,
+        });
+        if let Outcome::RateLimited(Some(wait)) = guess.outcome {
+            line["wait_s"] = json!(wait.as_secs());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+
+        self.guesses.push(guess);
+        Ok(())
+    }
+
+    /// The outcome `answer` is known to have without asking the server: the outcome of the same
+    /// answer submitted before, or too high / too low if a number lies beyond an earlier bound.
+    pub fn known_outcome(&self, year: u32, day: u32, part: u32, answer: &str) -> Option<Outcome> {
+        let answers = self
+            .guesses
+            .iter()
+            .filter(|g| g.year == year && g.day == day && g.part == part);
+
+        let mut ret = None;
+        for g in answers {
+            if g.answer == answer && (g.outcome == Outcome::Correct || g.outcome.is_wrong()) {
+                return Some(g.outcome);
+            }
+            if let (Ok(a), Ok(bound)) = (answer.parse::<i128>(), g.answer.parse::<i128>()) {
+                match g.outcome {
+                    Outcome::TooHigh if a > bound => ret = Some(Outcome::TooHigh),
+                    Outcome::TooLow if a < bound => ret = Some(Outcome::TooLow),
+                    _ => {}
+                }
+            }
+        }
+        ret
+    }
+
+    /// Time left until the server accepts another answer, given the current time `now`.
+    pub fn wait_time(&self, now: u64) -> Option<Duration> {
+        self.guesses
+            .iter()
+            .filter_map(|g| {
+                let delay = match g.outcome {
+                    Outcome::RateLimited(wait) => wait.unwrap_or(WRONG_ANSWER_DELAY),
+                    o if o.is_wrong() => WRONG_ANSWER_DELAY,
+                    _ => return None,
+                };
+                Some(g.time.saturating_add(delay.as_secs()))
+            })
+            .max()
+            .filter(|until| *until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    fn guess(answer: &str, outcome: Outcome, time: u64) -> Guess {
+        Guess {
+            year: 2020,
+            day: 1,
+            part: 2,
+            answer: String::from(answer),
+            outcome,
+            time,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer. If you're stuck, ...")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 4m 32s left to wait."
+            )),
+            Outcome::RateLimited(Some(Duration::from_secs(272)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently. You have 9s left to wait."
+            )),
+            Outcome::RateLimited(Some(Duration::from_secs(9)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently. You have 99999999999999999999s left to wait."
+            )),
+            Outcome::RateLimited(None)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently. You have 307445734561825861m 0s left to wait."
+            )),
+            Outcome::RateLimited(None)
+        );
+        assert_eq!(
+            parse_response(&page("You don't seem to be solving the right level.")),
+            Outcome::WrongLevel
+        );
+        assert_eq!(parse_response("<html></html>"), Outcome::Unknown);
+    }
+
+    #[test]
+    fn test_known_outcome() {
+        let mut log = GuessLog {
+            path: PathBuf::new(),
+            guesses: Vec::new(),
+        };
+        log.guesses.push(guess("500", Outcome::TooHigh, 0));
+        log.guesses.push(guess("100", Outcome::TooLow, 0));
+        log.guesses.push(guess("300", Outcome::Wrong, 0));
+        log.guesses.push(guess("abc", Outcome::Wrong, 0));
+
+        assert_eq!(log.known_outcome(2020, 1, 2, "500"), Some(Outcome::TooHigh));
+        assert_eq!(log.known_outcome(2020, 1, 2, "501"), Some(Outcome::TooHigh));
+        assert_eq!(log.known_outcome(2020, 1, 2, "99"), Some(Outcome::TooLow));
+        assert_eq!(log.known_outcome(2020, 1, 2, "300"), Some(Outcome::Wrong));
+        assert_eq!(log.known_outcome(2020, 1, 2, "abc"), Some(Outcome::Wrong));
+        assert_eq!(log.known_outcome(2020, 1, 2, "250"), None);
+        assert_eq!(log.known_outcome(2020, 1, 1, "500"), None);
+        assert_eq!(log.known_outcome(2021, 1, 2, "500"), None);
+    }
+
+    #[test]
+    fn test_wait_time() {
+        let mut log = GuessLog {
+            path: PathBuf::new(),
+            guesses: vec![guess("1", Outcome::Correct, 1000)],
+        };
+        assert_eq!(log.wait_time(1000), None);
+
+        log.guesses.push(guess("2", Outcome::TooLow, 1000));
+        assert_eq!(log.wait_time(1010), Some(Duration::from_secs(50)));
+        assert_eq!(log.wait_time(1060), None);
+
+        let wait = Some(Duration::from_secs(300));
+        log.guesses
+            .push(guess("3", Outcome::RateLimited(wait), 1100));
+        assert_eq!(log.wait_time(1200), Some(Duration::from_secs(200)));
+
+        // A wait too long to be added to the time is still a wait, not one in the past
+        let wait = Some(Duration::from_secs(u64::MAX));
+        log.guesses
+            .push(guess("4", Outcome::RateLimited(wait), 1300));
+        assert_eq!(
+            log.wait_time(1400),
+            Some(Duration::from_secs(u64::MAX - 1400))
+        );
+    }
+
+    #[test]
+    fn test_log_file() {
+        let path = env::temp_dir().join(format!("aoc_guesses_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut log = GuessLog::open(&path).unwrap();
+        assert!(log.guesses().is_empty());
+        log.record(guess("42", Outcome::TooLow, 7)).unwrap();
+        let wait = Some(Duration::from_secs(30));
+        log.record(guess("43", Outcome::RateLimited(wait), 8))
+            .unwrap();
+
+        let reopened = GuessLog::open(&path).unwrap();
+        assert_eq!(reopened.guesses(), log.guesses());
+
+        fs::write(&path, "{\"day\": 1}\n").unwrap();
+        assert_eq!(
+            GuessLog::open(&path).unwrap_err().location,
+            Location::Line(1)
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+use common::check::{self, Checker, Problem};
+
+/// Expense reports: one number per line.
+pub struct Day01Checker;
+
+impl Checker for Day01Checker {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| s.number::<i32>().map(|_| ()))
+    }
+}
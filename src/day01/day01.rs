@@ -1,31 +1,187 @@
-fn main() {
+pub mod check;
+pub mod generate;
+pub mod ksum;
 
-    let numbers = common::parse_file_linewise_as::<i32>("src/day01/input.txt");
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use ksum::{Combination, Strategy};
+use std::collections::BTreeMap;
+use std::fmt;
 
-    'outer1: for i in 0..numbers.len() {
-        for j in i + 1..numbers.len() {
-            let a = numbers[i];
-            let b = numbers[j];
+pub const TARGET: i64 = 2020;
 
-            if a + b == 2020 {
-                println!("{} * {} = {}", a, b, a * b);
-                break 'outer1;
-            }
-        }
+/// Product of the `k` entries summing to 2020.
+pub fn product_of_sum(numbers: &[i32], k: usize) -> Result<i64, Error> {
+    let combination = ksum::first(numbers, k, TARGET, Strategy::Hashing)
+        .ok_or_else(|| Error::no_solution(format!("no {} entries sum to {}", k, TARGET)))?;
+    combination.product().ok_or_else(|| {
+        Error::invalid_input(format!(
+            "product of {:?} does not fit into 64 bits",
+            combination.values
+        ))
+    })
+}
+
+/// Every pair and triple of an expense report reaching a target, and the values listed more
+/// than once. Entries are numbered from 1 in the order of the report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Audit {
+    pub target: i64,
+    pub entries: usize,
+    pub pairs: Vec<Combination>,
+    pub triples: Vec<Combination>,
+    /// Values with the 0-based indices of all their entries
+    pub duplicates: Vec<(i32, Vec<usize>)>,
+}
+
+pub fn audit(numbers: &[i32], target: i64) -> Audit {
+    let mut positions = BTreeMap::<i32, Vec<usize>>::new();
+    for (i, &n) in numbers.iter().enumerate() {
+        positions.entry(n).or_default().push(i);
     }
 
-    'outer2: for i in 0..numbers.len() {
-        for j in i + 1..numbers.len() {
-            for k in j + 1..numbers.len() {
-                let a = &numbers[i];
-                let b = &numbers[j];
-                let c = &numbers[k];
+    Audit {
+        target,
+        entries: numbers.len(),
+        pairs: ksum::all(numbers, 2, target, Strategy::Hashing),
+        triples: ksum::all(numbers, 3, target, Strategy::Hashing),
+        duplicates: positions
+            .into_iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .collect(),
+    }
+}
+
+/// Audits the expense report in a file, one entry per line.
+pub fn audit_file(source: &InputSource, target: i64) -> Result<Audit, ParseError> {
+    let numbers = common::try_parse_file_linewise_as::<i32>(source)?;
+    Ok(audit(&numbers, target))
+}
+
+fn entry_numbers(indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|i| (i + 1).to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
 
-                if a + b + c == 2020 {
-                    println!("{} * {} * {} = {}", a, b, c, a * b * c);
-                    break 'outer2;
-                }
-            }
+impl Audit {
+    fn write_combinations(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        kind: &str,
+        combinations: &[Combination],
+    ) -> fmt::Result {
+        if combinations.is_empty() {
+            return writeln!(f, "No {} sums to {}", kind, self.target);
+        }
+        writeln!(
+            f,
+            "{} {}(s) sum to {}:",
+            combinations.len(),
+            kind,
+            self.target
+        )?;
+        for c in combinations {
+            let values = c
+                .values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(" + ");
+            let product = c
+                .product()
+                .map_or_else(|| String::from("too large"), |p| p.to_string());
+            writeln!(
+                f,
+                "  entries {}: {}, product {}",
+                entry_numbers(&c.indices),
+                values,
+                product
+            )?;
         }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} entries, target {}", self.entries, self.target)?;
+        self.write_combinations(f, "pair", &self.pairs)?;
+        self.write_combinations(f, "triple", &self.triples)?;
+        if self.duplicates.is_empty() {
+            return writeln!(f, "No duplicate entries");
+        }
+        writeln!(f, "{} duplicate value(s):", self.duplicates.len())?;
+        for (value, indices) in &self.duplicates {
+            writeln!(f, "  {} at entries {}", value, entry_numbers(indices))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<i32>, ParseError> {
+        common::try_parse_file_linewise_as::<i32>(input)
+    }
+
+    fn part1(&self, numbers: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(product_of_sum(numbers, 2)?.into())
+    }
+
+    fn part2(&self, numbers: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(product_of_sum(numbers, 3)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_audit_example() {
+        let source = InputSource::from("src/day01/input_example_01.txt");
+        let audit = audit_file(&source, TARGET).unwrap();
+        assert_eq!(audit.entries, 6);
+        assert_eq!(audit.pairs.len(), 1);
+        assert_eq!(audit.pairs[0].product(), Some(514579));
+        assert_eq!(audit.triples.len(), 1);
+        assert_eq!(audit.triples[0].product(), Some(241861950));
+        assert!(audit.duplicates.is_empty());
+        assert_eq!(
+            audit.to_string(),
+            "6 entries, target 2020\n\
+             1 pair(s) sum to 2020:\n  entries 1, 4: 1721 + 299, product 514579\n\
+             1 triple(s) sum to 2020:\n  entries 2, 3, 5: 979 + 366 + 675, product 241861950\n\
+             No duplicate entries\n"
+        );
+    }
+
+    #[test]
+    fn test_audit_without_combinations() {
+        let numbers = common::parse_file_linewise_as::<i32>("src/day01/input_example_01.txt");
+        let audit = audit(&numbers, 1);
+        assert!(audit.pairs.is_empty() && audit.triples.is_empty());
+        let report = audit.to_string();
+        assert!(report.contains("No pair sums to 1\n"));
+        assert!(report.contains("No triple sums to 1\n"));
+    }
+
+    #[test]
+    fn test_audit_duplicates() {
+        let audit = audit(&[1010, 5, 1010, 1721, 1010, 5], TARGET);
+        assert_eq!(audit.duplicates, [(5, vec![1, 5]), (1010, vec![0, 2, 4])]);
+        assert_eq!(audit.pairs.len(), 3);
+        assert!(audit
+            .to_string()
+            .ends_with("2 duplicate value(s):\n  5 at entries 2, 6\n  1010 at entries 1, 3, 5\n"));
     }
 }
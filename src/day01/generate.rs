@@ -0,0 +1,105 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+const TARGET: i32 = 2020;
+
+/// Expense reports with exactly one pair and exactly one triple summing to 2020.
+///
+/// Every entry but the four small members of the pair and the triple is at least 1011, so no two
+/// of them can be part of a sum of 2020. Fillers completing a sum with the small members are left
+/// out. The size is the number of entries, between 5 and 900.
+pub struct Day01Generator;
+
+fn is_ambiguous(pair: (i32, i32), triple: (i32, i32, i32)) -> bool {
+    let (a, b) = pair;
+    let (c, d, e) = triple;
+    let small = [b, c, d, e];
+    let distinct = [a, b, c, d, e].iter().collect::<HashSet<_>>().len() == 5;
+    !distinct
+        || e > 1009
+        || small.iter().any(|&s| s != b && a + s == TARGET)
+        || [c, d, e]
+            .iter()
+            .enumerate()
+            .any(|(i, &x)| [c, d, e][i + 1..].iter().any(|&y| a + x + y == TARGET))
+        || [b + c + d, b + c + e, b + d + e].contains(&TARGET)
+}
+
+impl Generator for Day01Generator {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn default_size(&self) -> usize {
+        200
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.clamp(5, 900);
+
+        let (pair, triple) = loop {
+            let a = rng.gen_range(1011..TARGET);
+            let c = rng.gen_range(505..1010);
+            let d = rng.gen_range(505..1010);
+            let pair = (a, TARGET - a);
+            let triple = (c, d, TARGET - c - d);
+            if !is_ambiguous(pair, triple) {
+                break (pair, triple);
+            }
+        };
+
+        let small = [pair.1, triple.0, triple.1, triple.2];
+        let mut excluded = small
+            .iter()
+            .map(|s| TARGET - s)
+            .chain(
+                small
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, x)| small[i + 1..].iter().map(move |y| TARGET - x - y)),
+            )
+            .collect::<HashSet<i32>>();
+        excluded.insert(pair.0);
+
+        let mut fillers = (1011..TARGET)
+            .filter(|x| !excluded.contains(x))
+            .collect::<Vec<i32>>();
+        fillers.shuffle(rng);
+
+        let mut numbers = vec![pair.0, pair.1, triple.0, triple.1, triple.2];
+        numbers.extend(fillers.into_iter().take(size - 5));
+        numbers.shuffle(rng);
+
+        Generated::new(
+            generate::lines(numbers.iter().map(|n| n.to_string())),
+            Some((pair.0 * pair.1).into()),
+            Some((triple.0 * triple.1 * triple.2).into()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate_has_a_single_pair() {
+        for seed in 0..20 {
+            let generated = Day01Generator.generate(&mut generate::rng(seed), 100);
+            let numbers = generated
+                .input
+                .lines()
+                .map(|l| l.parse::<i32>().unwrap())
+                .collect::<Vec<i32>>();
+            assert_eq!(numbers.len(), 100);
+
+            let pairs = (0..numbers.len())
+                .flat_map(|i| (i + 1..numbers.len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| numbers[i] + numbers[j] == TARGET)
+                .count();
+            assert_eq!(pairs, 1);
+        }
+    }
+}
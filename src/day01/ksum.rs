@@ -0,0 +1,326 @@
+//! Search for k entries of a list summing to a target, for any k.
+//!
+//! All but the last two entries of a combination are fixed one after the other, the last two are
+//! found in linear time, so the search takes O(n^(k-1)) steps instead of O(n^k). Entries are
+//! told apart by their index: a value listed twice can be used twice, a value listed once can't.
+
+use std::collections::HashMap;
+
+/// How the last two entries of a combination are found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Looks up the partner of each entry among the entries before it in a hash map
+    Hashing,
+    /// Sorts the entries once and closes in on the last two from both ends of the sorted list
+    TwoPointer,
+}
+
+/// Entries summing to the target: their indices in ascending order and their values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Combination {
+    pub indices: Vec<usize>,
+    pub values: Vec<i32>,
+}
+
+impl Combination {
+    fn new(numbers: &[i32], indices: &[usize]) -> Combination {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        let values = indices.iter().map(|&i| numbers[i]).collect();
+        Combination { indices, values }
+    }
+
+    pub fn sum(&self) -> i64 {
+        self.values.iter().map(|&v| v as i64).sum()
+    }
+
+    /// Product of the values, `None` if it does not fit into an `i64`.
+    pub fn product(&self) -> Option<i64> {
+        self.values
+            .iter()
+            .try_fold(1i64, |acc, &v| acc.checked_mul(v as i64))
+    }
+}
+
+/// Called with the indices of every combination found, returns whether to go on searching.
+type Emit<'a> = dyn FnMut(&[usize]) -> bool + 'a;
+
+fn emit_with(chosen: &mut Vec<usize>, last: &[usize], emit: &mut Emit) -> bool {
+    let len = chosen.len();
+    chosen.extend_from_slice(last);
+    let go_on = emit(chosen);
+    chosen.truncate(len);
+    go_on
+}
+
+fn search_hashing(
+    numbers: &[i32],
+    start: usize,
+    k: usize,
+    target: i64,
+    chosen: &mut Vec<usize>,
+    emit: &mut Emit,
+) -> bool {
+    match k {
+        0 => target != 0 || emit(chosen),
+        1 => (start..numbers.len())
+            .filter(|&i| numbers[i] as i64 == target)
+            .all(|i| emit_with(chosen, &[i], emit)),
+        2 => {
+            let mut seen = HashMap::<i64, Vec<usize>>::new();
+            for (j, &value) in numbers.iter().enumerate().skip(start) {
+                let value = value as i64;
+                if let Some(partners) = seen.get(&(target - value)) {
+                    for &i in partners {
+                        if !emit_with(chosen, &[i, j], emit) {
+                            return false;
+                        }
+                    }
+                }
+                seen.entry(value).or_default().push(j);
+            }
+            true
+        }
+        _ => (start..numbers.len()).all(|i| {
+            chosen.push(i);
+            let go_on = search_hashing(
+                numbers,
+                i + 1,
+                k - 1,
+                target - numbers[i] as i64,
+                chosen,
+                emit,
+            );
+            chosen.pop();
+            go_on
+        }),
+    }
+}
+
+/// Like `search_hashing` on `sorted`, a list of values and their original indices ordered by
+/// value. Runs of equal values pair up with every entry of a matching run.
+fn search_two_pointer(
+    sorted: &[(i64, usize)],
+    start: usize,
+    k: usize,
+    target: i64,
+    chosen: &mut Vec<usize>,
+    emit: &mut Emit,
+) -> bool {
+    match k {
+        0 => target != 0 || emit(chosen),
+        1 => sorted[start..]
+            .iter()
+            .filter(|(v, _)| *v == target)
+            .all(|(_, i)| emit_with(chosen, &[*i], emit)),
+        2 => {
+            if sorted.len() < start + 2 {
+                return true;
+            }
+            let (mut lo, mut hi) = (start, sorted.len() - 1);
+            while lo < hi {
+                let sum = sorted[lo].0 + sorted[hi].0;
+                if sum < target {
+                    lo += 1;
+                } else if sum > target {
+                    hi -= 1;
+                } else if sorted[lo].0 == sorted[hi].0 {
+                    // Everything in between has the same value and pairs up with each other
+                    for a in lo..=hi {
+                        for b in a + 1..=hi {
+                            if !emit_with(chosen, &[sorted[a].1, sorted[b].1], emit) {
+                                return false;
+                            }
+                        }
+                    }
+                    return true;
+                } else {
+                    let lo_end = lo
+                        + sorted[lo..]
+                            .iter()
+                            .take_while(|e| e.0 == sorted[lo].0)
+                            .count();
+                    let hi_start = hi + 1
+                        - sorted[..=hi]
+                            .iter()
+                            .rev()
+                            .take_while(|e| e.0 == sorted[hi].0)
+                            .count();
+                    for a in lo..lo_end {
+                        for b in hi_start..=hi {
+                            if !emit_with(chosen, &[sorted[a].1, sorted[b].1], emit) {
+                                return false;
+                            }
+                        }
+                    }
+                    lo = lo_end;
+                    hi = hi_start - 1;
+                }
+            }
+            true
+        }
+        _ => (start..sorted.len()).all(|p| {
+            chosen.push(sorted[p].1);
+            let go_on =
+                search_two_pointer(sorted, p + 1, k - 1, target - sorted[p].0, chosen, emit);
+            chosen.pop();
+            go_on
+        }),
+    }
+}
+
+fn search(numbers: &[i32], k: usize, target: i64, strategy: Strategy, emit: &mut Emit) {
+    let mut chosen = Vec::with_capacity(k);
+    match strategy {
+        Strategy::Hashing => {
+            search_hashing(numbers, 0, k, target, &mut chosen, emit);
+        }
+        Strategy::TwoPointer => {
+            let mut sorted = numbers
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| (v as i64, i))
+                .collect::<Vec<(i64, usize)>>();
+            sorted.sort_unstable();
+            search_two_pointer(&sorted, 0, k, target, &mut chosen, emit);
+        }
+    }
+}
+
+/// Some combination of `k` entries summing to `target`. Which one depends on the strategy.
+pub fn first(numbers: &[i32], k: usize, target: i64, strategy: Strategy) -> Option<Combination> {
+    let mut ret = None;
+    search(numbers, k, target, strategy, &mut |indices| {
+        ret = Some(Combination::new(numbers, indices));
+        false
+    });
+    ret
+}
+
+/// Every combination of `k` entries summing to `target`, ordered by their indices.
+pub fn all(numbers: &[i32], k: usize, target: i64, strategy: Strategy) -> Vec<Combination> {
+    let mut ret = Vec::new();
+    search(numbers, k, target, strategy, &mut |indices| {
+        ret.push(Combination::new(numbers, indices));
+        true
+    });
+    ret.sort_by(|a, b| a.indices.cmp(&b.indices));
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    const STRATEGIES: [Strategy; 2] = [Strategy::Hashing, Strategy::TwoPointer];
+    const EXAMPLE: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    fn brute_force(numbers: &[i32], k: usize, target: i64) -> Vec<Vec<usize>> {
+        fn rec(
+            n: usize,
+            k: usize,
+            start: usize,
+            chosen: &mut Vec<usize>,
+            out: &mut Vec<Vec<usize>>,
+        ) {
+            if k == 0 {
+                out.push(chosen.clone());
+                return;
+            }
+            for i in start..n {
+                chosen.push(i);
+                rec(n, k - 1, i + 1, chosen, out);
+                chosen.pop();
+            }
+        }
+        let mut subsets = Vec::new();
+        rec(numbers.len(), k, 0, &mut Vec::new(), &mut subsets);
+        subsets
+            .into_iter()
+            .filter(|s| s.iter().map(|&i| numbers[i] as i64).sum::<i64>() == target)
+            .collect()
+    }
+
+    #[test]
+    fn test_example() {
+        for strategy in STRATEGIES {
+            let pair = first(&EXAMPLE, 2, 2020, strategy).unwrap();
+            assert_eq!(pair.indices, [0, 3]);
+            assert_eq!(pair.values, [1721, 299]);
+            assert_eq!(pair.product(), Some(514579));
+
+            let triple = first(&EXAMPLE, 3, 2020, strategy).unwrap();
+            assert_eq!(triple.values, [979, 366, 675]);
+            assert_eq!(triple.product(), Some(241861950));
+
+            assert_eq!(first(&EXAMPLE, 2, 1, strategy), None);
+            assert!(all(&EXAMPLE, 4, 2020, strategy).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        let numbers = [1010, 5, 1010, 1010, 2015];
+        for strategy in STRATEGIES {
+            let pairs = all(&numbers, 2, 2020, strategy)
+                .into_iter()
+                .map(|c| c.indices)
+                .collect::<Vec<_>>();
+            assert_eq!(pairs, [vec![0, 2], vec![0, 3], vec![1, 4], vec![2, 3]]);
+
+            // A single 1010 can't be used twice
+            assert_eq!(first(&[1010, 7], 2, 2020, strategy), None);
+            assert_eq!(all(&[3, 3, 3, 3], 3, 9, strategy).len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_small_k() {
+        for strategy in STRATEGIES {
+            assert_eq!(all(&EXAMPLE, 0, 0, strategy).len(), 1);
+            assert!(all(&EXAMPLE, 0, 5, strategy).is_empty());
+            let single = all(&[4, 2, 4], 1, 4, strategy);
+            assert_eq!(single.len(), 2);
+            assert_eq!(single[1].indices, [2]);
+            assert!(all(&[2020], 2, 2020, strategy).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_strategies_agree_with_brute_force() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        for _ in 0..30 {
+            let len = rng.gen_range(0..14);
+            let numbers = (0..len)
+                .map(|_| rng.gen_range(-6..10))
+                .collect::<Vec<i32>>();
+            let k = rng.gen_range(0..5);
+            let target = rng.gen_range(-5..15);
+
+            let expected = brute_force(&numbers, k, target);
+            for strategy in STRATEGIES {
+                let found = all(&numbers, k, target, strategy);
+                assert!(found.iter().all(|c| c.sum() == target));
+                let found = found.into_iter().map(|c| c.indices).collect::<Vec<_>>();
+                assert_eq!(found, expected, "{:?} k={} target={}", numbers, k, target);
+                assert_eq!(
+                    first(&numbers, k, target, strategy).is_some(),
+                    !expected.is_empty()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_values() {
+        let numbers = [i32::MAX, i32::MAX, i32::MAX, -3];
+        for strategy in STRATEGIES {
+            let pair = first(&numbers, 2, 2 * i32::MAX as i64, strategy).unwrap();
+            assert_eq!(pair.product(), Some(i32::MAX as i64 * i32::MAX as i64));
+
+            let triple = first(&numbers, 3, 3 * i32::MAX as i64, strategy).unwrap();
+            assert_eq!(triple.product(), None);
+        }
+    }
+}
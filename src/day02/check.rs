@@ -0,0 +1,65 @@
+use common::check::{self, Checker, Problem, Scanner};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Password lists: `min-max letter: password` per line. Both numbers are read as 1-based
+/// positions by some policies, so 0 is never valid. Whether they fit a password is up to the
+/// policy and not checked here.
+pub struct Day02Checker;
+
+fn check_line(s: &mut Scanner) -> Result<(), Problem> {
+    let min_column = s.column();
+    let min = s.number::<usize>()?;
+    s.literal("-")?;
+    let max_column = s.column();
+    let max = s.number::<usize>()?;
+    s.literal(" ")?;
+
+    let letter_column = s.column();
+    let letter = s.word("a letter", |c| c != ':' && !c.is_whitespace())?;
+    if letter.graphemes(true).count() != 1 {
+        return Err(s.problem_at(
+            letter_column,
+            format!("policy letter {:?} is not a single character", letter),
+            "keep a single letter",
+        ));
+    }
+    s.literal(": ")?;
+    s.word("a password", |c| !c.is_whitespace())?;
+
+    for (column, position) in [(min_column, min), (max_column, max)] {
+        if position == 0 {
+            return Err(s.problem_at(column, "position 0 is invalid", "positions start at 1"));
+        }
+    }
+    Ok(())
+}
+
+impl Checker for Day02Checker {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, check_line)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input =
+            "1-3 a: abcde\n1 3 b: cdefg\n2-9 c: ccccccccc\n3-1 a: abc\n1-7 b: bbbb\n0-1 a: a\n";
+        let problems = Day02Checker.check(input);
+        let positions = problems
+            .iter()
+            .map(|p| (p.line, p.column))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [(2, 2), (6, 1)]);
+        assert_eq!(problems[0].message, "expected \"-\", found \"3\"");
+        assert_eq!(problems[0].fix, "write \"-\" here");
+        assert_eq!(problems[1].message, "position 0 is invalid");
+    }
+}
@@ -1,71 +1,206 @@
-struct Policy {
-    letter: char,
-    min: i32,
-    max: i32,
+pub mod check;
+pub mod generate;
+pub mod policy;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use policy::PolicyKind;
+use std::fmt;
+
+/// Splits a line of the password database into its policy text and password. The password
+/// follows the last `": "`, so a policy may contain the separator itself.
+pub fn parse_line(line: &str) -> Result<(String, String), String> {
+    match line.rsplit_once(": ") {
+        Some((policy, password)) if !policy.is_empty() => {
+            Ok((String::from(policy), String::from(password)))
+        }
+        _ => Err(String::from("expected \"<policy>: <password>\"")),
+    }
 }
 
-impl Policy {
-    fn from_string(s: &str) -> Policy {
-        let tokens: Vec<&str> = s.split(|c| c == ' ' || c == '-').collect();
+/// Password of the database that does not satisfy its policy, or whose policy can't be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    /// Number of the entry, counted from 1
+    pub entry: usize,
+    pub policy: String,
+    pub password: String,
+    pub reason: String,
+    /// Whether the policy itself can't be read, `reason` is the error then
+    pub unreadable: bool,
+}
 
-        Policy {
-            letter: tokens[2].chars().next().unwrap(),
-            min: tokens[0].parse().unwrap(),
-            max: tokens[1].parse().unwrap(),
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "entry {}: {}: {}: ",
+            self.entry, self.policy, self.password
+        )?;
+        if self.unreadable {
+            write!(f, "can't read policy, ")?;
         }
+        write!(f, "{}", self.reason)
     }
+}
 
-    fn matches(&self, password: &str) -> bool {
-        let mut cnt: i32 = 0;
+/// Outcome of evaluating every password of a database under one kind of policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub kind: &'static str,
+    pub entries: usize,
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    pub fn valid(&self) -> usize {
+        self.entries - self.failures.len()
+    }
+}
 
-        for c in password.chars(){
-            if c == self.letter{
-                cnt += 1;
+/// Prints how many passwords are valid, the alternate form `{:#}` lists the failures as well.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} passwords are valid under policy {}",
+            self.valid(),
+            self.entries,
+            self.kind
+        )?;
+        if f.alternate() {
+            for failure in &self.failures {
+                writeln!(f, "  {}", failure)?;
             }
         }
+        Ok(())
+    }
+}
+
+/// Evaluates every password against its policy read as a policy of `kind`. Entries whose policy
+/// can't be read fail as well.
+pub fn report(entries: &[(String, String)], kind: &dyn PolicyKind) -> Report {
+    let mut failures = Vec::new();
+    for (i, (text, password)) in entries.iter().enumerate() {
+        let (reason, unreadable) = match kind.parse(text) {
+            Ok(policy) => (policy.violation(password), false),
+            Err(e) => (Some(e), true),
+        };
+        if let Some(reason) = reason {
+            failures.push(Failure {
+                entry: i + 1,
+                policy: text.clone(),
+                password: password.clone(),
+                reason,
+                unreadable,
+            });
+        }
+    }
+    Report {
+        kind: kind.name(),
+        entries: entries.len(),
+        failures,
+    }
+}
 
-        return cnt >= self.min && cnt <= self.max;
+/// Number of passwords satisfying their policy when read as policies of `kind`, an error on the
+/// first policy that can't be read.
+pub fn count_valid(entries: &[(String, String)], kind: &dyn PolicyKind) -> Result<usize, String> {
+    let report = report(entries, kind);
+    match report.failures.iter().find(|f| f.unreadable) {
+        Some(f) => Err(format!("entry {}: {}", f.entry, f.reason)),
+        None => Ok(report.valid()),
     }
+}
 
-    fn matches_new(&self, password: &str) -> bool {
-        let mut cnt: i32 = 0;
+fn count_valid_as(entries: &[(String, String)], name: &str) -> Result<Answer, Error> {
+    let kind = policy::kind(name).unwrap();
+    count_valid(entries, kind.as_ref())
+        .map(Answer::from)
+        .map_err(Error::invalid_input)
+}
 
-        let i1 = (self.min - 1) as usize;
-        let i2 = (self.max - 1) as usize;
+pub struct Day02;
 
-        let chars: Vec<char> = password.chars().collect();
+impl Solution for Day02 {
+    type Input = Vec<(String, String)>;
 
-        if chars[i1] == self.letter{
-            cnt += 1;
-        }
+    fn day(&self) -> u32 {
+        2
+    }
 
-        if chars[i2] == self.letter{
-            cnt += 1;
-        }
+    fn parse(&self, input: &InputSource) -> Result<Self::Input, ParseError> {
+        common::try_parse_file_linewise(input, parse_line)
+    }
 
-        return cnt == 1;
+    fn part1(&self, input: &Self::Input) -> Result<Answer, Error> {
+        count_valid_as(input, "count")
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, Error> {
+        count_valid_as(input, "positions")
     }
 }
 
-fn main() {
-    let lines = common::read_file_linewise("src/day02/input.txt");
-    
-    let mut valid_passwords_old: i32 = 0;
-    let mut valid_passwords_new: i32 = 0;
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    for line in lines {
-        let tokens: Vec<&str> = line.split(": ").collect();
-        let policy = Policy::from_string(&tokens[0]);
+    #[test]
+    fn test_parse_line() {
+        let (policy, password) = parse_line("1-3 a: abcde").unwrap();
+        assert_eq!((policy.as_str(), password.as_str()), ("1-3 a", "abcde"));
 
-        if policy.matches(&tokens[1]) {
-            valid_passwords_old += 1;
-        }
+        let (policy, password) = parse_line("^a: b$: ab").unwrap();
+        assert_eq!((policy.as_str(), password.as_str()), ("^a: b$", "ab"));
 
-        if policy.matches_new(&tokens[1]) {
-            valid_passwords_new += 1;
-        }
+        assert!(parse_line("1-3 a abcde").is_err());
+        assert!(parse_line(": abcde").is_err());
     }
 
-    println!("Valid Passwords (old): {}", valid_passwords_old);
-    println!("Valid Passwords (new): {}", valid_passwords_new);
+    #[test]
+    fn test_count_valid() {
+        let entries = ["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: ccccccccc"]
+            .iter()
+            .map(|line| parse_line(line).unwrap())
+            .collect::<Vec<_>>();
+        let count = |name| count_valid(&entries, policy::kind(name).unwrap().as_ref());
+        assert_eq!(count("count"), Ok(2));
+        assert_eq!(count("positions"), Ok(1));
+        assert_eq!(count("substring"), Ok(2));
+
+        let entries = vec![(String::from("1-x a"), String::from("abcde"))];
+        let error = count_valid(&entries, policy::kind("count").unwrap().as_ref()).unwrap_err();
+        assert!(error.starts_with("entry 1: invalid maximum"), "{}", error);
+    }
+
+    #[test]
+    fn test_report() {
+        let entries = [
+            "1-3 a: abcde",
+            "1-3 b: cdefg",
+            "2-9 c: ccccccccc",
+            "1-9 b: ab",
+            "0-2 a: abc",
+            "1-9 a: ab",
+        ]
+        .iter()
+        .map(|line| parse_line(line).unwrap())
+        .collect::<Vec<_>>();
+        let positions = report(&entries, policy::kind("positions").unwrap().as_ref());
+        assert_eq!(positions.valid(), 2);
+        assert_eq!(
+            positions.to_string(),
+            "2 of 6 passwords are valid under policy positions\n"
+        );
+        assert_eq!(
+            format!("{:#}", positions),
+            "2 of 6 passwords are valid under policy positions\n  \
+             entry 2: 1-3 b: cdefg: \"b\" is at neither position 1 nor 3, found \"c\" and \"e\"\n  \
+             entry 3: 2-9 c: ccccccccc: \"c\" is at both positions 2 and 9\n  \
+             entry 4: 1-9 b: ab: \"b\" is at neither position 1 nor 9, found \"a\" and nothing; \
+             position 9 is past the end of the 2 character password\n  \
+             entry 5: 0-2 a: abc: can't read policy, position 0 is invalid, positions start at 1\n"
+        );
+        assert!(positions.failures[3].unreadable);
+    }
 }
@@ -0,0 +1,62 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+
+/// Password databases with policies whose positions always lie within the password.
+///
+/// Passwords are biased towards the policy letter so that both policies are matched by a fair
+/// share of them. The size is the number of lines.
+pub struct Day02Generator;
+
+impl Generator for Day02Generator {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn default_size(&self) -> usize {
+        1000
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut lines = Vec::with_capacity(size);
+        let mut valid_count = 0;
+        let mut valid_positions = 0;
+
+        for _ in 0..size {
+            let letter = rng.gen_range(b'a'..=b'z') as char;
+            let len = rng.gen_range(3..=20);
+            let password = (0..len)
+                .map(|_| {
+                    if rng.gen_bool(0.4) {
+                        letter
+                    } else {
+                        rng.gen_range(b'a'..=b'z') as char
+                    }
+                })
+                .collect::<Vec<char>>();
+            let min = rng.gen_range(1..len);
+            let max = rng.gen_range(min + 1..=len);
+
+            let count = password.iter().filter(|&&c| c == letter).count();
+            if (min..=max).contains(&count) {
+                valid_count += 1;
+            }
+            if (password[min - 1] == letter) != (password[max - 1] == letter) {
+                valid_positions += 1;
+            }
+
+            lines.push(format!(
+                "{}-{} {}: {}",
+                min,
+                max,
+                letter,
+                password.iter().collect::<String>()
+            ));
+        }
+
+        Generated::new(
+            generate::lines(lines),
+            Some(valid_count.into()),
+            Some(valid_positions.into()),
+        )
+    }
+}
@@ -0,0 +1,532 @@
+//! Password policies of different kinds, selected by name.
+//!
+//! A line of the password database is `<policy>: <password>`. How the policy text is read and
+//! what it requires depends on the kind the database is evaluated with, so the same line can
+//! mean different things: `1-3 a` asks for one to three `a` under `count` and for an `a` at
+//! exactly one of the positions 1 and 3 under `positions`.
+//!
+//! Letters and positions are grapheme clusters rather than chars, so a letter with a combining
+//! accent is a single letter at a single position whether or not it is precomposed.
+
+use regex::Regex;
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Rule a password has to satisfy.
+pub trait Policy: fmt::Debug {
+    /// Why the password does not satisfy the policy, `None` if it does.
+    fn violation(&self, password: &str) -> Option<String>;
+
+    fn matches(&self, password: &str) -> bool {
+        self.violation(password).is_none()
+    }
+}
+
+/// Kind of policy: how the policy text of a line is read.
+pub trait PolicyKind: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Syntax of the policy text and what it requires, for the usage text.
+    fn description(&self) -> &'static str;
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Policy>, String>;
+}
+
+/// Kinds of policies by name, the first two being those of the puzzle.
+pub fn kinds() -> Vec<Box<dyn PolicyKind>> {
+    vec![
+        Box::new(CountKind),
+        Box::new(PositionsKind),
+        Box::new(AtLeastKind),
+        Box::new(SubstringKind),
+        Box::new(RegexKind),
+    ]
+}
+
+pub fn kind(name: &str) -> Option<Box<dyn PolicyKind>> {
+    kinds().into_iter().find(|k| k.name() == name)
+}
+
+fn parse_number(s: &str, what: &str) -> Result<usize, String> {
+    s.parse()
+        .map_err(|e| format!("invalid {} {:?}: {}", what, s, e))
+}
+
+fn parse_position(s: &str) -> Result<usize, String> {
+    match parse_number(s, "position")? {
+        0 => Err(String::from("position 0 is invalid, positions start at 1")),
+        p => Ok(p),
+    }
+}
+
+/// Splits `<first>-<second> <rest>` as used by the range policies of the puzzle.
+fn parse_range<'a>(text: &'a str, what: &str) -> Result<(&'a str, &'a str, &'a str), String> {
+    let (range, rest) = text.split_once(' ').ok_or_else(|| {
+        format!(
+            "expected policy of the form \"a-b {}\", got {:?}",
+            what, text
+        )
+    })?;
+    let (first, second) = range
+        .split_once('-')
+        .ok_or_else(|| format!("expected a range \"a-b\", got {:?}", range))?;
+    if rest.is_empty() || rest.contains(' ') {
+        return Err(format!("expected a single {}, got {:?}", what, rest));
+    }
+    Ok((first, second, rest))
+}
+
+fn parse_bounds(min: &str, max: &str) -> Result<(usize, usize), String> {
+    Ok((parse_number(min, "minimum")?, parse_number(max, "maximum")?))
+}
+
+fn parse_letter(s: &str) -> Result<String, String> {
+    match graphemes(s).as_slice() {
+        [_] => Ok(String::from(s)),
+        _ => Err(format!(
+            "policy letter must be a single character, got {:?}",
+            s
+        )),
+    }
+}
+
+fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+/// Letters of a password at 1-based positions, `None` for positions outside of it, and a note
+/// on the first of those to explain why nothing was found there.
+fn letters_at<'a>(
+    password: &'a str,
+    positions: &[usize],
+) -> (Vec<Option<&'a str>>, Option<String>) {
+    let letters = graphemes(password);
+    let found = positions
+        .iter()
+        .map(|&p| p.checked_sub(1).and_then(|i| letters.get(i).copied()))
+        .collect::<Vec<Option<&str>>>();
+    let note = positions
+        .iter()
+        .zip(&found)
+        .find(|(_, letter)| letter.is_none())
+        .map(|(&p, _)| match p {
+            0 => String::from("position 0 does not exist, positions start at 1"),
+            p => format!(
+                "position {} is past the end of the {} character password",
+                p,
+                letters.len()
+            ),
+        });
+    (found, note)
+}
+
+fn with_note(message: String, note: Option<String>) -> String {
+    match note {
+        Some(note) => format!("{}; {}", message, note),
+        None => message,
+    }
+}
+
+fn show(letter: Option<&str>) -> String {
+    letter.map_or_else(|| String::from("nothing"), |g| format!("{:?}", g))
+}
+
+fn list(positions: &[usize]) -> String {
+    positions
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// A letter occurs between `min` and `max` times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Count {
+    pub letter: String,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Policy for Count {
+    fn violation(&self, password: &str) -> Option<String> {
+        let count = password
+            .graphemes(true)
+            .filter(|&g| g == self.letter)
+            .count();
+        if (self.min..=self.max).contains(&count) {
+            return None;
+        }
+        Some(format!(
+            "{:?} occurs {} time(s), expected {} to {}",
+            self.letter, count, self.min, self.max
+        ))
+    }
+}
+
+struct CountKind;
+
+impl PolicyKind for CountKind {
+    fn name(&self) -> &'static str {
+        "count"
+    }
+
+    fn description(&self) -> &'static str {
+        "\"min-max c\": c occurs between min and max times"
+    }
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Policy>, String> {
+        let (min, max, letter) = parse_range(text, "letter")?;
+        let (min, max) = parse_bounds(min, max)?;
+        Ok(Box::new(Count {
+            letter: parse_letter(letter)?,
+            min,
+            max,
+        }))
+    }
+}
+
+/// A letter is at exactly one of two 1-based positions. Positions past the end of the password
+/// never match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Positions {
+    pub letter: String,
+    pub first: usize,
+    pub second: usize,
+}
+
+impl Policy for Positions {
+    fn violation(&self, password: &str) -> Option<String> {
+        let (found, note) = letters_at(password, &[self.first, self.second]);
+        let letter = Some(self.letter.as_str());
+        let message = match (found[0] == letter, found[1] == letter) {
+            (true, true) => format!(
+                "{:?} is at both positions {} and {}",
+                self.letter, self.first, self.second
+            ),
+            (false, false) => format!(
+                "{:?} is at neither position {} nor {}, found {} and {}",
+                self.letter,
+                self.first,
+                self.second,
+                show(found[0]),
+                show(found[1])
+            ),
+            _ => return None,
+        };
+        Some(with_note(message, note))
+    }
+}
+
+struct PositionsKind;
+
+impl PolicyKind for PositionsKind {
+    fn name(&self) -> &'static str {
+        "positions"
+    }
+
+    fn description(&self) -> &'static str {
+        "\"i-j c\": c is at exactly one of the positions i and j, counted from 1"
+    }
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Policy>, String> {
+        let (first, second, letter) = parse_range(text, "letter")?;
+        Ok(Box::new(Positions {
+            letter: parse_letter(letter)?,
+            first: parse_position(first)?,
+            second: parse_position(second)?,
+        }))
+    }
+}
+
+/// A letter is at `n` or more of the given 1-based positions. Positions past the end of the
+/// password never match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtLeast {
+    pub letter: String,
+    pub n: usize,
+    pub positions: Vec<usize>,
+}
+
+impl Policy for AtLeast {
+    fn violation(&self, password: &str) -> Option<String> {
+        let (found, note) = letters_at(password, &self.positions);
+        let hits = found
+            .iter()
+            .filter(|&&g| g == Some(self.letter.as_str()))
+            .count();
+        if hits >= self.n {
+            return None;
+        }
+        let message = format!(
+            "{:?} is at {} of the positions {}, expected at least {}",
+            self.letter,
+            hits,
+            list(&self.positions),
+            self.n
+        );
+        Some(with_note(message, note))
+    }
+}
+
+struct AtLeastKind;
+
+impl PolicyKind for AtLeastKind {
+    fn name(&self) -> &'static str {
+        "at-least"
+    }
+
+    fn description(&self) -> &'static str {
+        "\"n of i,j,... c\": c is at n or more of the positions, counted from 1"
+    }
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Policy>, String> {
+        let tokens = text.split(' ').collect::<Vec<&str>>();
+        match tokens.as_slice() {
+            [n, "of", positions, letter] => {
+                let positions = positions
+                    .split(',')
+                    .map(parse_position)
+                    .collect::<Result<Vec<usize>, String>>()?;
+                let n = parse_number(n, "count")?;
+                if n > positions.len() {
+                    return Err(format!(
+                        "{} of {} positions can never match",
+                        n,
+                        positions.len()
+                    ));
+                }
+                Ok(Box::new(AtLeast {
+                    letter: parse_letter(letter)?,
+                    n,
+                    positions,
+                }))
+            }
+            _ => Err(format!(
+                "expected policy of the form \"n of i,j,... c\", got {:?}",
+                text
+            )),
+        }
+    }
+}
+
+/// A text of one or more characters occurs between `min` and `max` times, overlapping
+/// occurrences included. Occurrences start and end at letters, so "e" is not found in an "e"
+/// with a combining accent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Substring {
+    pub text: String,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Policy for Substring {
+    fn violation(&self, password: &str) -> Option<String> {
+        let text = graphemes(&self.text);
+        let count = match text.len() {
+            0 => 0,
+            len => graphemes(password)
+                .windows(len)
+                .filter(|w| *w == text.as_slice())
+                .count(),
+        };
+        if (self.min..=self.max).contains(&count) {
+            return None;
+        }
+        Some(format!(
+            "{:?} occurs {} time(s), expected {} to {}",
+            self.text, count, self.min, self.max
+        ))
+    }
+}
+
+struct SubstringKind;
+
+impl PolicyKind for SubstringKind {
+    fn name(&self) -> &'static str {
+        "substring"
+    }
+
+    fn description(&self) -> &'static str {
+        "\"min-max text\": text occurs between min and max times, overlaps included"
+    }
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Policy>, String> {
+        let (min, max, text) = parse_range(text, "text")?;
+        let (min, max) = parse_bounds(min, max)?;
+        Ok(Box::new(Substring {
+            text: String::from(text),
+            min,
+            max,
+        }))
+    }
+}
+
+/// The password contains a match of a regular expression.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    pub regex: Regex,
+}
+
+impl Policy for Pattern {
+    fn violation(&self, password: &str) -> Option<String> {
+        if self.regex.is_match(password) {
+            return None;
+        }
+        Some(format!(
+            "does not contain a match of {:?}",
+            self.regex.as_str()
+        ))
+    }
+}
+
+struct RegexKind;
+
+impl PolicyKind for RegexKind {
+    fn name(&self) -> &'static str {
+        "regex"
+    }
+
+    fn description(&self) -> &'static str {
+        "\"pattern\": the password contains a match, use ^ and $ to match all of it"
+    }
+
+    fn parse(&self, text: &str) -> Result<Box<dyn Policy>, String> {
+        Regex::new(text)
+            .map(|regex| Box::new(Pattern { regex }) as Box<dyn Policy>)
+            .map_err(|e| format!("invalid regular expression {:?}: {}", text, e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn policy(kind_name: &str, text: &str) -> Box<dyn Policy> {
+        kind(kind_name).unwrap().parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_kinds_have_unique_names() {
+        let kinds = kinds();
+        for (i, k) in kinds.iter().enumerate() {
+            assert!(kinds[i + 1..].iter().all(|other| other.name() != k.name()));
+        }
+        assert!(kind("nope").is_none());
+    }
+
+    #[test]
+    fn test_count() {
+        assert!(policy("count", "1-3 a").matches("abcde"));
+        assert!(!policy("count", "1-3 b").matches("cdefg"));
+        assert!(policy("count", "2-9 c").matches("ccccccccc"));
+        assert_eq!(
+            policy("count", "1-3 b").violation("cdefg").unwrap(),
+            "\"b\" occurs 0 time(s), expected 1 to 3"
+        );
+
+        let count = kind("count").unwrap();
+        assert!(count.parse("1-x a").is_err());
+        assert!(count.parse("1-3 ab").is_err());
+        assert!(count.parse("13 a").is_err());
+    }
+
+    #[test]
+    fn test_positions() {
+        assert!(policy("positions", "1-3 a").matches("abcde"));
+        assert!(!policy("positions", "1-3 b").matches("cdefg"));
+        assert!(!policy("positions", "2-9 c").matches("ccccccccc"));
+        assert_eq!(
+            policy("positions", "2-9 c").violation("ccccccccc").unwrap(),
+            "\"c\" is at both positions 2 and 9"
+        );
+        assert_eq!(
+            policy("positions", "1-3 b").violation("cdefg").unwrap(),
+            "\"b\" is at neither position 1 nor 3, found \"c\" and \"e\""
+        );
+    }
+
+    #[test]
+    fn test_positions_out_of_range() {
+        assert!(policy("positions", "1-12 a").matches("abc"));
+        assert_eq!(
+            policy("positions", "1-12 b").violation("abc").unwrap(),
+            "\"b\" is at neither position 1 nor 12, found \"a\" and nothing; \
+             position 12 is past the end of the 3 character password"
+        );
+        assert!(!policy("positions", "1-2 a").matches(""));
+
+        // Built directly, position 0 is never a match
+        let p = Positions {
+            letter: String::from("a"),
+            first: 0,
+            second: 1,
+        };
+        assert!(p.matches("ab"));
+        assert_eq!(
+            p.violation("ba").unwrap(),
+            "\"a\" is at neither position 0 nor 1, found nothing and \"b\"; \
+             position 0 does not exist, positions start at 1"
+        );
+
+        let error = kind("positions").unwrap().parse("0-2 a").unwrap_err();
+        assert_eq!(error, "position 0 is invalid, positions start at 1");
+        assert!(kind("at-least").unwrap().parse("1 of 0,2 a").is_err());
+    }
+
+    #[test]
+    fn test_grapheme_positions() {
+        // "é" as "e" followed by a combining accent is a single letter
+        let password = "ae\u{301}b";
+        assert!(policy("positions", "2-3 b").matches(password));
+        assert!(policy("positions", "2-3 b").matches("a\u{e9}b"));
+        assert!(policy("positions", "3-4 b").matches(password));
+        assert!(policy("positions", "1-2 e\u{301}").matches(password));
+        assert!(policy("count", "0-0 e").matches(password));
+        assert!(policy("count", "1-1 e\u{301}").matches(password));
+        assert!(policy("positions", "1-2 \u{1F44D}\u{1F3FD}").matches("\u{1F44D}\u{1F3FD}x"));
+    }
+
+    #[test]
+    fn test_at_least() {
+        let p = policy("at-least", "2 of 1,3,5 a");
+        assert!(p.matches("abaca"));
+        assert!(p.matches("abacx"));
+        assert!(!p.matches("abxcx"));
+        assert_eq!(
+            p.violation("abxcx").unwrap(),
+            "\"a\" is at 1 of the positions 1,3,5, expected at least 2"
+        );
+        assert!(p.matches("aba"));
+        assert_eq!(
+            p.violation("ab").unwrap(),
+            "\"a\" is at 1 of the positions 1,3,5, expected at least 2; \
+             position 3 is past the end of the 2 character password"
+        );
+
+        let at_least = kind("at-least").unwrap();
+        assert!(at_least.parse("4 of 1,2,3 a").is_err());
+        assert!(at_least.parse("2 from 1,2 a").is_err());
+        assert!(at_least.parse("1 of 1,x a").is_err());
+    }
+
+    #[test]
+    fn test_substring() {
+        let p = policy("substring", "2-2 aa");
+        assert!(p.matches("aaa"));
+        assert!(!p.matches("aa"));
+        assert!(!p.matches("aaaa"));
+        assert!(policy("substring", "1-1 ü").matches("müde"));
+        assert!(policy("substring", "0-0 e").matches("ae\u{301}b"));
+        assert!(policy("substring", "1-1 e\u{301}b").matches("ae\u{301}b"));
+        assert!(kind("substring").unwrap().parse("1-2").is_err());
+    }
+
+    #[test]
+    fn test_regex() {
+        let p = policy("regex", "^[a-z]+[0-9]$");
+        assert!(p.matches("hunter2"));
+        assert!(!p.matches("hunter"));
+        assert!(policy("regex", "x").matches("axb"));
+        assert!(kind("regex").unwrap().parse("(").is_err());
+    }
+}
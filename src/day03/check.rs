@@ -0,0 +1,18 @@
+use common::check::{self, Checker, Problem};
+
+/// Maps of open squares `.` and trees `#`.
+pub struct Day03Checker;
+
+impl Checker for Day03Checker {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+        check::check_grid(&lines, ".#")
+    }
+}
@@ -1,83 +1,64 @@
-struct Field {
-    field: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
-}
-
-impl Field {
-    fn from_file(filename: &str) -> Field{
-        Field::from_vec(&common::read_file_linewise(filename))
-    }
+pub mod check;
+pub mod generate;
 
-    fn from_vec(lines: &Vec<String>) -> Field {
-        let height = lines.len();
-        let width = lines[0].len();
+use common::{Answer, Error, Grid, InputSource, ParseError, Solution};
+use nalgebra::Point2;
 
-        let mut field = Vec::<Vec<char>>::new();
+pub type Field = Grid<char>;
 
-        for line in lines {
-            if line.len() != width {
-                panic!("Lines are not equal length.");
-            }
-            field.push(line.chars().collect());
-        }
-
-        return Field {
-            field: field,
-            width: width,
-            height: height,
-        };
-    }
-
-    #[allow(dead_code)]
-    fn print(&self) {
-        for line in &self.field {
-            for c in line {
-                print!("{}", c)
-            }
-            print!("\n")
-        }
-    }
-
-    fn at(&self, x: usize, y: usize) -> char {
-        if y >= self.height {
-            panic!("Out of range");
-        }
-
-        return self.field[y][x % (self.width)];
+fn parse_square(c: char) -> Result<char, String> {
+    match c {
+        '.' | '#' => Ok(c),
+        _ => Err(format!("unknown square {:?}", c)),
     }
 }
 
-fn count_trees(field: &Field, dx: usize, dy: usize) -> i32 {
+pub fn count_trees(field: &Field, dx: i32, dy: i32) -> i32 {
     let mut cnt: i32 = 0;
-    let mut x: usize = 0;
-    let mut y: usize = 0;
+    let mut x: i32 = 0;
+    let mut y: i32 = 0;
 
-    while y < field.height - 1 {
+    while y + dy < field.height() {
         x += dx;
         y += dy;
-        if field.at(x, y) == '#' {
+        if *field.get_wrapping(Point2::new(x, y)) == '#' {
             cnt += 1;
         }
     }
 
-    return cnt;
+    cnt
 }
 
-fn count_multiple(field: &Field, directions: &[(usize, usize)]) -> i64 {
+pub fn count_multiple(field: &Field, directions: &[(i32, i32)]) -> i64 {
     let mut cnt: i64 = 1;
 
     for d in directions {
-        cnt *= count_trees(&field, d.0, d.1) as i64;
+        cnt *= count_trees(field, d.0, d.1) as i64;
     }
 
-    return cnt;
+    cnt
 }
 
-fn main() {
-    let field = Field::from_file("src/day03/input.txt");
-    println!("Num trees encountered: {}", count_trees(&field, 3, 1));
-    println!("Num multiplied trees: {}", count_multiple(&field, &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]));
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Field;
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Field, ParseError> {
+        Field::parse(input, parse_square)
+    }
+
+    fn part1(&self, field: &Field) -> Result<Answer, Error> {
+        Ok(count_trees(field, 3, 1).into())
+    }
+
+    fn part2(&self, field: &Field) -> Result<Answer, Error> {
+        Ok(count_multiple(field, &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]).into())
+    }
 }
 
 #[cfg(test)]
@@ -86,13 +67,13 @@ mod test {
 
     #[test]
     fn test_count_trees() {
-        let field = Field::from_file("src/day03/input_test.txt");
+        let field = Field::parse(&"src/day03/input_test.txt".into(), parse_square).unwrap();
         assert_eq!(count_trees(&field, 3, 1), 7);
     }
 
     #[test]
     fn test_count_multiple() {
-        let field = Field::from_file("src/day03/input_test.txt");
+        let field = Field::parse(&"src/day03/input_test.txt".into(), parse_square).unwrap();
         assert_eq!(
             count_multiple(&field, &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]),
             336
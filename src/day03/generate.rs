@@ -0,0 +1,47 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+
+const WIDTH: usize = 31;
+
+/// Maps of 31 squares wide with about a quarter of them trees. The size is the number of rows.
+pub struct Day03Generator;
+
+fn trees_on_slope(rows: &[Vec<bool>], dx: usize, dy: usize) -> i64 {
+    (1..)
+        .map(|step| (step * dx, step * dy))
+        .take_while(|&(_, y)| y < rows.len())
+        .filter(|&(x, y)| rows[y][x % WIDTH])
+        .count() as i64
+}
+
+impl Generator for Day03Generator {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn default_size(&self) -> usize {
+        323
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let rows = (0..size.max(1))
+            .map(|_| (0..WIDTH).map(|_| rng.gen_bool(0.25)).collect())
+            .collect::<Vec<Vec<bool>>>();
+
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        let product = slopes
+            .iter()
+            .map(|&(dx, dy)| trees_on_slope(&rows, dx, dy))
+            .product::<i64>();
+
+        Generated::new(
+            generate::lines(rows.iter().map(|row| {
+                row.iter()
+                    .map(|&tree| if tree { '#' } else { '.' })
+                    .collect::<String>()
+            })),
+            Some(trees_on_slope(&rows, 3, 1).into()),
+            Some(product.into()),
+        )
+    }
+}
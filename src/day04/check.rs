@@ -0,0 +1,59 @@
+use common::check::{self, Checker, Problem, Scanner};
+use std::collections::HashSet;
+
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+/// Passports: groups of `key:value` fields separated by spaces or line breaks. Values and
+/// missing fields are what the puzzle validates, so only the field names are checked.
+pub struct Day04Checker;
+
+fn check_fields<'a>(s: &mut Scanner<'a>, seen: &mut HashSet<&'a str>) -> Result<(), Problem> {
+    loop {
+        let column = s.column();
+        let key = s.word("a field name", |c| c.is_ascii_lowercase())?;
+        if !FIELDS.contains(&key) {
+            return Err(s.problem_at(
+                column,
+                format!("unknown field {:?}", key),
+                format!("use one of {}", FIELDS.join(", ")),
+            ));
+        }
+        if !seen.insert(key) {
+            return Err(s.problem_at(
+                column,
+                format!("field {:?} appears twice in the passport", key),
+                "remove one of them or separate the passports by a blank line",
+            ));
+        }
+        s.literal(":")?;
+        s.word("a value", |c| !c.is_whitespace())?;
+        if s.at_end() {
+            return Ok(());
+        }
+        s.word("a space", char::is_whitespace)?;
+    }
+}
+
+impl Checker for Day04Checker {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let groups = check::groups(input);
+        if groups.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = Vec::new();
+        for group in &groups {
+            let mut seen = HashSet::new();
+            for line in group {
+                if let Err(problem) = check_fields(&mut line.scanner(), &mut seen) {
+                    problems.push(problem);
+                }
+            }
+        }
+        problems
+    }
+}
@@ -1,31 +1,32 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use std::collections::HashMap;
 use substring::Substring;
 
-struct Passport {
+pub struct Passport {
     data: HashMap<String, String>,
 }
 
 impl Passport {
-    fn from_string(s: &str) -> Passport {
+    pub fn from_string(s: &str) -> Result<Passport, String> {
         let mut map = HashMap::<String, String>::new();
-        let tokens: Vec<&str> = s
-            .split(|c| c == ' ' || c == '\n')
-            .map(|line| line.trim())
-            .collect();
+        let tokens: Vec<&str> = s.split([' ', '\n']).map(|line| line.trim()).collect();
         for t in tokens {
             let key_value: Vec<&str> = t.split(":").collect();
 
             if key_value.len() != 2 {
-                panic!("Key-Value pair should only have two elements");
+                return Err(format!("expected key:value pair, got {:?}", t));
             }
 
             map.insert(String::from(key_value[0]), String::from(key_value[1]));
         }
 
-        Passport { data: map }
+        Ok(Passport { data: map })
     }
 
-    fn is_val_in_range(s: &String, min: i32, max: i32) -> bool {
+    fn is_val_in_range(s: &str, min: i32, max: i32) -> bool {
         let val = match s.parse::<i32>() {
             Ok(v) => v,
             Err(_) => return false,
@@ -35,20 +36,22 @@ impl Passport {
             return false;
         }
 
-        return true;
+        true
     }
 
-    fn is_valid(&self) -> bool {
+    /// Whether all required fields are present.
+    pub fn is_valid(&self) -> bool {
         let required_fields = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
         for &f in &required_fields {
             if !self.data.contains_key(f) {
                 return false;
             }
         }
-        return true;
+        true
     }
 
-    fn is_valid_extensive(&self) -> bool {
+    /// Whether all required fields are present and hold valid values.
+    pub fn is_valid_extensive(&self) -> bool {
         if !self.is_valid() {
             return false;
         }
@@ -93,7 +96,7 @@ impl Passport {
         }
 
         let ecl = self.data.get("ecl").unwrap();
-        if !vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
+        if !["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
             .iter()
             .any(|x| x == ecl)
         {
@@ -111,31 +114,34 @@ impl Passport {
             }
         }
 
-        return true;
+        true
     }
 }
 
-fn main() {
-    let data = common::read_grouped_file("src/day04/input.txt");
+pub struct Day04;
 
-    let mut cnt_valid_simple: i32 = 0;
-    let mut cnt_valid_extensive: i32 = 0;
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
 
-    for d in data {
-        let p = Passport::from_string(&d);
-        if p.is_valid() {
-            cnt_valid_simple += 1;
-        }
-        if p.is_valid_extensive() {
-            cnt_valid_extensive += 1;
-        }
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<Passport>, ParseError> {
+        common::try_parse_grouped_file(input, Passport::from_string)
     }
 
-    println!("Number of simple valid passports: {}", cnt_valid_simple);
-    println!(
-        "Number of extensive valid passports: {}",
-        cnt_valid_extensive
-    );
+    fn part1(&self, passports: &Vec<Passport>) -> Result<Answer, Error> {
+        Ok(passports.iter().filter(|p| p.is_valid()).count().into())
+    }
+
+    fn part2(&self, passports: &Vec<Passport>) -> Result<Answer, Error> {
+        Ok(passports
+            .iter()
+            .filter(|p| p.is_valid_extensive())
+            .count()
+            .into())
+    }
 }
 
 #[cfg(test)]
@@ -149,13 +155,13 @@ mod test {
         let mut passports = Vec::<Passport>::new();
 
         for d in data {
-            passports.push(Passport::from_string(&d));
+            passports.push(Passport::from_string(&d).unwrap());
         }
 
-        assert_eq!(passports[0].is_valid(), true);
-        assert_eq!(passports[1].is_valid(), false);
-        assert_eq!(passports[2].is_valid(), true);
-        assert_eq!(passports[3].is_valid(), false);
+        assert!(passports[0].is_valid());
+        assert!(!passports[1].is_valid());
+        assert!(passports[2].is_valid());
+        assert!(!passports[3].is_valid());
     }
 
     #[test]
@@ -163,7 +169,7 @@ mod test {
         let data = common::read_grouped_file("src/day04/input_test_02_valid.txt");
 
         for d in data {
-            assert_eq!(Passport::from_string(&d).is_valid_extensive(), true);
+            assert!(Passport::from_string(&d).unwrap().is_valid_extensive());
         }
     }
 
@@ -172,7 +178,7 @@ mod test {
         let data = common::read_grouped_file("src/day04/input_test_02_invalid.txt");
 
         for d in data {
-            assert_eq!(Passport::from_string(&d).is_valid_extensive(), false);
+            assert!(!Passport::from_string(&d).unwrap().is_valid_extensive());
         }
     }
 }
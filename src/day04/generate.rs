@@ -0,0 +1,113 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// Batches of passports that are valid, miss a required field or hold exactly one invalid value,
+/// in about equal shares. The size is the number of passports.
+pub struct Day04Generator;
+
+fn digits(rng: &mut StdRng, n: usize) -> String {
+    (0..n).map(|_| rng.gen_range(0..10).to_string()).collect()
+}
+
+fn valid_value(rng: &mut StdRng, field: &str) -> String {
+    match field {
+        "byr" => rng.gen_range(1920..=2002).to_string(),
+        "iyr" => rng.gen_range(2010..=2020).to_string(),
+        "eyr" => rng.gen_range(2020..=2030).to_string(),
+        "hgt" if rng.gen_bool(0.5) => format!("{}cm", rng.gen_range(150..=193)),
+        "hgt" => format!("{}in", rng.gen_range(59..=76)),
+        "hcl" => format!("#{:06x}", rng.gen_range(0..0x1000000)),
+        "ecl" => EYE_COLORS.choose(rng).unwrap().to_string(),
+        "pid" => digits(rng, 9),
+        _ => digits(rng, 3),
+    }
+}
+
+fn invalid_value(rng: &mut StdRng, field: &str) -> String {
+    match field {
+        "byr" => rng.gen_range(2003..2030).to_string(),
+        "iyr" => rng.gen_range(1990..2010).to_string(),
+        "eyr" => rng.gen_range(2031..2040).to_string(),
+        "hgt" => match rng.gen_range(0..3) {
+            0 => format!("{}cm", rng.gen_range(194..250)),
+            1 => format!("{}in", rng.gen_range(20..59)),
+            _ => rng.gen_range(150..194).to_string(),
+        },
+        "hcl" if rng.gen_bool(0.5) => format!("{:06x}", rng.gen_range(0..0x1000000)),
+        "hcl" => format!("#{:05x}z", rng.gen_range(0..0x100000)),
+        "ecl" => ["zzz", "red", "gmt", "#123abc"]
+            .choose(rng)
+            .unwrap()
+            .to_string(),
+        _ => {
+            let len = if rng.gen_bool(0.5) { 8 } else { 10 };
+            digits(rng, len)
+        }
+    }
+}
+
+impl Generator for Day04Generator {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn default_size(&self) -> usize {
+        290
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut passports = Vec::with_capacity(size);
+        let mut complete = 0;
+        let mut valid = 0;
+
+        for _ in 0..size {
+            let mut fields = FIELDS.to_vec();
+            if rng.gen_bool(0.5) {
+                fields.push("cid");
+            }
+            fields.shuffle(rng);
+
+            let kind = rng.gen_range(0..3);
+            let mut entries = fields
+                .iter()
+                .map(|&f| format!("{}:{}", f, valid_value(rng, f)))
+                .collect::<Vec<String>>();
+            match kind {
+                0 => valid += 1,
+                1 => {
+                    let i = rng.gen_range(0..fields.len());
+                    let field = if fields[i] == "cid" { "pid" } else { fields[i] };
+                    let i = fields.iter().position(|&f| f == field).unwrap();
+                    entries[i] = format!("{}:{}", field, invalid_value(rng, field));
+                }
+                _ => {
+                    let missing = FIELDS.choose(rng).unwrap();
+                    let i = fields.iter().position(|f| f == missing).unwrap();
+                    entries.remove(i);
+                }
+            }
+            if kind != 2 {
+                complete += 1;
+            }
+
+            let text = entries.iter().fold(String::new(), |mut text, entry| {
+                if !text.is_empty() {
+                    text.push(if rng.gen_bool(0.3) { '\n' } else { ' ' });
+                }
+                text.push_str(entry);
+                text
+            });
+            passports.push(text);
+        }
+
+        Generated::new(
+            passports.join("\n\n") + "\n",
+            Some(complete.into()),
+            Some(valid.into()),
+        )
+    }
+}
@@ -0,0 +1,22 @@
+use common::check::{self, Checker, Problem};
+
+/// Boarding passes: seven of `F` and `B` followed by three of `L` and `R`.
+pub struct Day05Checker;
+
+impl Checker for Day05Checker {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            for _ in 0..7 {
+                s.one_of(&["F", "B"])?;
+            }
+            for _ in 0..3 {
+                s.one_of(&["L", "R"])?;
+            }
+            Ok(())
+        })
+    }
+}
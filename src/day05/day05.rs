@@ -1,34 +1,70 @@
-fn str_to_number(s: &str, c0: char, c1: char) -> u8 {
-    let binary_str: String = s
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+
+fn str_to_number(s: &str, c0: char, c1: char) -> Result<u8, String> {
+    let binary_str = s
         .chars()
         .map(|x| match x {
-            c if c == c0 => '0',
-            c if c == c1 => '1',
-            _ => panic!("invalid char encountered"),
+            c if c == c0 => Ok('0'),
+            c if c == c1 => Ok('1'),
+            c => Err(format!(
+                "invalid char {:?}, expected {:?} or {:?}",
+                c, c0, c1
+            )),
         })
-        .collect();
+        .collect::<Result<String, String>>()?;
 
-    return u8::from_str_radix(&binary_str, 2).unwrap();
+    Ok(u8::from_str_radix(&binary_str, 2).unwrap())
 }
 
-fn get_seat_id(s: &str) -> i32 {
-    let row = str_to_number(&s[..7], 'F', 'B');
-    let col = str_to_number(&s[7..], 'L', 'R');
+pub fn get_seat_id(s: &str) -> Result<i32, String> {
+    if s.len() != 10 || !s.is_ascii() {
+        return Err(String::from("seat must consist of exactly 10 characters"));
+    }
 
-    return (row as i32) * 8 + (col as i32);
-}
+    let row = str_to_number(&s[..7], 'F', 'B')?;
+    let col = str_to_number(&s[7..], 'L', 'R')?;
 
-fn main() {
-    let mut ids = common::parse_file_linewise("src/day05/input.txt", get_seat_id);
-    ids.sort();
+    Ok((row as i32) * 8 + (col as i32))
+}
 
-    println!("Max seat ID: {}", ids[ids.len() - 1]);
-    for (i, &id) in ids[1..].iter().enumerate() {
-        if id - ids[i] == 2 {
-            println!("My seat ID: {}", id - 1);
-            break;
+pub fn find_free_seat(sorted_ids: &[i32]) -> Option<i32> {
+    for (i, &id) in sorted_ids[1..].iter().enumerate() {
+        if id - sorted_ids[i] == 2 {
+            return Some(id - 1);
         }
     }
+    None
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<i32>;
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<i32>, ParseError> {
+        let mut ids = common::try_parse_file_linewise(input, get_seat_id)?;
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn part1(&self, ids: &Vec<i32>) -> Result<Answer, Error> {
+        ids.last()
+            .map(|id| (*id).into())
+            .ok_or_else(|| Error::invalid_input("no boarding passes"))
+    }
+
+    fn part2(&self, ids: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(find_free_seat(ids)
+            .ok_or_else(|| Error::no_solution("no free seat found"))?
+            .into())
+    }
 }
 
 #[cfg(test)]
@@ -37,9 +73,11 @@ mod test {
 
     #[test]
     fn test_seat_id() {
-        assert_eq!(get_seat_id("FBFBBFFRLR"), 357);
-        assert_eq!(get_seat_id("BFFFBBFRRR"), 567);
-        assert_eq!(get_seat_id("FFFBBBFRRR"), 119);
-        assert_eq!(get_seat_id("BBFFBBFRLL"), 820);
+        assert_eq!(get_seat_id("FBFBBFFRLR"), Ok(357));
+        assert_eq!(get_seat_id("BFFFBBFRRR"), Ok(567));
+        assert_eq!(get_seat_id("FFFBBBFRRR"), Ok(119));
+        assert_eq!(get_seat_id("BBFFBBFRLL"), Ok(820));
+        assert!(get_seat_id("BBFFBBFRL").is_err());
+        assert!(get_seat_id("BBFFBBFRLX").is_err());
     }
 }
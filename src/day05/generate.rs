@@ -0,0 +1,63 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Boarding passes of a consecutive block of seats with exactly one of them missing. The size is
+/// the number of passes, between 2 and 1023.
+pub struct Day05Generator;
+
+fn boarding_pass(id: i32) -> String {
+    let row = (0..7).rev().map(|bit| {
+        if (id >> 3) & (1 << bit) != 0 {
+            'B'
+        } else {
+            'F'
+        }
+    });
+    let col = (0..3)
+        .rev()
+        .map(|bit| if id & (1 << bit) != 0 { 'R' } else { 'L' });
+    row.chain(col).collect()
+}
+
+impl Generator for Day05Generator {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn default_size(&self) -> usize {
+        800
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.clamp(2, 1023) as i32;
+        let first = rng.gen_range(0..1024 - size);
+        let last = first + size;
+        let free = rng.gen_range(first + 1..last);
+
+        let mut ids = (first..=last)
+            .filter(|&id| id != free)
+            .collect::<Vec<i32>>();
+        ids.shuffle(rng);
+
+        Generated::new(
+            generate::lines(ids.iter().map(|&id| boarding_pass(id))),
+            Some(last.into()),
+            Some(free.into()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::day05::get_seat_id;
+
+    #[test]
+    fn test_boarding_pass() {
+        assert_eq!(boarding_pass(357), "FBFBBFFRLR");
+        for id in [0, 7, 8, 567, 1023].iter() {
+            assert_eq!(get_seat_id(&boarding_pass(*id)), Ok(*id));
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use common::check::{self, Checker, Problem};
+
+/// Customs answers: a line of questions `a` to `z` per person, groups separated by blank lines.
+pub struct Day06Checker;
+
+impl Checker for Day06Checker {
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            s.word("questions a to z", |c| c.is_ascii_lowercase())
+                .map(|_| ())
+        })
+    }
+}
@@ -1,16 +1,22 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use std::collections::HashSet;
 
-fn group_count_or(answers: &str) -> i32 {
-    return answers
+/// Number of questions anyone in the group answered with yes.
+pub fn group_count_or(answers: &str) -> i32 {
+    answers
         .split("\n")
         .flat_map(|line| line.chars())
         .collect::<HashSet<char>>()
-        .len() as i32;
+        .len() as i32
 }
 
-fn group_count_and(answers: &str) -> i32 {
+/// Number of questions everyone in the group answered with yes.
+pub fn group_count_and(answers: &str) -> i32 {
     // Can be shortened by using unstable feature fold_first insteads of fold with a dummy set
-    return answers
+    answers
         .split("\n")
         .map(|s| s.chars().collect::<HashSet<char>>())
         .fold(
@@ -19,24 +25,33 @@ fn group_count_and(answers: &str) -> i32 {
                 .collect::<HashSet<char>>(),
             |a, b| a.intersection(&b).cloned().collect(),
         )
-        .len() as i32;
+        .len() as i32
 }
 
-fn total_count(groups: &Vec<String>, f: &dyn Fn(&str) -> i32) -> i32 {
-    return groups.iter().map(|s| f(s)).fold(0, |a, b| a + b);
+pub fn total_count(groups: &[String], f: &dyn Fn(&str) -> i32) -> i32 {
+    groups.iter().map(|s| f(s)).sum()
 }
 
-fn main() {
-    let groups = common::read_grouped_file("src/day06/input.txt");
-
-    println!(
-        "Total count \"anyone\":   {}",
-        total_count(&groups, &group_count_or)
-    );
-    println!(
-        "Total count \"everyone\": {}",
-        total_count(&groups, &group_count_and)
-    );
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_grouped_file(input)
+    }
+
+    fn part1(&self, groups: &Vec<String>) -> Result<Answer, Error> {
+        Ok(total_count(groups, &group_count_or).into())
+    }
+
+    fn part2(&self, groups: &Vec<String>) -> Result<Answer, Error> {
+        Ok(total_count(groups, &group_count_and).into())
+    }
 }
 
 #[cfg(test)]
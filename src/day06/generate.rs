@@ -0,0 +1,56 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::Rng;
+
+/// Customs declaration forms of groups with one to five people. Everyone answers yes to a core of
+/// questions shared by the group and to a few of their own. The size is the number of groups.
+pub struct Day06Generator;
+
+fn answers(rng: &mut StdRng, p: f64) -> u32 {
+    (0..26)
+        .filter(|_| rng.gen_bool(p))
+        .fold(0, |a, q| a | 1 << q)
+}
+
+impl Generator for Day06Generator {
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn default_size(&self) -> usize {
+        480
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut groups = Vec::with_capacity(size);
+        let mut anyone = 0;
+        let mut everyone = 0;
+
+        for _ in 0..size {
+            let core = answers(rng, 0.15);
+            let people = (0..rng.gen_range(1..=5))
+                .map(|_| core | answers(rng, 0.2) | 1 << rng.gen_range(0..26))
+                .collect::<Vec<u32>>();
+
+            anyone += people.iter().fold(0, |a, p| a | p).count_ones();
+            everyone += people.iter().fold(!0, |a, p| a & p).count_ones();
+            groups.push(
+                people
+                    .iter()
+                    .map(|p| {
+                        (0..26)
+                            .filter(|q| p & 1 << q != 0)
+                            .map(|q| (b'a' + q as u8) as char)
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            );
+        }
+
+        Generated::new(
+            groups.join("\n\n") + "\n",
+            Some(anyone.into()),
+            Some(everyone.into()),
+        )
+    }
+}
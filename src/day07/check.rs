@@ -0,0 +1,35 @@
+use common::check::{self, Checker, Problem, Scanner};
+
+/// Bag rules like `light red bags contain 1 bright white bag, 2 muted yellow bags.` or
+/// `faded blue bags contain no other bags.`
+pub struct Day07Checker;
+
+fn check_color(s: &mut Scanner) -> Result<(), Problem> {
+    s.word("an adjective", |c| c.is_ascii_lowercase())?;
+    s.literal(" ")?;
+    s.word("a color", |c| c.is_ascii_lowercase())?;
+    Ok(())
+}
+
+impl Checker for Day07Checker {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            check_color(s)?;
+            s.literal(" bags contain ")?;
+            if !s.eat("no other bags") {
+                s.list(", ", |s| {
+                    s.number::<i32>()?;
+                    s.literal(" ")?;
+                    check_color(s)?;
+                    s.literal(" ")?;
+                    s.one_of(&["bags", "bag"]).map(|_| ())
+                })?;
+            }
+            s.literal(".")
+        })
+    }
+}
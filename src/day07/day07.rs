@@ -1,3 +1,7 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -23,12 +27,12 @@ impl Node {
     }
 }
 
-struct Graph {
+pub struct Graph {
     nodes: HashMap<String, Node>,
 }
 
 impl Graph {
-    fn from_description(lines: &Vec<String>) -> Graph {
+    pub fn from_description(lines: &Vec<String>) -> Graph {
         let mut nodes = HashMap::<String, Node>::new();
 
         for line in lines {
@@ -47,7 +51,7 @@ impl Graph {
                 let tokens = contains.split(" ").collect::<Vec<&str>>();
                 let count = tokens[0].parse::<i32>().unwrap();
                 let mut contains_name = String::from(tokens[1]);
-                contains_name.push_str(" ");
+                contains_name.push(' ');
                 contains_name.push_str(tokens[2]);
 
                 if !nodes.contains_key(&contains_name) {
@@ -71,13 +75,13 @@ impl Graph {
     fn dfs_reverse(&self, n: &Node) -> HashSet<String> {
         let mut ret = HashSet::<String>::new();
 
-        for (source, _) in &n.incoming_connections {
+        for source in n.incoming_connections.keys() {
             ret.insert(source.clone());
             let transitive_sources = self.dfs_reverse(self.nodes.get(source).unwrap());
             ret = ret.union(&transitive_sources).cloned().collect();
         }
 
-        return ret;
+        ret
     }
 
     fn dfs_count(&self, n: &Node) -> i32 {
@@ -87,22 +91,39 @@ impl Graph {
             cnt += amount * self.dfs_count(self.nodes.get(target).unwrap());
         }
 
-        return cnt + 1;
+        cnt + 1
     }
 
-    fn find_all_containing_nodes(&self, name: &str) -> i32 {
-        return self.dfs_reverse(self.nodes.get(name).unwrap()).len() as i32;
+    /// Number of bag colors that eventually contain a `name` bag.
+    pub fn find_all_containing_nodes(&self, name: &str) -> i32 {
+        self.dfs_reverse(self.nodes.get(name).unwrap()).len() as i32
     }
 
-    fn count_total_bags(&self, name: &str) -> i32 {
+    /// Number of bags inside a single `name` bag.
+    pub fn count_total_bags(&self, name: &str) -> i32 {
         // Subtract the bag itself
-        return self.dfs_count(self.nodes.get(name).unwrap()) - 1;
+        self.dfs_count(self.nodes.get(name).unwrap()) - 1
     }
 }
 
-fn main() {
-    let g = Graph::from_description(&common::read_file_linewise("src/day07/input.txt"));
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Graph;
 
-    println!("Solution 1: {}", g.find_all_containing_nodes("shiny gold"));
-    println!("Solution 2: {}", g.count_total_bags("shiny gold"));
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Graph, ParseError> {
+        common::try_read_file_linewise(input).map(|lines| Graph::from_description(&lines))
+    }
+
+    fn part1(&self, g: &Graph) -> Result<Answer, Error> {
+        Ok(g.find_all_containing_nodes("shiny gold").into())
+    }
+
+    fn part2(&self, g: &Graph) -> Result<Answer, Error> {
+        Ok(g.count_total_bags("shiny gold").into())
+    }
 }
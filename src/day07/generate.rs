@@ -0,0 +1,97 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+const TARGET: &str = "shiny gold";
+
+/// Limit of the bags inside a single bag that still may be packed into another one, which keeps
+/// the total of part 2 within an `i32`.
+const MAX_CONTENT: i64 = 10_000;
+
+/// Acyclic bag rules in random order. A bag only contains bags of colors listed after it in a
+/// hidden ordering, with the shiny gold bag in its first tenth so that few colors contain it.
+/// The size is the number of colors, at least 2.
+pub struct Day07Generator;
+
+fn color_names(rng: &mut StdRng, n: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    seen.insert(TARGET.to_string());
+    let mut names = Vec::with_capacity(n);
+    while names.len() < n {
+        let name = format!("{} {}", generate::word(rng, 5), generate::word(rng, 4));
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+impl Generator for Day07Generator {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn default_size(&self) -> usize {
+        600
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let n = size.max(2);
+        let mut names = color_names(rng, n);
+        let target = rng.gen_range(0..(n / 10).max(1));
+        names[target] = TARGET.to_string();
+
+        // Filled back to front so the content of every bag is known when choosing its parents
+        let mut contents: Vec<Vec<(usize, i64)>> = vec![Vec::new(); n];
+        let mut totals = vec![0i64; n];
+        for i in (0..n - 1).rev() {
+            let candidates = (i + 1..n)
+                .filter(|&j| totals[j] <= MAX_CONTENT)
+                .collect::<Vec<usize>>();
+            let kinds = rng.gen_range(0..=4).min(candidates.len());
+            for &j in candidates.choose_multiple(rng, kinds) {
+                let count = rng.gen_range(1..=5);
+                contents[i].push((j, count));
+                totals[i] += count * (1 + totals[j]);
+            }
+        }
+
+        let mut containers = HashSet::new();
+        let mut pending = vec![target];
+        while let Some(bag) = pending.pop() {
+            for (i, content) in contents.iter().enumerate() {
+                if content.iter().any(|&(j, _)| j == bag) && containers.insert(i) {
+                    pending.push(i);
+                }
+            }
+        }
+
+        let mut rules = contents
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                let listed = if content.is_empty() {
+                    String::from("no other bags")
+                } else {
+                    content
+                        .iter()
+                        .map(|&(j, count)| {
+                            let plural = if count == 1 { "" } else { "s" };
+                            format!("{} {} bag{}", count, names[j], plural)
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                };
+                format!("{} bags contain {}.", names[i], listed)
+            })
+            .collect::<Vec<String>>();
+        rules.shuffle(rng);
+
+        Generated::new(
+            generate::lines(rules),
+            Some(containers.len().into()),
+            Some(totals[target].into()),
+        )
+    }
+}
@@ -0,0 +1,18 @@
+use common::check::{self, Checker, Problem};
+
+/// Boot code: an operation `nop`, `acc` or `jmp` and a signed argument per line.
+pub struct Day08Checker;
+
+impl Checker for Day08Checker {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            s.one_of(&["nop", "acc", "jmp"])?;
+            s.literal(" ")?;
+            s.signed::<i32>().map(|_| ())
+        })
+    }
+}
@@ -1,5 +1,10 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use std::collections::HashSet;
 
+#[derive(Clone, Copy, Debug)]
 enum InstructionType {
     NoOperation,
     Accumulate,
@@ -26,7 +31,7 @@ impl State {
     }
 }
 
-struct Program {
+pub struct Program {
     state: State,
     code: Vec<Box<dyn Instruction>>,
 }
@@ -86,30 +91,48 @@ impl Instruction for Jump {
     }
 }
 
+fn make_instruction(instruction_type: InstructionType, argument: i32) -> Box<dyn Instruction> {
+    match instruction_type {
+        InstructionType::NoOperation => Box::new(NoOperation { argument }),
+        InstructionType::Accumulate => Box::new(Accumulate { argument }),
+        InstructionType::Jump => Box::new(Jump { argument }),
+    }
+}
+
+fn parse_instruction(line: &str) -> Result<Box<dyn Instruction>, String> {
+    let tokens: Vec<&str> = line.split(' ').collect();
+    if tokens.len() != 2 {
+        return Err(String::from("expected \"<operation> <argument>\""));
+    }
+
+    let argument = tokens[1]
+        .parse::<i32>()
+        .map_err(|e| format!("invalid argument {:?}: {}", tokens[1], e))?;
+
+    match tokens[0] {
+        "nop" => Ok(make_instruction(InstructionType::NoOperation, argument)),
+        "acc" => Ok(make_instruction(InstructionType::Accumulate, argument)),
+        "jmp" => Ok(make_instruction(InstructionType::Jump, argument)),
+        op => Err(format!("unknown instruction {:?}", op)),
+    }
+}
+
 impl Program {
-    fn from_vec(lines: &Vec<String>) -> Program {
-        Program {
+    pub fn from_file(source: impl Into<InputSource>) -> Result<Program, ParseError> {
+        Ok(Program {
             state: State::new(),
-            code: lines
-                .iter()
-                .map(|line| {
-                    let tokens: Vec<&str> = line.split(" ").collect();
-                    let argument = tokens[1].parse::<i32>().unwrap();
-
-                    match tokens[0] {
-                        s if s == "nop" => {
-                            Box::new(NoOperation { argument }) as Box<dyn Instruction>
-                        }
-                        s if s == "acc" => Box::new(Accumulate { argument }),
-                        s if s == "jmp" => Box::new(Jump { argument }),
-                        _ => panic!("Encountered unknown instruction"),
-                    }
-                })
-                .collect(),
-        }
+            code: common::try_parse_file_linewise(source, parse_instruction)?,
+        })
+    }
+
+    /// Value of the accumulator after the last run.
+    pub fn accumulator(&self) -> i32 {
+        self.state.accumulator
     }
 
-    fn try_run(&mut self) -> bool {
+    /// Runs the program until it terminates or an instruction is about to be executed a second
+    /// time. Returns whether it terminated.
+    pub fn try_run(&mut self) -> bool {
         let mut executed_instructions = HashSet::<i32>::new();
         self.state = State::new();
 
@@ -122,50 +145,84 @@ impl Program {
             instruction.apply(&mut self.state);
         }
 
-        return self.state.instruction_pointer == self.code.len() as i32;
+        self.state.instruction_pointer == self.code.len() as i32
     }
 
-    fn try_fix_code(&mut self) -> bool {
-        for fix_idx in 0..self.code.len() - 1 {
+    /// Swaps a single `nop` or `jmp` so that the program terminates and leaves it run.
+    pub fn try_fix_code(&mut self) -> Result<(), Error> {
+        for fix_idx in 0..self.code.len() {
             match self.code[fix_idx].instruction_type() {
                 InstructionType::NoOperation | InstructionType::Jump => {
-                    self.swap_nop_for_jmp(fix_idx);
+                    self.swap_nop_for_jmp(fix_idx)?;
                 }
                 _ => continue,
             }
 
             if self.try_run() {
-                return true;
+                return Ok(());
             }
 
-            self.swap_nop_for_jmp(fix_idx);
+            self.swap_nop_for_jmp(fix_idx)?;
         }
 
-        return false;
+        Err(Error::no_solution(
+            "no single nop/jmp swap makes the program terminate",
+        ))
     }
 
-    fn swap_nop_for_jmp(&mut self, i: usize) {
+    fn swap_nop_for_jmp(&mut self, i: usize) -> Result<(), Error> {
         let arg = self.code[i].argument();
 
         self.code[i] = match self.code[i].instruction_type() {
-            InstructionType::Jump => {
-                Box::new(NoOperation { argument: arg }) as Box<dyn Instruction>
+            InstructionType::Jump => make_instruction(InstructionType::NoOperation, arg),
+            InstructionType::NoOperation => make_instruction(InstructionType::Jump, arg),
+            t => {
+                return Err(Error::invalid_input(format!(
+                    "instruction {} of type {:?} can't be swapped",
+                    i, t
+                )))
             }
-            InstructionType::NoOperation => Box::new(Jump { argument: arg }),
-            _ => panic!("Encountered unswitchable type"),
+        };
+        Ok(())
+    }
+}
+
+impl Clone for Program {
+    fn clone(&self) -> Program {
+        Program {
+            state: State::new(),
+            code: self
+                .code
+                .iter()
+                .map(|i| make_instruction(i.instruction_type(), i.argument()))
+                .collect(),
         }
     }
 }
 
-fn main() {
-    let mut code = Program::from_vec(&common::read_file_linewise("src/day08/input.txt"));
-    code.try_run();
-    println!("Accumulator before first loop: {}", code.state.accumulator);
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Program;
+
+    fn day(&self) -> u32 {
+        8
+    }
 
-    if code.try_fix_code() {
-        println!("Fixed. Final accumulator: {}", code.state.accumulator);
-    } else {
-        println!("Could not fix code");
+    fn parse(&self, input: &InputSource) -> Result<Program, ParseError> {
+        Program::from_file(input)
+    }
+
+    fn part1(&self, code: &Program) -> Result<Answer, Error> {
+        let mut code = code.clone();
+        code.try_run();
+        Ok(code.accumulator().into())
+    }
+
+    fn part2(&self, code: &Program) -> Result<Answer, Error> {
+        let mut code = code.clone();
+        code.try_fix_code()?;
+        Ok(code.accumulator().into())
     }
 }
 
@@ -175,15 +232,23 @@ mod test {
 
     #[test]
     fn test_acc_before_first_loop() {
-        let mut code = Program::from_vec(&common::read_file_linewise("src/day08/input_test.txt"));
+        let mut code = Program::from_file("src/day08/input_test.txt").unwrap();
         code.try_run();
         assert_eq!(code.state.accumulator, 5);
     }
 
     #[test]
     fn test_fix_code() {
-        let mut code = Program::from_vec(&common::read_file_linewise("src/day08/input_test.txt"));
-        assert_eq!(code.try_fix_code(), true);
+        let mut code = Program::from_file("src/day08/input_test.txt").unwrap();
+        assert_eq!(code.try_fix_code(), Ok(()));
         assert_eq!(code.state.accumulator, 8);
     }
+
+    #[test]
+    fn test_parse_instruction() {
+        assert_eq!(parse_instruction("jmp -4").unwrap().argument(), -4);
+        assert!(parse_instruction("jmp").is_err());
+        assert!(parse_instruction("jmp x").is_err());
+        assert!(parse_instruction("mul +2").is_err());
+    }
 }
@@ -0,0 +1,148 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Nop,
+    Acc,
+    Jmp,
+}
+
+type Code = Vec<(Op, i32)>;
+
+/// Boot code with exactly one corrupted instruction: a `jmp` back that makes it loop forever.
+///
+/// Up to that jump the code only ever jumps forward over traps, instructions that can't be
+/// reached without looping. Every `nop` would jump back to code that was already executed, so
+/// swapping any other instruction still ends up at the corrupted jump, or loops before it. After
+/// it, the code runs straight to its end. The size is the number of instructions, at least 4.
+pub struct Day08Generator;
+
+/// Appends a block of instructions to `code`: an `acc`, a `nop` that would jump back or a jump
+/// forward over one to three traps.
+fn push_block(rng: &mut StdRng, code: &mut Code, executed: &mut Vec<usize>) {
+    let pos = code.len();
+    executed.push(pos);
+    match rng.gen_range(0..5) {
+        0 | 1 => code.push((Op::Acc, rng.gen_range(-50..=50))),
+        2 => code.push((Op::Nop, -(rng.gen_range(0..=pos) as i32))),
+        _ => {
+            let traps = rng.gen_range(1..=3);
+            code.push((Op::Jmp, traps + 1));
+            for i in 1..=traps {
+                let back = rng.gen_range(0..=pos + i as usize) as i32;
+                code.push((Op::Jmp, -back));
+            }
+        }
+    }
+}
+
+/// Runs the code until it terminates or repeats an instruction, returning whether it terminated
+/// and the accumulator at that point.
+fn run(code: &Code) -> (bool, i32) {
+    let mut executed = HashSet::new();
+    let mut acc = 0;
+    let mut ip = 0i32;
+    while (ip as usize) < code.len() && executed.insert(ip) {
+        let (op, arg) = code[ip as usize];
+        match op {
+            Op::Nop => ip += 1,
+            Op::Acc => {
+                acc += arg;
+                ip += 1;
+            }
+            Op::Jmp => ip += arg,
+        }
+    }
+    (ip as usize == code.len(), acc)
+}
+
+impl Generator for Day08Generator {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn default_size(&self) -> usize {
+        600
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.max(4);
+        let mut code = Code::with_capacity(size + 3);
+        let mut executed = Vec::new();
+
+        let corrupted = rng.gen_range(size / 2..size - 1);
+        while code.len() < corrupted {
+            push_block(rng, &mut code, &mut executed);
+        }
+        let corrupted = code.len();
+        let target = *executed.choose(rng).unwrap_or(&corrupted);
+        code.push((Op::Jmp, target as i32 - corrupted as i32));
+        while code.len() < size {
+            push_block(rng, &mut code, &mut Vec::new());
+        }
+
+        let (_, looped) = run(&code);
+        let mut fixed = code.clone();
+        fixed[corrupted].0 = Op::Nop;
+        let (terminated, acc) = run(&fixed);
+        debug_assert!(terminated);
+
+        Generated::new(
+            generate::lines(code.iter().map(|(op, arg)| {
+                let name = match op {
+                    Op::Nop => "nop",
+                    Op::Acc => "acc",
+                    Op::Jmp => "jmp",
+                };
+                format!("{} {:+}", name, arg)
+            })),
+            Some(looped.into()),
+            Some(acc.into()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn swapped(code: &Code, i: usize) -> Option<Code> {
+        let mut code = code.clone();
+        code[i].0 = match code[i].0 {
+            Op::Nop => Op::Jmp,
+            Op::Jmp => Op::Nop,
+            Op::Acc => return None,
+        };
+        Some(code)
+    }
+
+    #[test]
+    fn test_generate_has_a_single_fix() {
+        for seed in 0..20 {
+            let generated = Day08Generator.generate(&mut generate::rng(seed), 50);
+            let code = generated
+                .input
+                .lines()
+                .map(|line| {
+                    let (op, arg) = line.split_at(3);
+                    let op = match op {
+                        "nop" => Op::Nop,
+                        "acc" => Op::Acc,
+                        _ => Op::Jmp,
+                    };
+                    (op, arg.trim().parse::<i32>().unwrap())
+                })
+                .collect::<Code>();
+
+            assert!(!run(&code).0);
+            let fixes = (0..code.len())
+                .filter_map(|i| swapped(&code, i))
+                .filter(|c| run(c).0)
+                .count();
+            assert_eq!(fixes, 1);
+        }
+    }
+}
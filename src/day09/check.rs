@@ -0,0 +1,14 @@
+use common::check::{self, Checker, Problem};
+
+/// XMAS data: one number per line.
+pub struct Day09Checker;
+
+impl Checker for Day09Checker {
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| s.number::<u128>().map(|_| ()))
+    }
+}
@@ -1,4 +1,10 @@
-fn is_valid(preamble: &[u128], x: u128) -> bool {
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use std::collections::VecDeque;
+
+pub fn is_valid(preamble: &[u128], x: u128) -> bool {
     for i in 0..preamble.len() {
         for j in i + 1..preamble.len() {
             if preamble[i] + preamble[j] == x {
@@ -6,20 +12,44 @@ fn is_valid(preamble: &[u128], x: u128) -> bool {
             }
         }
     }
-    return false;
+    false
 }
 
-fn check_sequence(numbers: &Vec<u128>, preamble_length: usize) -> (bool, u128) {
-    for i in 0..numbers.len() - preamble_length {
-        let x = numbers[i + preamble_length];
-        if !is_valid(&numbers[i..i + preamble_length], x) {
-            return (false, x);
+/// Only keeps the current preamble in memory, so `numbers` may be streamed straight from the input.
+pub fn first_invalid_number<I>(numbers: I, preamble_length: usize) -> Result<u128, Error>
+where
+    I: IntoIterator<Item = u128>,
+{
+    let mut preamble = VecDeque::<u128>::with_capacity(preamble_length + 1);
+    for x in numbers {
+        if preamble.len() == preamble_length {
+            if !is_valid(preamble.make_contiguous(), x) {
+                return Ok(x);
+            }
+            preamble.pop_front();
         }
+        preamble.push_back(x);
     }
-    return (true, 0);
+    Err(Error::no_solution(
+        "every number is the sum of two of its predecessors",
+    ))
 }
 
-fn find_encryption(numbers: &Vec<u128>, x: u128) -> u128 {
+/// First invalid number of the list in `source`, read line by line so that only the preamble is
+/// kept in memory. For inputs too large to be solved by [`Day09`].
+pub fn first_invalid_in(source: &InputSource, preamble_length: usize) -> Result<u128, Error> {
+    let mut error = None;
+    let numbers = common::reader::lines(source)?
+        .parse(source, |line| line.parse::<u128>())
+        .map_while(|n| n.map_err(|e| error = Some(e)).ok());
+    let ret = first_invalid_number(numbers, preamble_length);
+    match error {
+        Some(e) => Err(e.into()),
+        None => ret,
+    }
+}
+
+pub fn find_encryption(numbers: &[u128], x: u128) -> Result<u128, Error> {
     for i in 0..numbers.len() {
         let mut y = 0;
         for j in i..numbers.len() {
@@ -28,18 +58,39 @@ fn find_encryption(numbers: &Vec<u128>, x: u128) -> u128 {
                 break;
             } else if y == x {
                 let slice = &numbers[i..j + 1];
-                return slice.iter().min().unwrap() + slice.iter().max().unwrap();
+                return Ok(slice.iter().min().unwrap() + slice.iter().max().unwrap());
             }
         }
     }
-    panic!("Could not break encryption");
+    Err(Error::no_solution(format!(
+        "no contiguous range sums to {}",
+        x
+    )))
 }
 
-fn main() {
-    let numbers = common::parse_file_linewise_as::<u128>("src/day09/input.txt");
-    let (_, x) = check_sequence(&numbers, 25);
-    println!("Fist unbuildable number: {}", x);
-    println!("Encryption weakness: {}", find_encryption(&numbers, x));
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<u128>;
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    /// Both parts share the parsed input and part 2 looks for ranges anywhere before the invalid
+    /// number, so the whole list is kept in memory. [`first_invalid_in`] streams part 1 instead.
+    fn parse(&self, input: &InputSource) -> Result<Vec<u128>, ParseError> {
+        common::try_parse_file_linewise_as::<u128>(input)
+    }
+
+    fn part1(&self, numbers: &Vec<u128>) -> Result<Answer, Error> {
+        Ok(first_invalid_number(numbers.iter().copied(), 25)?.into())
+    }
+
+    fn part2(&self, numbers: &Vec<u128>) -> Result<Answer, Error> {
+        let x = first_invalid_number(numbers.iter().copied(), 25)?;
+        Ok(find_encryption(numbers, x)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -49,23 +100,33 @@ mod test {
     #[test]
     fn test_is_valid() {
         let preamble: Vec<u128> = (1u128..26u128).collect();
-        assert_eq!(is_valid(&preamble, 26), true);
-        assert_eq!(is_valid(&preamble, 49), true);
-        assert_eq!(is_valid(&preamble, 100), false);
-        assert_eq!(is_valid(&preamble, 50), false);
+        assert!(is_valid(&preamble, 26));
+        assert!(is_valid(&preamble, 49));
+        assert!(!is_valid(&preamble, 100));
+        assert!(!is_valid(&preamble, 50));
     }
 
     #[test]
-    fn test_check_sequence() {
+    fn test_first_invalid_number() {
         let numbers = common::parse_file_linewise_as::<u128>("src/day09/input_test.txt");
-        let (valid, x) = check_sequence(&numbers, 5);
-        assert_eq!(valid, false);
-        assert_eq!(x, 127);
+        assert_eq!(first_invalid_number(numbers, 5), Ok(127));
+        assert!(first_invalid_number(vec![1, 2, 3], 2).is_err());
+    }
+
+    #[test]
+    fn test_first_invalid_in() {
+        let source = InputSource::from("src/day09/input_test.txt");
+        assert_eq!(first_invalid_in(&source, 5), Ok(127));
+        assert!(matches!(
+            first_invalid_in(&InputSource::from("src/day09/missing.txt"), 5),
+            Err(Error::Parse(_))
+        ));
     }
 
     #[test]
     fn test_find_encryption() {
         let numbers = common::parse_file_linewise_as::<u128>("src/day09/input_test.txt");
-        assert_eq!(find_encryption(&numbers, 127), 62);
+        assert_eq!(find_encryption(&numbers, 127), Ok(62));
+        assert!(find_encryption(&numbers, 1).is_err());
     }
 }
@@ -0,0 +1,78 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const PREAMBLE: usize = 25;
+
+/// XMAS data where every number after the preamble is the sum of two of the 25 numbers before it,
+/// except for the last one. That one is the sum of a contiguous range early on and no sum of two
+/// of its predecessors. Numbers are only built from the smallest of their predecessors so that
+/// they grow slowly. The size is the number of numbers, at least 30.
+pub struct Day09Generator;
+
+/// Minimum plus maximum of the first contiguous range of at least two numbers summing to `x`,
+/// which does not occur itself.
+fn weakness(numbers: &[u128], x: u128) -> Option<u128> {
+    (0..numbers.len()).find_map(|start| {
+        let mut sum = 0;
+        for end in start..numbers.len() {
+            sum += numbers[end];
+            if sum > x {
+                return None;
+            }
+            if sum == x && end > start {
+                let range = &numbers[start..=end];
+                return Some(range.iter().min().unwrap() + range.iter().max().unwrap());
+            }
+        }
+        None
+    })
+}
+
+fn is_pair_sum(window: &[u128], x: u128) -> bool {
+    window
+        .iter()
+        .enumerate()
+        .any(|(i, a)| window[i + 1..].iter().any(|b| a + b == x))
+}
+
+impl Generator for Day09Generator {
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn default_size(&self) -> usize {
+        1000
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.max(PREAMBLE + 5);
+        let mut numbers = (1..=2 * PREAMBLE as u128).collect::<Vec<u128>>();
+        numbers.shuffle(rng);
+        numbers.truncate(PREAMBLE);
+
+        while numbers.len() < size - 1 {
+            let mut window = numbers[numbers.len() - PREAMBLE..].to_vec();
+            window.sort_unstable();
+            let picked = window[..6].choose_multiple(rng, 2).collect::<Vec<&u128>>();
+            numbers.push(picked[0] + picked[1]);
+        }
+
+        let window = &numbers[numbers.len() - PREAMBLE..];
+        let (invalid, weakness) = loop {
+            let start = rng.gen_range(0..numbers.len() / 2);
+            let len = rng.gen_range(2..=10);
+            let invalid = numbers[start..start + len].iter().sum::<u128>();
+            if !is_pair_sum(window, invalid) && !numbers.contains(&invalid) {
+                break (invalid, weakness(&numbers, invalid).unwrap());
+            }
+        };
+        numbers.push(invalid);
+
+        Generated::new(
+            generate::lines(numbers.iter().map(|n| n.to_string())),
+            Some(invalid.into()),
+            Some(weakness.into()),
+        )
+    }
+}
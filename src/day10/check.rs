@@ -0,0 +1,14 @@
+use common::check::{self, Checker, Problem};
+
+/// Joltage adapters: one number per line.
+pub struct Day10Checker;
+
+impl Checker for Day10Checker {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| s.number::<i32>().map(|_| ()))
+    }
+}
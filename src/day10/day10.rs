@@ -1,29 +1,41 @@
-fn count_differences(mut jolts: Vec<i32>) -> (i32, i32, i32) {
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+
+/// Counts the 1, 2 and 3 jolt differences in the full adapter chain.
+pub fn count_differences(mut jolts: Vec<i32>) -> Result<(i32, i32, i32), Error> {
     jolts.push(0);
     jolts.sort();
 
     let mut ret = (0, 0, 0);
 
-    jolts[..jolts.len() - 1]
-        .iter()
-        .zip(jolts[1..].iter())
-        .for_each(|(x, y)| match y - x {
-            c if c == 1 => ret.0 += 1,
-            c if c == 2 => ret.1 += 1,
-            c if c == 3 => ret.2 += 1,
-            _ => panic!("Invalid diff encountered"),
-        });
-
-    return ret;
+    for (x, y) in jolts[..jolts.len() - 1].iter().zip(jolts[1..].iter()) {
+        match y - x {
+            1 => ret.0 += 1,
+            2 => ret.1 += 1,
+            3 => ret.2 += 1,
+            d => {
+                return Err(Error::no_solution(format!(
+                    "adapters {} and {} differ by {}",
+                    x, y, d
+                )))
+            }
+        }
+    }
+
+    Ok(ret)
 }
 
-fn solve_01(filename: &str) -> i32 {
-    let cnts = count_differences(common::parse_file_linewise_as::<i32>(filename));
-    return cnts.0 * (cnts.2 + 1);
+/// Number of 1 jolt differences multiplied by the number of 3 jolt differences.
+pub fn solve_01(jolts: &[i32]) -> Result<i32, Error> {
+    let cnts = count_differences(jolts.to_vec())?;
+    Ok(cnts.0 * (cnts.2 + 1))
 }
 
-fn solve_02(filename: &str) -> u128 {
-    let mut jolts = common::parse_file_linewise_as::<i32>(filename);
+/// Number of distinct adapter arrangements.
+pub fn solve_02(jolts: &[i32]) -> u128 {
+    let mut jolts = jolts.to_vec();
     jolts.push(0);
     jolts.sort();
     jolts.push(jolts[jolts.len() - 1] + 3);
@@ -37,12 +49,29 @@ fn solve_02(filename: &str) -> u128 {
             }
         }
     }
-    return accumulator[jolts.len() - 1];
+    accumulator[jolts.len() - 1]
 }
 
-fn main() {
-    println!("Solution 1: {}", solve_01("src/day10/input.txt"));
-    println!("Solution 2: {}", solve_02("src/day10/input.txt"));
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<i32>;
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<i32>, ParseError> {
+        common::try_parse_file_linewise_as::<i32>(input)
+    }
+
+    fn part1(&self, jolts: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(solve_01(jolts)?.into())
+    }
+
+    fn part2(&self, jolts: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(solve_02(jolts).into())
+    }
 }
 
 #[cfg(test)]
@@ -53,7 +82,8 @@ mod test {
     fn test_difference_count() {
         let cnts = count_differences(common::parse_file_linewise_as::<i32>(
             "src/day10/input_test_01.txt",
-        ));
+        ))
+        .unwrap();
         assert_eq!(cnts.0, 7);
         assert_eq!(cnts.1, 0);
         assert_eq!(cnts.2, 4);
@@ -61,13 +91,18 @@ mod test {
 
     #[test]
     fn test_solve_01() {
-        assert_eq!(solve_01("src/day10/input_test_01.txt"), 35);
-        assert_eq!(solve_01("src/day10/input_test_02.txt"), 220);
+        let jolts_01 = common::parse_file_linewise_as::<i32>("src/day10/input_test_01.txt");
+        let jolts_02 = common::parse_file_linewise_as::<i32>("src/day10/input_test_02.txt");
+        assert_eq!(solve_01(&jolts_01), Ok(35));
+        assert_eq!(solve_01(&jolts_02), Ok(220));
+        assert!(solve_01(&[1, 5]).is_err());
     }
 
     #[test]
     fn test_solve_02() {
-        assert_eq!(solve_02("src/day10/input_test_01.txt"), 8);
-        assert_eq!(solve_02("src/day10/input_test_02.txt"), 19208);
+        let jolts_01 = common::parse_file_linewise_as::<i32>("src/day10/input_test_01.txt");
+        let jolts_02 = common::parse_file_linewise_as::<i32>("src/day10/input_test_02.txt");
+        assert_eq!(solve_02(&jolts_01), 8);
+        assert_eq!(solve_02(&jolts_02), 19208);
     }
 }
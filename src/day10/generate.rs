@@ -0,0 +1,54 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Number of ways to cross a run of consecutive 1 jolt differences, indexed by its length.
+const RUN_ARRANGEMENTS: [u128; 5] = [1, 1, 2, 4, 7];
+
+/// Adapter bags with differences of 1 and 3 jolts and at most four 1 jolt steps in a row, like
+/// the real inputs. Adapters around a 3 jolt step can't be left out, so every run of 1 jolt
+/// steps contributes a fixed factor to the number of arrangements. The size is the number of
+/// adapters, between 1 and 200 to keep that number within a `u128`.
+pub struct Day10Generator;
+
+impl Generator for Day10Generator {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn default_size(&self) -> usize {
+        100
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.clamp(1, 200);
+        let mut jolts = Vec::with_capacity(size);
+        let mut ones = 0;
+        let mut threes = 1; // The device is always 3 jolts above the last adapter
+        let mut arrangements = 1;
+        let mut run = 0;
+
+        let mut jolt = 0;
+        for _ in 0..size {
+            if run < 4 && rng.gen_bool(0.6) {
+                jolt += 1;
+                ones += 1;
+                run += 1;
+            } else {
+                jolt += 3;
+                threes += 1;
+                arrangements *= RUN_ARRANGEMENTS[run];
+                run = 0;
+            }
+            jolts.push(jolt);
+        }
+        arrangements *= RUN_ARRANGEMENTS[run];
+        jolts.shuffle(rng);
+
+        Generated::new(
+            generate::lines(jolts.iter().map(|j| j.to_string())),
+            Some((ones * threes).into()),
+            Some(arrangements.into()),
+        )
+    }
+}
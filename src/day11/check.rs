@@ -0,0 +1,18 @@
+use common::check::{self, Checker, Problem};
+
+/// Seat layouts of floor `.`, empty seats `L` and occupied seats `#`.
+pub struct Day11Checker;
+
+impl Checker for Day11Checker {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+        check::check_grid(&lines, ".L#")
+    }
+}
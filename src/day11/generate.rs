@@ -0,0 +1,27 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+
+/// Square waiting areas of empty seats with about one floor tile in ten. Where the seating
+/// settles can only be found by simulating it, so no answers are known. The size is the side
+/// length.
+pub struct Day11Generator;
+
+impl Generator for Day11Generator {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn default_size(&self) -> usize {
+        90
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.max(1);
+        let rows = (0..size).map(|_| {
+            (0..size)
+                .map(|_| if rng.gen_bool(0.1) { '.' } else { 'L' })
+                .collect::<String>()
+        });
+        Generated::new(generate::lines(rows.collect::<Vec<String>>()), None, None)
+    }
+}
@@ -0,0 +1,27 @@
+use common::check::{self, Checker, Problem};
+
+/// Navigation instructions: an action `N`, `S`, `E`, `W`, `L`, `R` or `F` followed by a value.
+/// The waypoint can only be rotated by 90, 180 or 270 degrees.
+pub struct Day12Checker;
+
+impl Checker for Day12Checker {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            let action = s.one_of(&["N", "S", "E", "W", "L", "R", "F"])?;
+            let column = s.column();
+            let value = s.number::<i32>()?;
+            if (action == "L" || action == "R") && ![90, 180, 270].contains(&value) {
+                return Err(s.problem_at(
+                    column,
+                    format!("rotation by {} degrees is not supported", value),
+                    "turn by 90, 180 or 270 degrees",
+                ));
+            }
+            Ok(())
+        })
+    }
+}
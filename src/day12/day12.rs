@@ -1,12 +1,12 @@
-use std::fmt;
+pub mod check;
+pub mod generate;
 
-extern crate image;
-extern crate imageproc;
-extern crate nalgebra as na;
+use std::fmt;
 
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use image::{GrayImage, Luma};
 use imageproc::drawing;
-use na::{Point2, Vector2};
+use nalgebra::{Point2, Vector2};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Direction {
@@ -33,15 +33,15 @@ impl Direction {
 
     fn from_char(c: char) -> Option<Direction> {
         match c {
-            x if x == 'N' => Some(Direction::North),
-            x if x == 'E' => Some(Direction::East),
-            x if x == 'S' => Some(Direction::South),
-            x if x == 'W' => Some(Direction::West),
+            'N' => Some(Direction::North),
+            'E' => Some(Direction::East),
+            'S' => Some(Direction::South),
+            'W' => Some(Direction::West),
             _ => None,
         }
     }
 
-    fn to_vec(&self, value: i32) -> Vector2<i32> {
+    fn to_vec(self, value: i32) -> Vector2<i32> {
         match self {
             Direction::North => Vector2::new(0, value),
             Direction::East => Vector2::new(value, 0),
@@ -52,7 +52,7 @@ impl Direction {
 
     fn rotate_impl(arr: &[Direction], d: Direction, amount: i32) -> Direction {
         let start = arr.iter().position(|x| *x == d).unwrap() as i32;
-        let pos = (start + (amount / 90)) % 4;
+        let pos = (start + (amount / 90)).rem_euclid(4);
         arr[pos as usize]
     }
 
@@ -75,14 +75,33 @@ enum Turn {
 impl Turn {
     fn from_char(c: char) -> Option<Turn> {
         match c {
-            x if x == 'L' => Some(Turn::Left),
-            x if x == 'R' => Some(Turn::Right),
-            x if x == 'F' => Some(Turn::Forward),
+            'L' => Some(Turn::Left),
+            'R' => Some(Turn::Right),
+            'F' => Some(Turn::Forward),
             _ => None,
         }
     }
 }
 
+fn parse_action(s: &str) -> Result<(char, i32), Error> {
+    let instruction = s
+        .chars()
+        .next()
+        .ok_or_else(|| Error::invalid_input("empty action"))?;
+    let value = s[instruction.len_utf8()..]
+        .parse::<i32>()
+        .map_err(|e| Error::invalid_input(format!("invalid value in action {:?}: {}", s, e)))?;
+    Ok((instruction, value))
+}
+
+fn invalid_action_code(c: char) -> Error {
+    Error::invalid_input(format!("invalid action code {:?}", c))
+}
+
+fn invalid_rotation(v: i32) -> Error {
+    Error::invalid_input(format!("invalid rotation by {} degrees", v))
+}
+
 struct Ship {
     orientation: Direction,
     path: Vec<Point2<i32>>,
@@ -102,7 +121,8 @@ impl Ship {
         self.waypoint += d.to_vec(v);
     }
 
-    fn rotate_waypoint_by(&mut self, t: Turn, mut v: i32) {
+    fn rotate_waypoint_by(&mut self, t: Turn, value: i32) -> Result<(), Error> {
+        let mut v = value;
         if t == Turn::Right {
             v *= -1;
         }
@@ -116,12 +136,13 @@ impl Ship {
         let p = &self.waypoint;
 
         match v {
-            x if x == 0 => (),
-            x if x == 90 => self.waypoint = Point2::new(-p[1], p[0]),
-            x if x == 180 => self.waypoint = Point2::new(-p[0], -p[1]),
-            x if x == 270 => self.waypoint = Point2::new(p[1], -p[0]),
-            _ => panic!("Invalid rotation"),
+            0 => (),
+            90 => self.waypoint = Point2::new(-p[1], p[0]),
+            180 => self.waypoint = Point2::new(-p[0], -p[1]),
+            270 => self.waypoint = Point2::new(p[1], -p[0]),
+            _ => return Err(invalid_rotation(value)),
         }
+        Ok(())
     }
 
     fn move_to_waypoint(&mut self, v: i32) {
@@ -135,50 +156,48 @@ impl Ship {
         self.path.push(new_pos);
     }
 
-    fn rotate_by(&mut self, t: Turn, v: i32) {
+    fn rotate_by(&mut self, t: Turn, v: i32) -> Result<(), Error> {
+        if v % 90 != 0 {
+            return Err(invalid_rotation(v));
+        }
         self.orientation = self.orientation.rotate(t, v);
+        Ok(())
     }
 
-    fn take_action(&mut self, s: &str) {
-        let instruction = s.chars().next().unwrap();
-        let value = s[1..].parse::<i32>().unwrap();
+    fn take_action(&mut self, s: &str) -> Result<(), Error> {
+        let (instruction, value) = parse_action(s)?;
 
-        match Direction::from_char(instruction) {
-            Some(dir) => {
-                self.move_by(dir, value);
-                return;
-            }
-            None => (),
+        if let Some(dir) = Direction::from_char(instruction) {
+            self.move_by(dir, value);
+            return Ok(());
         }
 
         match Turn::from_char(instruction) {
             Some(t) => match t {
                 Turn::Forward => self.move_by(self.orientation, value),
-                Turn::Left | Turn::Right => self.rotate_by(t, value),
+                Turn::Left | Turn::Right => self.rotate_by(t, value)?,
             },
-            None => panic!("Encoutered invalid action code"),
+            None => return Err(invalid_action_code(instruction)),
         }
+        Ok(())
     }
 
-    fn take_waypoint_action(&mut self, s: &str) {
-        let instruction = s.chars().next().unwrap();
-        let value = s[1..].parse::<i32>().unwrap();
+    fn take_waypoint_action(&mut self, s: &str) -> Result<(), Error> {
+        let (instruction, value) = parse_action(s)?;
 
-        match Direction::from_char(instruction) {
-            Some(dir) => {
-                self.move_waypoint_by(dir, value);
-                return;
-            }
-            None => (),
+        if let Some(dir) = Direction::from_char(instruction) {
+            self.move_waypoint_by(dir, value);
+            return Ok(());
         }
 
         match Turn::from_char(instruction) {
             Some(t) => match t {
                 Turn::Forward => self.move_to_waypoint(value),
-                Turn::Left | Turn::Right => self.rotate_waypoint_by(t, value),
+                Turn::Left | Turn::Right => self.rotate_waypoint_by(t, value)?,
             },
-            None => panic!("Encoutered invalid action code"),
+            None => return Err(invalid_action_code(instruction)),
         }
+        Ok(())
     }
 
     fn travel_distance(&self) -> i32 {
@@ -239,7 +258,7 @@ impl fmt::Display for Direction {
 
 impl fmt::Display for Ship {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Facing: {}\n", self.orientation).unwrap();
+        writeln!(f, "Facing: {}", self.orientation).unwrap();
         write!(f, "Path taken: ").unwrap();
         self.path.iter().for_each(|&p| {
             write!(f, " {} ", p).unwrap();
@@ -248,39 +267,46 @@ impl fmt::Display for Ship {
     }
 }
 
-fn calc_travel_distance(filename: &str) -> i32 {
-    let instructions = common::read_file_linewise(filename);
-
+pub fn calc_travel_distance(instructions: &[String]) -> Result<i32, Error> {
     let mut ship = Ship::new();
     for i in instructions {
-        ship.take_action(&i);
+        ship.take_action(i)?;
     }
 
     //ship.draw_path().save("src/day12/path_1.png").unwrap();
-    ship.travel_distance()
+    Ok(ship.travel_distance())
 }
 
-fn calc_travel_distance_by_waypoint(filename: &str) -> i32 {
-    let instructions = common::read_file_linewise(filename);
-
+pub fn calc_travel_distance_by_waypoint(instructions: &[String]) -> Result<i32, Error> {
     let mut ship = Ship::new();
     for i in instructions {
-        ship.take_waypoint_action(&i);
+        ship.take_waypoint_action(i)?;
     }
 
     //ship.draw_path().save("src/day12/path_2.png").unwrap();
-    ship.travel_distance()
+    Ok(ship.travel_distance())
 }
 
-fn main() {
-    println!(
-        "Solution 1: {}",
-        calc_travel_distance("src/day12/input.txt")
-    );
-    println!(
-        "Solution 2: {}",
-        calc_travel_distance_by_waypoint("src/day12/input.txt")
-    );
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_file_linewise(input)
+    }
+
+    fn part1(&self, instructions: &Vec<String>) -> Result<Answer, Error> {
+        Ok(calc_travel_distance(instructions)?.into())
+    }
+
+    fn part2(&self, instructions: &Vec<String>) -> Result<Answer, Error> {
+        Ok(calc_travel_distance_by_waypoint(instructions)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -289,14 +315,26 @@ mod test {
 
     #[test]
     fn test_calc_travel_distance() {
-        assert_eq!(calc_travel_distance("src/day12/input_test.txt"), 25);
+        let instructions = common::read_file_linewise("src/day12/input_test.txt");
+        assert_eq!(calc_travel_distance(&instructions), Ok(25));
     }
 
     #[test]
     fn test_calc_travel_distance_by_waypoint() {
+        let instructions = common::read_file_linewise("src/day12/input_test.txt");
+        assert_eq!(calc_travel_distance_by_waypoint(&instructions), Ok(286));
+    }
+
+    #[test]
+    fn test_invalid_actions() {
+        let rotation = vec![String::from("R45")];
+        assert_eq!(calc_travel_distance(&rotation), Err(invalid_rotation(45)));
         assert_eq!(
-            calc_travel_distance_by_waypoint("src/day12/input_test.txt"),
-            286
+            calc_travel_distance_by_waypoint(&rotation),
+            Err(invalid_rotation(45))
         );
+
+        let action = vec![String::from("X10")];
+        assert_eq!(calc_travel_distance(&action), Err(invalid_action_code('X')));
     }
 }
@@ -0,0 +1,73 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+
+/// Navigation instructions with turns by multiples of 90 degrees. Both routes are followed to
+/// find the answers. The size is the number of instructions.
+pub struct Day12Generator;
+
+/// Unit vectors of north, east, south and west, in clockwise order.
+const DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+fn rotate_clockwise((x, y): (i32, i32), quarters: i32) -> (i32, i32) {
+    (0..quarters.rem_euclid(4)).fold((x, y), |(x, y), _| (y, -x))
+}
+
+impl Generator for Day12Generator {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn default_size(&self) -> usize {
+        780
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut lines = Vec::with_capacity(size);
+        let mut ship = (0, 0);
+        let mut heading = 1i32;
+        let mut waypoint_ship = (0, 0);
+        let mut waypoint = (10, 1);
+
+        for _ in 0..size {
+            let action = ['N', 'E', 'S', 'W', 'L', 'R', 'F'][rng.gen_range(0..7)];
+            let value = match action {
+                'L' | 'R' => 90 * rng.gen_range(1..=3),
+                'F' => rng.gen_range(1..=100),
+                _ => rng.gen_range(1..=5),
+            };
+            lines.push(format!("{}{}", action, value));
+
+            let quarters = if action == 'L' {
+                -value / 90
+            } else {
+                value / 90
+            };
+            match action {
+                'L' | 'R' => {
+                    heading = (heading + quarters).rem_euclid(4);
+                    waypoint = rotate_clockwise(waypoint, quarters);
+                }
+                'F' => {
+                    let (dx, dy) = DIRECTIONS[heading as usize];
+                    ship = (ship.0 + value * dx, ship.1 + value * dy);
+                    waypoint_ship = (
+                        waypoint_ship.0 + value * waypoint.0,
+                        waypoint_ship.1 + value * waypoint.1,
+                    );
+                }
+                _ => {
+                    let i = "NESW".find(action).unwrap();
+                    let (dx, dy) = DIRECTIONS[i];
+                    ship = (ship.0 + value * dx, ship.1 + value * dy);
+                    waypoint = (waypoint.0 + value * dx, waypoint.1 + value * dy);
+                }
+            }
+        }
+
+        Generated::new(
+            generate::lines(lines),
+            Some((ship.0.abs() + ship.1.abs()).into()),
+            Some((waypoint_ship.0.abs() + waypoint_ship.1.abs()).into()),
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use common::check::{self, Checker, Problem, Scanner};
+
+/// Bus notes: the earliest departure on the first line, and comma-separated bus ids or `x` for
+/// buses out of service on the second.
+pub struct Day13Checker;
+
+fn check_buses(s: &mut Scanner) -> Result<(), Problem> {
+    let ids = s.list(",", |s| {
+        if s.eat("x") {
+            return Ok(None);
+        }
+        let column = s.column();
+        let id = s.number::<i128>()?;
+        if id == 0 {
+            return Err(s.problem_at(
+                column,
+                "bus id 0 is invalid",
+                "use x for buses out of service",
+            ));
+        }
+        Ok(Some(id))
+    })?;
+    if ids.iter().all(Option::is_none) {
+        return Err(s.problem_at(1, "no bus is in service", "add the id of at least one bus"));
+    }
+    Ok(())
+}
+
+impl Checker for Day13Checker {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = check::check_each(&lines[..1], |s| s.number::<i32>().map(|_| ()));
+        if lines.len() < 2 {
+            problems.push(check::missing(
+                &lines,
+                "missing line with the bus ids",
+                "add the comma-separated bus ids, with x for buses out of service",
+            ));
+        } else {
+            problems.extend(check::check_each(&lines[1..2], check_buses));
+            problems.extend(check::unexpected_lines(&lines[2..], "the bus ids"));
+        }
+        problems
+    }
+}
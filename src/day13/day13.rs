@@ -1,27 +1,27 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+
 // Sloppy implementation of: https://de.wikipedia.org/wiki/Chinesischer_Restsatz
-// Finds the smallest integer x > 0 that fulfills
+// Finds the smallest integer x >= 0 that fulfills
 //      x = input[0] (mod input[1])
 //      ...
 //      x = input[n] (mond input[n])
-fn find_congruency(input: &Vec<(i128, i128)>) -> i128 {
+pub fn find_congruency(input: &[(i128, i128)]) -> i128 {
     let big_m = input.iter().fold(1, |acc, (_x, m)| acc * m);
 
-    let mut res = input.iter().fold(0, |acc, (x, m)| {
+    let res = input.iter().fold(0, |acc, (x, m)| {
         let mi = big_m / m;
         let (_g, _r, s) = modinverse::egcd(*m, mi);
         let e = s * mi;
         acc + x * e
     });
 
-    while res < 0 {
-        res += big_m
-    }
-
-    res
+    res.rem_euclid(big_m)
 }
 
-fn find_first_bus_to_take(filename: &str) -> i32 {
-    let input = common::read_file_linewise(filename);
+pub fn find_first_bus_to_take(input: &[String]) -> i32 {
     let estimate = input[0].parse::<i32>().unwrap();
     let res = input[1]
         .split(',')
@@ -35,8 +35,7 @@ fn find_first_bus_to_take(filename: &str) -> i32 {
     res.0 * res.1
 }
 
-fn find_earliest_time_for_contest(filename: &str) -> i128 {
-    let input = common::read_file_linewise(filename);
+pub fn find_earliest_time_for_contest(input: &[String]) -> i128 {
     let timetable = input[1]
         .split(',')
         .enumerate()
@@ -50,17 +49,26 @@ fn find_earliest_time_for_contest(filename: &str) -> i128 {
     find_congruency(&timetable)
 }
 
-fn main() {
-    println!(
-        "Solution 1: {}",
-        find_first_bus_to_take("src/day13/input.txt")
-    );
-    println!(
-        "Solution 2: {}",
-        find_earliest_time_for_contest("src/day13/input.txt")
-    );
-
-    find_earliest_time_for_contest("src/day13/input_test.txt");
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_file_linewise(input)
+    }
+
+    fn part1(&self, input: &Vec<String>) -> Result<Answer, Error> {
+        Ok(find_first_bus_to_take(input).into())
+    }
+
+    fn part2(&self, input: &Vec<String>) -> Result<Answer, Error> {
+        Ok(find_earliest_time_for_contest(input).into())
+    }
 }
 
 #[cfg(test)]
@@ -69,38 +77,38 @@ mod test {
 
     #[test]
     fn test_find_first_bus_to_take() {
-        assert_eq!(find_first_bus_to_take("src/day13/input_test.txt"), 295);
+        let input = common::read_file_linewise("src/day13/input_test.txt");
+        assert_eq!(find_first_bus_to_take(&input), 295);
     }
 
     #[test]
     fn test_find_earliest_time_for_contest() {
-        assert_eq!(
-            find_earliest_time_for_contest("src/day13/input_test.txt"),
-            1068781
-        );
+        let input = common::read_file_linewise("src/day13/input_test.txt");
+        assert_eq!(find_earliest_time_for_contest(&input), 1068781);
     }
 
     #[test]
     fn test_find_congruency() {
         assert_eq!(
-            find_congruency(&vec![(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]),
+            find_congruency(&[(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]),
             1068781
         );
-        //assert_eq!(find_congruency(&vec![(0, 17), (11, 13), (16, 19)]), 3417); // This test case somehow doesn't work...
+        // The unreduced sum is positive but larger than the product of the moduli
+        assert_eq!(find_congruency(&[(0, 17), (11, 13), (16, 19)]), 3417);
         assert_eq!(
-            find_congruency(&vec![(0, 67), (6, 7), (57, 59), (58, 61)]),
+            find_congruency(&[(0, 67), (6, 7), (57, 59), (58, 61)]),
             754018
         );
         assert_eq!(
-            find_congruency(&vec![(0, 67), (5, 7), (56, 59), (57, 61)]),
+            find_congruency(&[(0, 67), (5, 7), (56, 59), (57, 61)]),
             779210
         );
         assert_eq!(
-            find_congruency(&vec![(0, 67), (6, 7), (56, 59), (57, 61)]),
+            find_congruency(&[(0, 67), (6, 7), (56, 59), (57, 61)]),
             1261476
         );
         assert_eq!(
-            find_congruency(&vec![(0, 1789), (36, 37), (45, 47), (1886, 1889)]),
+            find_congruency(&[(0, 1789), (36, 37), (45, 47), (1886, 1889)]),
             1202161486
         );
     }
@@ -0,0 +1,77 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Bus notes around a chosen contest time. Bus IDs are distinct primes, so the time is the only
+/// solution below their product. Every bus is placed at the first free offset at which it departs
+/// in time. The estimate is chosen so that exactly one bus leaves first after it. The size is
+/// the number of buses, between 1 and 9.
+pub struct Day13Generator;
+
+fn primes(below: i128) -> Vec<i128> {
+    (2..below)
+        .filter(|&n| (2..).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect()
+}
+
+impl Generator for Day13Generator {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn default_size(&self) -> usize {
+        9
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.clamp(1, 9);
+        let primes = primes(1000)
+            .into_iter()
+            .filter(|&p| p > 10)
+            .collect::<Vec<i128>>();
+        let buses = primes
+            .choose_multiple(rng, size)
+            .copied()
+            .collect::<Vec<i128>>();
+        let product = buses.iter().product::<i128>();
+        let time = rng.gen_range(1..product);
+
+        let mut offsets = Vec::<Option<i128>>::new();
+        for &bus in &buses {
+            let mut offset = (-time).rem_euclid(bus) as usize;
+            while offsets.get(offset).is_some_and(|o| o.is_some()) {
+                offset += bus as usize;
+            }
+            if offsets.len() <= offset {
+                offsets.resize(offset + 1, None);
+            }
+            offsets[offset] = Some(bus);
+        }
+
+        let (estimate, first) = loop {
+            let estimate = rng.gen_range(100_000..1_000_000);
+            let mut waits = buses
+                .iter()
+                .map(|&bus| (bus - estimate % bus, bus))
+                .collect::<Vec<(i128, i128)>>();
+            waits.sort();
+            if waits.iter().all(|&(wait, bus)| wait != bus)
+                && waits.get(1).is_none_or(|w| w.0 != waits[0].0)
+            {
+                break (estimate, waits[0]);
+            }
+        };
+
+        let timetable = offsets
+            .iter()
+            .map(|o| o.map_or_else(|| String::from("x"), |bus| bus.to_string()))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        Generated::new(
+            format!("{}\n{}\n", estimate, timetable),
+            Some((first.0 * first.1).into()),
+            Some(time.into()),
+        )
+    }
+}
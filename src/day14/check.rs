@@ -0,0 +1,33 @@
+use common::check::{self, Checker, Problem};
+
+/// Initialization programs of `mask = <36 of 0, 1 and X>` and `mem[<address>] = <value>` lines.
+pub struct Day14Checker;
+
+impl Checker for Day14Checker {
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            if s.one_of(&["mask", "mem"])? == "mask" {
+                s.literal(" = ")?;
+                let column = s.column();
+                let mask = s.word("a mask of 0, 1 and X", |c| matches!(c, '0' | '1' | 'X'))?;
+                if mask.len() != 36 {
+                    return Err(s.problem_at(
+                        column,
+                        format!("mask has {} bits, expected 36", mask.len()),
+                        "write exactly 36 of 0, 1 and X",
+                    ));
+                }
+            } else {
+                s.literal("[")?;
+                s.number::<u64>()?;
+                s.literal("] = ")?;
+                s.number::<u64>()?;
+            }
+            Ok(())
+        })
+    }
+}
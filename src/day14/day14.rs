@@ -1,12 +1,74 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+pub mod check;
+pub mod generate;
 
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 
+/// Bits of a `mask = ...` line, each a bit of the value the mask is applied to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mask {
+    ones: u64,
+    zeros: u64,
+    floating: u64,
+}
+
+impl Mask {
+    pub fn from_string(s: &str) -> Result<Mask, String> {
+        if s.chars().count() != 36 {
+            return Err(format!("mask has {} bits, expected 36", s.chars().count()));
+        }
+        let mut mask = Mask {
+            ones: 0,
+            zeros: 0,
+            floating: 0,
+        };
+        for (i, c) in s.chars().rev().enumerate() {
+            match c {
+                'X' => mask.floating |= 1u64 << i,
+                '1' => mask.ones |= 1u64 << i,
+                '0' => mask.zeros |= 1u64 << i,
+                _ => return Err(format!("unknown mask bit {:?}", c)),
+            }
+        }
+        Ok(mask)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Mask(Mask),
+    Mem { address: u64, value: u64 },
+}
+
+pub fn parse_instruction(line: &str) -> Result<Instruction, String> {
+    lazy_static! {
+        static ref RE_MASK: Regex = Regex::new("^mask = (.*)$").unwrap();
+        static ref RE_MEM: Regex = Regex::new("^mem\\[(\\d+)\\] = (\\d+)$").unwrap();
+    }
+
+    if let Some(matches) = RE_MASK.captures(line) {
+        Ok(Instruction::Mask(Mask::from_string(&matches[1])?))
+    } else if let Some(matches) = RE_MEM.captures(line) {
+        let number = |i: usize| {
+            matches[i]
+                .parse::<u64>()
+                .map_err(|e| format!("invalid number {:?}: {}", &matches[i], e))
+        };
+        Ok(Instruction::Mem {
+            address: number(1)?,
+            value: number(2)?,
+        })
+    } else {
+        Err(String::from(
+            "expected \"mask = <mask>\" or \"mem[<address>] = <value>\"",
+        ))
+    }
+}
+
 trait Program {
-    fn mask_instruction(&mut self, mask_str: &str);
+    fn mask_instruction(&mut self, mask: &Mask);
     fn mem_instruction(&mut self, address: u64, value: u64);
     fn memory_sum(&self) -> u64;
 }
@@ -28,17 +90,9 @@ impl ProgramV1 {
 }
 
 impl Program for ProgramV1 {
-    fn mask_instruction(&mut self, mask_str: &str) {
-        self.and_mask = !0u64;
-        self.or_mask = 0u64;
-        for (i, c) in mask_str.chars().rev().enumerate() {
-            match c {
-                x if x == 'X' => continue,
-                x if x == '1' => self.or_mask |= 1u64 << i,
-                x if x == '0' => self.and_mask &= !(1u64 << i),
-                _ => panic!("Unknown mask char encountered"),
-            }
-        }
+    fn mask_instruction(&mut self, mask: &Mask) {
+        self.and_mask = !mask.zeros;
+        self.or_mask = mask.ones;
     }
 
     fn mem_instruction(&mut self, address: u64, value: u64) {
@@ -66,17 +120,12 @@ impl ProgramV2 {
 }
 
 impl Program for ProgramV2 {
-    fn mask_instruction(&mut self, mask: &str) {
+    fn mask_instruction(&mut self, mask: &Mask) {
         self.masks.clear();
-        let mut const_or_mask = 0u64;
-        let mut pos_x = Vec::<usize>::new();
-        for (i, c) in mask.chars().rev().enumerate() {
-            match c {
-                x if x == '1' => const_or_mask |= 1u64 << i,
-                x if x == 'X' => pos_x.push(i),
-                _ => (),
-            }
-        }
+        let const_or_mask = mask.ones;
+        let pos_x = (0..64)
+            .filter(|i| mask.floating & 1u64 << i != 0)
+            .collect::<Vec<usize>>();
         for i in 0u64..(1u64 << pos_x.len()) {
             let mut and_mask = !0u64;
             let mut or_mask = const_or_mask;
@@ -102,55 +151,72 @@ impl Program for ProgramV2 {
     }
 }
 
-fn run_program(filename: &str, p: &mut dyn Program) -> u64 {
-    lazy_static! {
-        static ref RE_MASK: Regex = Regex::new("^mask = (.*)").unwrap();
-        static ref RE_MEM: Regex = Regex::new("^mem\\[(\\d+)\\] = (\\d+)").unwrap();
-    }
-
-    for line in &common::read_file_linewise(filename) {
-        if RE_MASK.is_match(line) {
-            let matches = RE_MASK.captures(line).unwrap();
-            p.mask_instruction(&matches[1]);
-        } else if RE_MEM.is_match(line) {
-            let matches = RE_MEM.captures(line).unwrap();
-            let address = matches[1].parse::<u64>().unwrap();
-            let value = matches[2].parse::<u64>().unwrap();
-            p.mem_instruction(address, value);
+fn run_program(instructions: &[Instruction], p: &mut dyn Program) -> u64 {
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(mask) => p.mask_instruction(mask),
+            Instruction::Mem { address, value } => p.mem_instruction(*address, *value),
         }
     }
 
     p.memory_sum()
 }
 
-fn main() {
-    println!(
-        "Solution 1: {}",
-        run_program("src/day14/input.txt", &mut ProgramV1::new())
-    );
-    println!(
-        "Solution 2: {}",
-        run_program("src/day14/input.txt", &mut ProgramV2::new())
-    );
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instruction>;
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<Instruction>, ParseError> {
+        common::try_parse_file_linewise(input, parse_instruction)
+    }
+
+    fn part1(&self, instructions: &Vec<Instruction>) -> Result<Answer, Error> {
+        Ok(run_program(instructions, &mut ProgramV1::new()).into())
+    }
+
+    fn part2(&self, instructions: &Vec<Instruction>) -> Result<Answer, Error> {
+        Ok(run_program(instructions, &mut ProgramV2::new()).into())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn instructions(path: &str) -> Vec<Instruction> {
+        common::try_parse_file_linewise(path, parse_instruction).unwrap()
+    }
+
     #[test]
     fn test_v1() {
-        assert_eq!(
-            run_program("src/day14/input_test_01.txt", &mut ProgramV1::new()),
-            165
-        );
+        let instructions = instructions("src/day14/input_test_01.txt");
+        assert_eq!(run_program(&instructions, &mut ProgramV1::new()), 165);
     }
 
     #[test]
     fn test_v2() {
+        let instructions = instructions("src/day14/input_test_02.txt");
+        assert_eq!(run_program(&instructions, &mut ProgramV2::new()), 208);
+    }
+
+    #[test]
+    fn test_parse_instruction() {
         assert_eq!(
-            run_program("src/day14/input_test_02.txt", &mut ProgramV2::new()),
-            208
+            parse_instruction("mem[8] = 11"),
+            Ok(Instruction::Mem {
+                address: 8,
+                value: 11
+            })
         );
+        assert!(parse_instruction("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").is_ok());
+        assert!(parse_instruction("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0Y").is_err());
+        assert!(parse_instruction("mask = X1X0").is_err());
+        assert!(parse_instruction("mem[8] = 99999999999999999999").is_err());
+        assert!(parse_instruction("mem[8] 11").is_err());
     }
 }
@@ -0,0 +1,85 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+use std::collections::HashMap;
+
+const BITS: usize = 36;
+
+/// Initialization programs with a new mask every few writes. Masks have at most eight floating
+/// bits, which keeps the addresses written by version 2 of the decoder at 256 per write. Both
+/// decoders are run to find the answers. The size is the number of lines.
+pub struct Day14Generator;
+
+fn mask(rng: &mut StdRng) -> String {
+    let floating = rng.gen_range(0..=8);
+    let mut mask = (0..BITS)
+        .map(|_| if rng.gen_bool(0.5) { '1' } else { '0' })
+        .collect::<Vec<char>>();
+    for _ in 0..floating {
+        mask[rng.gen_range(0..BITS)] = 'X';
+    }
+    mask.into_iter().collect()
+}
+
+/// Addresses a version 2 write to `address` ends up at.
+fn floating_addresses(mask: &str, address: u64) -> Vec<u64> {
+    mask.chars()
+        .rev()
+        .enumerate()
+        .fold(vec![address], |addresses, (bit, c)| match c {
+            '1' => addresses.iter().map(|a| a | 1 << bit).collect(),
+            'X' => addresses
+                .iter()
+                .flat_map(|a| vec![a & !(1 << bit), a | 1 << bit])
+                .collect(),
+            _ => addresses,
+        })
+}
+
+impl Generator for Day14Generator {
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn default_size(&self) -> usize {
+        570
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut lines = Vec::with_capacity(size);
+        let mut memory = HashMap::new();
+        let mut floating_memory = HashMap::new();
+        let mut current = String::new();
+
+        while lines.len() < size {
+            if current.is_empty() || rng.gen_bool(0.2) {
+                current = mask(rng);
+                lines.push(format!("mask = {}", current));
+                continue;
+            }
+
+            let address = rng.gen_range(0..1 << 16);
+            let value = rng.gen_range(0..1u64 << 30);
+            lines.push(format!("mem[{}] = {}", address, value));
+
+            let masked = current
+                .chars()
+                .rev()
+                .enumerate()
+                .fold(value, |v, (bit, c)| match c {
+                    '1' => v | 1 << bit,
+                    '0' => v & !(1 << bit),
+                    _ => v,
+                });
+            memory.insert(address, masked);
+            for a in floating_addresses(&current, address) {
+                floating_memory.insert(a, value);
+            }
+        }
+
+        Generated::new(
+            generate::lines(lines),
+            Some(memory.values().sum::<u64>().into()),
+            Some(floating_memory.values().sum::<u64>().into()),
+        )
+    }
+}
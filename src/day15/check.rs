@@ -0,0 +1,23 @@
+use common::check::{self, Checker, Problem};
+
+/// Starting numbers: a single line of comma-separated numbers.
+pub struct Day15Checker;
+
+impl Checker for Day15Checker {
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = check::check_each(&lines[..1], |s| {
+            s.list(",", |s| s.number::<u32>()).map(|_| ())
+        });
+        problems.extend(check::unexpected_lines(&lines[1..], "the starting numbers"));
+        problems
+    }
+}
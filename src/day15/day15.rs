@@ -1,13 +1,17 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, Location, ParseError, Solution};
 use std::collections::HashMap;
 
-struct Game {
+pub struct Game {
     last_number: u32,
     history: HashMap<u32, Vec<u32>>,
     current_turn: u32,
 }
 
 impl Game {
-    fn new(init: &Vec<u32>) -> Game {
+    pub fn new(init: &[u32]) -> Game {
         let mut game = Game {
             last_number: 0,
             history: HashMap::<u32, Vec<u32>>::new(),
@@ -30,12 +34,11 @@ impl Game {
         self.current_turn += 1;
     }
 
+    /// Turns since the last number was spoken before, 0 if it is new.
     fn get_number(&self) -> u32 {
-        let n = self.history.get(&self.last_number).unwrap();
-        match n.len() {
-            x if x == 1 => 0,
-            x if x > 1 => self.current_turn - 1 - n[n.len() - 2],
-            _ => panic!("shouldn't happen"),
+        match self.history.get(&self.last_number).map(Vec::as_slice) {
+            Some([.., before, _]) => self.current_turn - 1 - before,
+            _ => 0,
         }
     }
 
@@ -45,22 +48,54 @@ impl Game {
         n
     }
 
-    fn advance_to(&mut self, turn: u32) -> u32 {
+    pub fn advance_to(&mut self, turn: u32) -> Result<u32, Error> {
+        if self.history.is_empty() {
+            return Err(Error::invalid_input("there are no starting numbers"));
+        }
         if turn < self.current_turn {
-            panic!("turn too low")
+            return Err(Error::invalid_input(format!(
+                "turn {} is already covered by the {} starting numbers",
+                turn,
+                self.current_turn - 1
+            )));
         }
 
         while self.current_turn < turn {
             self.next();
         }
 
-        self.get_number()
+        Ok(self.get_number())
     }
 }
 
-fn main() {
-    println!("Solution 1: {}", Game::new(&vec![0,3,1,6,7,5]).advance_to(2020));
-    println!("Solution 2: {}", Game::new(&vec![0,3,1,6,7,5]).advance_to(30_000_000));
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<u32>;
+
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<u32>, ParseError> {
+        let lines = common::try_parse_file_linewise(input, |line| {
+            line.split(',')
+                .map(|x| x.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+        })?;
+        lines
+            .into_iter()
+            .next()
+            .ok_or_else(|| ParseError::new(input, Location::Input, "", "input is empty"))
+    }
+
+    fn part1(&self, init: &Vec<u32>) -> Result<Answer, Error> {
+        Ok(Game::new(init).advance_to(2020)?.into())
+    }
+
+    fn part2(&self, init: &Vec<u32>) -> Result<Answer, Error> {
+        Ok(Game::new(init).advance_to(30_000_000)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -69,12 +104,14 @@ mod test {
 
     #[test]
     fn test_game() {
-        assert_eq!(Game::new(&vec![0, 3, 6]).advance_to(2020), 436);
-        assert_eq!(Game::new(&vec![1, 3, 2]).advance_to(2020), 1);
-        assert_eq!(Game::new(&vec![2, 1, 3]).advance_to(2020), 10);
-        assert_eq!(Game::new(&vec![1, 2, 3]).advance_to(2020), 27);
-        assert_eq!(Game::new(&vec![2, 3, 1]).advance_to(2020), 78);
-        assert_eq!(Game::new(&vec![3, 2, 1]).advance_to(2020), 438);
-        assert_eq!(Game::new(&vec![3, 1, 2]).advance_to(2020), 1836);
+        assert_eq!(Game::new(&[0, 3, 6]).advance_to(2020), Ok(436));
+        assert_eq!(Game::new(&[1, 3, 2]).advance_to(2020), Ok(1));
+        assert_eq!(Game::new(&[2, 1, 3]).advance_to(2020), Ok(10));
+        assert_eq!(Game::new(&[1, 2, 3]).advance_to(2020), Ok(27));
+        assert_eq!(Game::new(&[2, 3, 1]).advance_to(2020), Ok(78));
+        assert_eq!(Game::new(&[3, 2, 1]).advance_to(2020), Ok(438));
+        assert_eq!(Game::new(&[3, 1, 2]).advance_to(2020), Ok(1836));
+        assert!(Game::new(&[0, 3, 6]).advance_to(2).is_err());
+        assert!(Game::new(&[]).advance_to(2020).is_err());
     }
 }
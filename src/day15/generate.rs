@@ -0,0 +1,46 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+
+/// Distinct starting numbers of the memory game. The 2020th number is found by playing the game,
+/// the 30000000th is left to the solvers. The size is the number of starting numbers, between 1
+/// and 100.
+pub struct Day15Generator;
+
+fn spoken_at(start: &[u32], turn: usize) -> u32 {
+    let mut last_seen = HashMap::new();
+    let mut last = start[0];
+    for t in 1..turn {
+        let next = match start.get(t) {
+            Some(&n) => n,
+            None => last_seen.get(&last).map_or(0, |&seen| (t - seen) as u32),
+        };
+        last_seen.insert(last, t);
+        last = next;
+    }
+    last
+}
+
+impl Generator for Day15Generator {
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn default_size(&self) -> usize {
+        6
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.clamp(1, 100);
+        let mut numbers = (0..3 * size as u32 + 10).collect::<Vec<u32>>();
+        numbers.shuffle(rng);
+        numbers.truncate(size);
+
+        let line = numbers
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        Generated::new(line + "\n", Some(spoken_at(&numbers, 2020).into()), None)
+    }
+}
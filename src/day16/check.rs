@@ -0,0 +1,128 @@
+use common::check::{self, Checker, Line, Problem, Scanner};
+use std::collections::HashSet;
+
+/// Ticket notes: field rules like `class: 1-3 or 5-7`, then `your ticket:` and `nearby tickets:`
+/// sections of comma-separated values, one value per field. Sections are separated by blank lines.
+pub struct Day16Checker;
+
+fn check_range(s: &mut Scanner) -> Result<(), Problem> {
+    let column = s.column();
+    let from = s.number::<i32>()?;
+    s.literal("-")?;
+    let to = s.number::<i32>()?;
+    if from > to {
+        return Err(s.problem_at(
+            column,
+            format!("range {}-{} is empty", from, to),
+            "swap the two numbers",
+        ));
+    }
+    Ok(())
+}
+
+fn check_rules(rules: &[Line]) -> Vec<Problem> {
+    let mut names = HashSet::new();
+    let mut problems = check::check_each(rules, |s| {
+        s.word("a field name", |c| c != ':')?;
+        s.literal(": ")?;
+        check_range(s)?;
+        s.literal(" or ")?;
+        check_range(s)
+    });
+    for line in rules {
+        let name = line.text.split(':').next().unwrap_or_default();
+        if !names.insert(name) {
+            problems.push(line.problem(
+                1,
+                format!("field {:?} is defined twice", name),
+                "remove one of the rules",
+            ));
+        }
+    }
+    problems.sort_by_key(|p| (p.line, p.column));
+    problems
+}
+
+fn check_tickets(section: &[Line], header: &str, fields: usize) -> Vec<Problem> {
+    let mut problems = check::check_each(&section[..1], |s| s.literal(header));
+    problems.extend(check::check_each(&section[1..], |s| {
+        let values = s.list(",", |s| s.number::<i32>())?;
+        if values.len() != fields {
+            return Err(s.problem_at(
+                1,
+                format!("ticket has {} values, expected {}", values.len(), fields),
+                "give every ticket one value per field rule",
+            ));
+        }
+        Ok(())
+    }));
+    problems
+}
+
+impl Checker for Day16Checker {
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let groups = check::groups(input);
+        if groups.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let fields = groups[0].len();
+        let mut problems = check_rules(&groups[0]);
+        match groups.get(1) {
+            Some(mine) => {
+                problems.extend(check_tickets(mine, "your ticket:", fields));
+                if mine.len() != 2 {
+                    problems.push(mine[0].problem(
+                        1,
+                        format!("expected 1 ticket of yours, got {}", mine.len() - 1),
+                        "list exactly one ticket below \"your ticket:\"",
+                    ));
+                }
+            }
+            None => problems.push(check::missing(
+                &groups[0],
+                "missing section \"your ticket:\"",
+                "add a blank line, \"your ticket:\" and the values of your ticket",
+            )),
+        }
+        match groups.get(2) {
+            Some(nearby) => problems.extend(check_tickets(nearby, "nearby tickets:", fields)),
+            None => problems.push(check::missing(
+                groups.last().unwrap(),
+                "missing section \"nearby tickets:\"",
+                "add a blank line, \"nearby tickets:\" and the nearby tickets",
+            )),
+        }
+        for group in groups.iter().skip(3) {
+            problems.extend(check::unexpected_lines(group, "the nearby tickets"));
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input = "class: 1-3 or 5-7\nrow 6-11 or 33-44\nseat: 13-40 or 45-5\n\n\
+                     your ticket:\n7,1\n\nnearby tickets:\n7,3,47\n40,x,50\n";
+        let problems = Day16Checker.check(input);
+        let positions = problems
+            .iter()
+            .map(|p| (p.line, p.column))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [(2, 18), (3, 16), (6, 1), (10, 4)]);
+        assert_eq!(problems[0].message, "expected \": \", found end of line");
+        assert_eq!(problems[1].message, "range 45-5 is empty");
+        assert_eq!(problems[2].message, "ticket has 2 values, expected 3");
+        assert!(Day16Checker.check("class: 1-3 or 5-7\n")[0]
+            .message
+            .contains("your ticket:"));
+    }
+}
@@ -1,7 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+pub mod check;
+pub mod generate;
 
+use common::matching;
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -55,13 +57,12 @@ impl Limits {
                 return false;
             }
         }
-        return true;
+        true
     }
 }
 
-fn solve_1(filename: &str) -> i32 {
-    let groups = common::read_grouped_file(filename);
-
+/// Sum of all values of nearby tickets that are invalid for every field.
+pub fn solve_1(groups: &[String]) -> i32 {
     let l = Limits::from_string(&groups[0]);
 
     groups[2]
@@ -79,9 +80,8 @@ fn solve_1(filename: &str) -> i32 {
         })
 }
 
-fn solve_2(filename: &str) -> u64 {
-    let groups = common::read_grouped_file(filename);
-
+/// Product of the fields of your ticket whose names start with "departure".
+pub fn solve_2(groups: &[String]) -> Result<u64, Error> {
     let l = Limits::from_string(&groups[0]);
 
     let valid_tickets = groups[2]
@@ -96,57 +96,83 @@ fn solve_2(filename: &str) -> u64 {
         .filter(|ticket| l.matches_ticket(ticket))
         .collect::<Vec<Vec<i32>>>();
 
-    let possible_values = l
-        .range
-        .iter()
-        .map(|(k, _v)| k.clone())
-        .collect::<HashSet<String>>();
-    let mut possible_values = (0..valid_tickets.first().unwrap().len())
-        .map(|_| possible_values.clone())
-        .collect::<Vec<HashSet<String>>>();
+    let possible_values = l.range.keys().cloned().collect::<HashSet<String>>();
+    let mut possible_values = (0..valid_tickets.first().map_or(0, |t| t.len()))
+        .map(|i| (i, possible_values.clone()))
+        .collect::<HashMap<usize, HashSet<String>>>();
 
     for ticket in valid_tickets {
         for (i, v) in ticket.iter().enumerate() {
             let matches = l.matches(*v);
-            possible_values[i] = possible_values[i].intersection(&matches).cloned().collect();
-        }
-    }
-
-    let len = possible_values.len();
-    while possible_values.iter().any(|entry| entry.len() > 1) {
-        for i in 0..len {
-            if possible_values[i].len() == 1 {
-                let value_to_remove = possible_values[i].iter().next().unwrap().clone();
-                for j in 0..len {
-                    if i == j {
-                        continue;
-                    }
-                    possible_values[j].remove(&value_to_remove);
-                }
+            if let Some(values) = possible_values.get_mut(&i) {
+                *values = values.intersection(&matches).cloned().collect();
             }
         }
     }
 
+    let fields = matching::assign_unique(&possible_values)?;
+
     let my_ticket = groups[1]
         .split("\n")
-        .skip(1)
-        .next()
+        .nth(1)
         .unwrap()
         .trim()
         .split(",")
         .map(|x| x.parse::<i32>().unwrap())
         .collect::<Vec<i32>>();
 
-    possible_values.iter().enumerate().fold(1u64, |acc, (i, v)| {
-        if v.iter().next().unwrap().clone().starts_with("departure") {
-            return acc * (my_ticket[i] as u64);
+    Ok(fields.iter().fold(1u64, |acc, (i, field)| {
+        if field.starts_with("departure") {
+            acc * (my_ticket[*i] as u64)
         } else {
-            return acc;
+            acc
         }
-    })
+    }))
 }
 
-fn main() {
-    println!("Solution 1: {}", solve_1("src/day16/input.txt"));
-    println!("Solution 2: {}", solve_2("src/day16/input.txt"));
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_grouped_file(input)
+    }
+
+    fn part1(&self, groups: &Vec<String>) -> Result<Answer, Error> {
+        Ok(solve_1(groups).into())
+    }
+
+    fn part2(&self, groups: &Vec<String>) -> Result<Answer, Error> {
+        Ok(solve_2(groups)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let groups = common::read_grouped_file("src/day16/input_test.txt");
+        assert_eq!(solve_1(&groups), 71);
+        assert_eq!(solve_2(&groups), Ok(1));
+    }
+
+    #[test]
+    fn test_ambiguous_fields() {
+        let groups = vec![
+            String::from("departure a: 1-2 or 4-5\ndeparture b: 1-2 or 4-5"),
+            String::from("your ticket:\n1,2"),
+            String::from("nearby tickets:\n1,2\n4,5"),
+        ];
+        assert_eq!(
+            solve_2(&groups),
+            Err(Error::no_solution("assignment is ambiguous for 0, 1"))
+        );
+    }
 }
@@ -0,0 +1,133 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Width of the gap between the two ranges of a field.
+const GAP: i32 = 10;
+
+/// Ticket notes for the twenty fields of the puzzle.
+///
+/// Every field accepts the values from about 40 to about 960 except for a gap of its own. Valid
+/// tickets only hold values outside of all gaps, except that the column of a field holds a value
+/// in the gap of each field ranked before it in a hidden order. So the column of the last field
+/// fits only that field, the one before it two fields and so on, which makes the assignment
+/// unique. A quarter of the tickets holds one value above all ranges. The size is the number of
+/// nearby tickets, at least 40.
+pub struct Day16Generator;
+
+fn safe_value(rng: &mut StdRng, gaps: &[i32]) -> i32 {
+    loop {
+        let v = rng.gen_range(50..950);
+        if gaps.iter().all(|&g| v < g || v >= g + GAP) {
+            return v;
+        }
+    }
+}
+
+fn join(values: &[i32]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+impl Generator for Day16Generator {
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn default_size(&self) -> usize {
+        240
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let n = FIELDS.len();
+        let size = size.max(2 * n);
+
+        let mut gaps = (0..n as i32).map(|i| 60 + 44 * i).collect::<Vec<i32>>();
+        gaps.shuffle(rng);
+        let mut rank = (0..n).collect::<Vec<usize>>();
+        rank.shuffle(rng);
+        let mut columns = (0..n).collect::<Vec<usize>>();
+        columns.shuffle(rng);
+
+        let rules = (0..n)
+            .map(|f| {
+                format!(
+                    "{}: {}-{} or {}-{}",
+                    FIELDS[f],
+                    rng.gen_range(25..50),
+                    gaps[f] - 1,
+                    gaps[f] + GAP,
+                    rng.gen_range(950..975)
+                )
+            })
+            .collect::<Vec<String>>();
+
+        let mine = (0..n).map(|_| safe_value(rng, &gaps)).collect::<Vec<i32>>();
+        let departures = (0..n)
+            .filter(|&c| FIELDS[columns[c]].starts_with("departure"))
+            .map(|c| mine[c] as u64)
+            .product::<u64>();
+
+        let mut nearby = (0..size)
+            .map(|_| (0..n).map(|_| safe_value(rng, &gaps)).collect())
+            .collect::<Vec<Vec<i32>>>();
+        let (valid, invalid) = nearby.split_at_mut(size - size / 4);
+        for (c, &field) in columns.iter().enumerate() {
+            let excluded = (0..n)
+                .filter(|&f| rank[f] < rank[field])
+                .collect::<Vec<usize>>();
+            let tickets = index::sample(rng, valid.len(), excluded.len());
+            for (t, f) in tickets.iter().zip(excluded) {
+                valid[t][c] = rng.gen_range(gaps[f]..gaps[f] + GAP);
+            }
+        }
+        let mut error_rate = 0;
+        for ticket in invalid {
+            let v = rng.gen_range(975..1000);
+            ticket[rng.gen_range(0..n)] = v;
+            error_rate += v;
+        }
+        nearby.shuffle(rng);
+
+        Generated::new(
+            format!(
+                "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}\n",
+                rules.join("\n"),
+                join(&mine),
+                nearby
+                    .iter()
+                    .map(|t| join(t))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ),
+            Some(error_rate.into()),
+            Some(departures.into()),
+        )
+    }
+}
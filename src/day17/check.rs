@@ -0,0 +1,18 @@
+use common::check::{self, Checker, Problem};
+
+/// Initial slices of inactive `.` and active `#` cubes.
+pub struct Day17Checker;
+
+impl Checker for Day17Checker {
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+        check::check_grid(&lines, ".#")
+    }
+}
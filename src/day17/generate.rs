@@ -0,0 +1,28 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+
+/// Square initial slices of the pocket dimension with about a third of the cubes active. The
+/// boot process can only be simulated, so no answers are known. The size is the side length.
+pub struct Day17Generator;
+
+impl Generator for Day17Generator {
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn default_size(&self) -> usize {
+        8
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = size.max(1);
+        let rows = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.gen_bool(0.35) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        Generated::new(generate::lines(rows), None, None)
+    }
+}
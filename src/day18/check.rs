@@ -0,0 +1,78 @@
+use common::check::{self, Checker, Problem, Scanner};
+
+/// Expressions of single digits, `+`, `*` and parentheses, one per line.
+pub struct Day18Checker;
+
+fn check_expression(s: &mut Scanner) -> Result<(), Problem> {
+    let mut open = Vec::new();
+    loop {
+        while s.peek() == Some('(') {
+            open.push(s.column());
+            s.literal("(")?;
+            s.skip_spaces();
+        }
+
+        let column = s.column();
+        let number = s.word("a digit or \"(\"", |c| c.is_ascii_digit())?;
+        if number.len() > 1 {
+            return Err(s.problem_at(
+                column,
+                format!("number {} has more than one digit", number),
+                "use single digits only",
+            ));
+        }
+        s.skip_spaces();
+
+        while s.peek() == Some(')') {
+            if open.pop().is_none() {
+                return Err(s.problem(
+                    "\")\" without matching \"(\"",
+                    "remove it or add a \"(\" before",
+                ));
+            }
+            s.literal(")")?;
+            s.skip_spaces();
+        }
+
+        if s.at_end() {
+            break;
+        }
+        s.one_of(&["+", "*"])?;
+        s.skip_spaces();
+    }
+
+    match open.pop() {
+        Some(column) => Err(s.problem_at(
+            column,
+            "\"(\" is never closed",
+            "add a \")\" or remove this one",
+        )),
+        None => Ok(()),
+    }
+}
+
+impl Checker for Day18Checker {
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, check_expression)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input = "1 + (2 * 3)\n(1 + 2\n1 + 2)\n12 * 3\n1 + * 2\n";
+        let problems = Day18Checker.check(input);
+        let positions = problems
+            .iter()
+            .map(|p| (p.line, p.column))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [(2, 1), (3, 6), (4, 1), (5, 5)]);
+    }
+}
@@ -1,97 +1,137 @@
-fn extract_parenthesis_substr(s: &str) -> &str {
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+
+fn extract_parenthesis_substr(s: &str) -> Result<&str, String> {
     let mut cnt = 1;
 
-    if s.chars().next().unwrap() != '(' {
-        panic!("string does not start with parenthesis");
+    if !s.starts_with('(') {
+        return Err(String::from("expected an opening parenthesis"));
     }
 
-    for (i, c) in s.chars().enumerate().skip(1) {
+    for (i, c) in s.char_indices().skip(1) {
         match c {
-            x if x == '(' => cnt += 1,
-            x if x == ')' => cnt -= 1,
+            '(' => cnt += 1,
+            ')' => cnt -= 1,
             _ => (),
         }
 
         if cnt == 0 {
-            return &s[1..i];
+            return Ok(&s[1..i]);
         }
     }
 
-    panic!("could not find matching closing paren")
+    Err(String::from("could not find matching closing parenthesis"))
 }
 
-fn action(op: char, a: u64, b: u64) -> u64 {
-    match op {
-        x if x == '+' => a + b,
-        x if x == '*' => a * b,
-        _ => panic!("invalid op"),
+fn action(op: char, a: u64, b: u64) -> Result<u64, String> {
+    let ret = match op {
+        '+' => a.checked_add(b),
+        '*' => a.checked_mul(b),
+        _ => return Err(format!("invalid operator {:?}", op)),
+    };
+    ret.ok_or_else(|| format!("{} {} {} does not fit into 64 bits", a, op, b))
+}
+
+fn digit(c: char) -> Result<u64, String> {
+    match c {
+        ')' => Err(String::from("unmatched closing parenthesis")),
+        c => c
+            .to_digit(10)
+            .map(u64::from)
+            .ok_or_else(|| format!("unexpected character {:?}", c)),
     }
 }
 
-fn eval_greedy(s: &str) -> u64 {
+pub fn eval_greedy(s: &str) -> Result<u64, String> {
     let mut ret = 0;
     let mut op = '+';
 
-    let mut iter = s.chars().enumerate();
+    let mut iter = s.char_indices();
     while let Some(val) = iter.next() {
         match val.1 {
-            x if x == ' ' => (),
-            x if x == '*' => op = x,
-            x if x == '+' => op = x,
-            x if x == '(' => {
-                let substr = extract_parenthesis_substr(&s[val.0..]);
-                ret = action(op, ret, eval_greedy(substr));
+            ' ' => (),
+            x @ '*' => op = x,
+            x @ '+' => op = x,
+            '(' => {
+                let substr = extract_parenthesis_substr(&s[val.0..])?;
+                ret = action(op, ret, eval_greedy(substr)?)?;
                 for _ in 0..(substr.len() + 1) {
                     iter.next();
                 }
             }
-            x => ret = action(op, ret, String::from(x).parse::<u64>().unwrap()),
+            x => ret = action(op, ret, digit(x)?)?,
         }
     }
-    ret
+    Ok(ret)
 }
 
-fn eval_addition_precedence(s: &str) -> u64 {
+pub fn eval_addition_precedence(s: &str) -> Result<u64, String> {
     let mut ret = 1;
     let mut cur = 0;
 
-    let mut iter = s.chars().enumerate();
+    let mut iter = s.char_indices();
     while let Some(val) = iter.next() {
         match val.1 {
-            x if x == ' ' => (),
-            x if x == '*' => {
-                ret *= cur;
+            ' ' => (),
+            '*' => {
+                ret = action('*', ret, cur)?;
                 cur = 0
             }
-            x if x == '+' => (),
-            x if x == '(' => {
-                let substr = extract_parenthesis_substr(&s[val.0..]);
-                cur += eval_addition_precedence(substr);
+            '+' => (),
+            '(' => {
+                let substr = extract_parenthesis_substr(&s[val.0..])?;
+                cur = action('+', cur, eval_addition_precedence(substr)?)?;
                 for _ in 0..(substr.len() + 1) {
                     iter.next();
                 }
             }
-            x => cur += String::from(x).parse::<u64>().unwrap(),
+            x => cur = action('+', cur, digit(x)?)?,
         }
     }
-    ret * cur
+    action('*', ret, cur)
 }
 
-fn solve_1(filename: &str) -> u64 {
-    common::read_file_linewise(filename)
-        .iter()
-        .fold(0u64, |acc, eq| acc + eval_greedy(eq))
+fn sum_of<F>(lines: &[String], eval: F) -> Result<u64, Error>
+where
+    F: Fn(&str) -> Result<u64, String>,
+{
+    lines.iter().try_fold(0u64, |acc, eq| {
+        let value = eval(eq).map_err(|e| Error::invalid_input(format!("{} in {:?}", e, eq)))?;
+        acc.checked_add(value)
+            .ok_or_else(|| Error::invalid_input("sum does not fit into 64 bits"))
+    })
 }
 
-fn solve_2(filename: &str) -> u64 {
-    common::read_file_linewise(filename)
-        .iter()
-        .fold(0u64, |acc, eq| acc + eval_addition_precedence(eq))
+fn solve_1(lines: &[String]) -> Result<u64, Error> {
+    sum_of(lines, eval_greedy)
 }
 
-fn main() {
-    println!("Solution 1: {}", solve_1("src/day18/input.txt"));
-    println!("Solution 1: {}", solve_2("src/day18/input.txt"));
+fn solve_2(lines: &[String]) -> Result<u64, Error> {
+    sum_of(lines, eval_addition_precedence)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_file_linewise(input)
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<Answer, Error> {
+        Ok(solve_1(lines)?.into())
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<Answer, Error> {
+        Ok(solve_2(lines)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -101,20 +141,38 @@ mod test {
     #[test]
     fn test_eval_greedy() {
         let lines = common::read_file_linewise("src/day18/input_test.txt");
-        let expected = vec![71u64, 51, 26, 437, 12240, 13632];
+        let expected = [71u64, 51, 26, 437, 12240, 13632];
 
         for i in 0..lines.len() {
-            assert_eq!(eval_greedy(&lines[i]), expected[i]);
+            assert_eq!(eval_greedy(&lines[i]), Ok(expected[i]));
         }
     }
 
     #[test]
     fn test_eval_addition_precedence() {
         let lines = common::read_file_linewise("src/day18/input_test.txt");
-        let expected = vec![231u64, 51, 46, 1445, 669060, 23340];
+        let expected = [231u64, 51, 46, 1445, 669060, 23340];
 
         for i in 0..lines.len() {
-            assert_eq!(eval_addition_precedence(&lines[i]), expected[i]);
+            assert_eq!(eval_addition_precedence(&lines[i]), Ok(expected[i]));
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        let too_large = format!("({}) * 9", vec!["9"; 21].join(" * "));
+        for eval in [eval_greedy, eval_addition_precedence] {
+            assert!(eval("1 + (2 * 3").is_err());
+            assert!(eval("1 + 2)").is_err());
+            assert!(eval("1 - 2").is_err());
+            assert!(eval(&too_large).is_err());
         }
+        let lines = vec![String::from("1 + 2"), String::from("3 ? 4")];
+        assert_eq!(
+            solve_1(&lines),
+            Err(Error::invalid_input(
+                "unexpected character '?' in \"3 ? 4\""
+            ))
+        );
     }
 }
@@ -0,0 +1,77 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::Rng;
+
+/// Largest value of a line, which keeps the sum of all lines within a `u64`.
+const MAX_VALUE: u128 = 1_000_000_000_000;
+
+/// Homework of expressions with up to two levels of parentheses. Every expression is evaluated
+/// under both sets of precedence rules while it is built. The size is the number of lines.
+pub struct Day18Generator;
+
+/// Random expression with its values when evaluating strictly from left to right and when
+/// evaluating additions before multiplications.
+fn expression(rng: &mut StdRng, depth: u32) -> (String, u128, u128) {
+    let operands = rng.gen_range(2..=6);
+    let mut text = String::new();
+    let mut left_to_right = 0;
+    let mut product = 1;
+    let mut sum = 0;
+
+    for i in 0..operands {
+        let (operand, value, value_addition_first) = if depth > 0 && rng.gen_bool(0.25) {
+            let (inner, a, b) = expression(rng, depth - 1);
+            (format!("({})", inner), a, b)
+        } else {
+            let n = rng.gen_range(1..10);
+            (n.to_string(), n, n)
+        };
+
+        if i == 0 {
+            left_to_right = value;
+            sum = value_addition_first;
+        } else if rng.gen_bool(0.5) {
+            text.push_str(" + ");
+            left_to_right += value;
+            sum += value_addition_first;
+        } else {
+            text.push_str(" * ");
+            left_to_right *= value;
+            product *= sum;
+            sum = value_addition_first;
+        }
+        text.push_str(&operand);
+    }
+
+    (text, left_to_right, product * sum)
+}
+
+impl Generator for Day18Generator {
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn default_size(&self) -> usize {
+        370
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut lines = Vec::with_capacity(size);
+        let mut total = 0;
+        let mut total_addition_first = 0;
+
+        while lines.len() < size {
+            let (line, a, b) = expression(rng, 2);
+            if a <= MAX_VALUE && b <= MAX_VALUE {
+                lines.push(line);
+                total += a;
+                total_addition_first += b;
+            }
+        }
+
+        Generated::new(
+            generate::lines(lines),
+            Some((total as u64).into()),
+            Some((total_addition_first as u64).into()),
+        )
+    }
+}
@@ -0,0 +1,124 @@
+use common::check::{self, Checker, Line, Problem, Scanner};
+use std::collections::HashMap;
+
+/// Message rules like `0: 4 1 5`, `1: 2 3 | 3 2` or `4: "a"`, followed by a blank line and the
+/// received messages. Every referenced rule and rule 0 have to be defined.
+pub struct Day19Checker;
+
+type Reference<'a> = (u32, Line<'a>, usize);
+
+fn check_rule<'a>(
+    s: &mut Scanner<'a>,
+    references: &mut Vec<Reference<'a>>,
+) -> Result<u32, Problem> {
+    let id = s.number::<u32>()?;
+    s.literal(": ")?;
+    if s.eat("\"") {
+        s.word("a letter", |c| c.is_ascii_alphabetic())?;
+        s.literal("\"")?;
+    } else {
+        loop {
+            let column = s.column();
+            let reference = s.number::<u32>()?;
+            references.push((reference, *s.line(), column));
+            if !s.eat(" | ") && !s.eat(" ") {
+                break;
+            }
+        }
+    }
+    s.end()?;
+    Ok(id)
+}
+
+fn check_rules(rules: &[Line]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut defined = HashMap::new();
+    let mut references = Vec::new();
+
+    for line in rules {
+        match check_rule(&mut line.scanner(), &mut references) {
+            Ok(id) => {
+                if let Some(first) = defined.insert(id, line.number) {
+                    problems.push(line.problem(
+                        1,
+                        format!("rule {} is already defined in line {}", id, first),
+                        "remove one of the definitions",
+                    ));
+                }
+            }
+            Err(problem) => problems.push(problem),
+        }
+    }
+    for (id, line, column) in references {
+        if !defined.contains_key(&id) {
+            problems.push(line.problem(
+                column,
+                format!("rule {} is not defined", id),
+                format!("add a line \"{}: ...\" defining it", id),
+            ));
+        }
+    }
+    if !defined.contains_key(&0) {
+        problems.push(check::missing(
+            rules,
+            "rule 0 is not defined",
+            "add the rule messages have to match, like \"0: 1 2\"",
+        ));
+    }
+    problems.sort_by_key(|p| (p.line, p.column));
+    problems
+}
+
+impl Checker for Day19Checker {
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let groups = check::groups(input);
+        if groups.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = check_rules(&groups[0]);
+        match groups.get(1) {
+            Some(messages) => problems.extend(check::check_each(messages, |s| {
+                s.word("a message", |c| c.is_ascii_alphabetic()).map(|_| ())
+            })),
+            None => problems.push(check::missing(
+                &groups[0],
+                "missing messages",
+                "add a blank line followed by the messages",
+            )),
+        }
+        for group in groups.iter().skip(2) {
+            problems.extend(check::unexpected_lines(group, "the messages"));
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input = "0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n1: \"b\"\n3 \"b\"\n\nabab\naba b\n";
+        let problems = Day19Checker.check(input);
+        let messages = problems
+            .iter()
+            .map(|p| (p.line, p.column, p.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                (3, 6, "rule 3 is not defined"),
+                (3, 10, "rule 3 is not defined"),
+                (4, 1, "rule 1 is already defined in line 2"),
+                (5, 2, "expected \": \", found \"\\\"b\\\"\""),
+                (8, 4, "unexpected \" b\" at end of line"),
+            ]
+        );
+    }
+}
@@ -1,6 +1,8 @@
-#[macro_use]
-extern crate itertools;
+pub mod check;
+pub mod generate;
 
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use itertools::iproduct;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
@@ -8,6 +10,7 @@ use std::iter::FromIterator;
 trait Rule {
     /// Return all strings that can be produced by that rule.
     /// May not terminate if rules contain recursions.
+    #[allow(dead_code)]
     fn valid_strings(&self, set: &RuleSet) -> Vec<String>;
 
     /// Tries to consume the part of the string that adheres to the rule.
@@ -34,7 +37,7 @@ impl Rule for LiteralRule {
         if s[..self.literal.len()] == self.literal {
             return vec![String::from(&s[self.literal.len()..])];
         }
-        return Vec::new();
+        Vec::new()
     }
 }
 
@@ -66,7 +69,7 @@ impl Rule for ComplexRule {
     fn try_consume(&self, s: &str, set: &RuleSet) -> Vec<String> {
         let mut ret = Vec::new();
 
-        if s == "" {
+        if s.is_empty() {
             return ret;
         }
 
@@ -87,12 +90,12 @@ impl Rule for ComplexRule {
     }
 }
 
-struct RuleSet {
+pub struct RuleSet {
     rules: HashMap<i32, Box<dyn Rule>>,
 }
 
 impl RuleSet {
-    fn from_vec(lines: Vec<&str>) -> RuleSet {
+    pub fn from_vec(lines: Vec<&str>) -> RuleSet {
         let mut rules = HashMap::new();
 
         'outer: for line in lines {
@@ -101,11 +104,11 @@ impl RuleSet {
             let rules_iter = token_iter.next().unwrap().split('|').map(|s| s.trim());
             let mut subrule_ids = Vec::<Vec<i32>>::new();
             for subrule in rules_iter {
-                if subrule.chars().next().unwrap() == '"' {
+                if subrule.starts_with('\"') {
                     rules.insert(
                         rule_id,
                         Box::new(LiteralRule {
-                            literal: String::from(subrule.split("\"").skip(1).next().unwrap()),
+                            literal: String::from(subrule.split("\"").nth(1).unwrap()),
                         }) as Box<dyn Rule>,
                     );
                     continue 'outer;
@@ -131,23 +134,24 @@ impl RuleSet {
         RuleSet { rules }
     }
 
+    #[allow(dead_code)]
     fn valid_strings(&self, rule_id: i32) -> Vec<String> {
-        self.rules.get(&rule_id).unwrap().valid_strings(&self)
+        self.rules.get(&rule_id).unwrap().valid_strings(self)
     }
 
-    fn does_match(&self, s: &str, rule_id: i32) -> bool {
+    pub fn does_match(&self, s: &str, rule_id: i32) -> bool {
         let ret = self.rules.get(&rule_id).unwrap().try_consume(s, self);
-        ret.iter().any(|x| x == "")
+        ret.iter().any(|x| x.is_empty())
     }
 }
 
-fn count_valid_words_exhaustive(filename: &str, rule_to_match: i32) -> i32 {
-    let groups = common::read_grouped_file(filename);
+#[allow(dead_code)]
+fn count_valid_words_exhaustive(groups: &[String], rule_to_match: i32) -> i32 {
     let rules = RuleSet::from_vec(groups[0].split("\n").collect::<Vec<&str>>());
     let valid_strings = rules.valid_strings(rule_to_match);
     let candidate_strings = groups[1]
         .split("\n")
-        .map(|s| String::from(s))
+        .map(String::from)
         .collect::<Vec<String>>();
 
     let valid_strings = HashSet::<String>::from_iter(valid_strings.iter().cloned());
@@ -160,8 +164,8 @@ fn count_valid_words_exhaustive(filename: &str, rule_to_match: i32) -> i32 {
         .len() as i32
 }
 
-fn count_valid_words(filename: &str, rule_to_match: i32) -> i32 {
-    let groups = common::read_grouped_file(filename);
+/// Number of messages matching rule `rule_to_match`.
+pub fn count_valid_words(groups: &[String], rule_to_match: i32) -> i32 {
     let rules = RuleSet::from_vec(groups[0].split("\n").collect::<Vec<&str>>());
     groups[1].split("\n").fold(0, |acc, s| {
         if rules.does_match(s, rule_to_match) {
@@ -171,19 +175,29 @@ fn count_valid_words(filename: &str, rule_to_match: i32) -> i32 {
     })
 }
 
-fn main() {
-    println!(
-        "Solution 1 (exhaustive): {}",
-        count_valid_words_exhaustive("src/day19/input.txt", 0)
-    );
-    println!(
-        "Solution 1: {}",
-        count_valid_words("src/day19/input.txt", 0)
-    );
-    println!(
-        "Solution 2: {}",
-        count_valid_words("src/day19/input_recursive.txt", 0)
-    );
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_grouped_file(input)
+    }
+
+    fn part1(&self, groups: &Vec<String>) -> Result<Answer, Error> {
+        Ok(count_valid_words(groups, 0).into())
+    }
+
+    fn part2(&self, groups: &Vec<String>) -> Result<Answer, Error> {
+        // Later rules replace earlier ones with the same id
+        let mut groups = groups.clone();
+        groups[0].push_str("\n8: 42 | 42 8\n11: 42 31 | 42 11 31");
+        Ok(count_valid_words(&groups, 0).into())
+    }
 }
 
 #[cfg(test)]
@@ -192,22 +206,25 @@ mod test {
 
     #[test]
     fn test_count_valid_words_exhaustive() {
-        assert_eq!(
-            count_valid_words_exhaustive("src/day19/input_test.txt", 0),
-            2
-        );
+        let groups = common::read_grouped_file("src/day19/input_test.txt");
+        assert_eq!(count_valid_words_exhaustive(&groups, 0), 2);
     }
 
     #[test]
     fn test_count_valid_words() {
-        assert_eq!(count_valid_words("src/day19/input_test.txt", 0), 2);
+        let groups = common::read_grouped_file("src/day19/input_test.txt");
+        assert_eq!(count_valid_words(&groups, 0), 2);
     }
 
     #[test]
     fn test_count_valid_words_recursive() {
-        assert_eq!(
-            count_valid_words("src/day19/input_test_recursive.txt", 0),
-            12
-        );
+        let groups = common::read_grouped_file("src/day19/input_test_recursive.txt");
+        assert_eq!(count_valid_words(&groups, 0), 12);
+    }
+
+    #[test]
+    fn test_part2_replaces_rules() {
+        let groups = common::read_grouped_file("src/day19/input_test_recursive.txt");
+        assert_eq!(Day19.part2(&groups), Ok(Answer::from(12)));
     }
 }
@@ -0,0 +1,179 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{BTreeSet, HashMap};
+
+/// Length of the words matched by rules 42 and 31.
+const WORD: usize = 5;
+
+/// Message rules in the shape of the real inputs: rule 0 is `8 11`, rule 8 is `42` and rule 11
+/// is `42 31`. Rules 42 and 31 match a random partition of all words of five letters, spelled out
+/// as a trie of rules. A message therefore matches if it splits into words of rule 42 followed by
+/// words of rule 31, which gives the answers for the original and the looping rules. The size is
+/// the number of messages.
+pub struct Day19Generator;
+
+fn letters(rng: &mut StdRng, len: usize) -> String {
+    (0..len)
+        .map(|_| if rng.gen_bool(0.5) { 'a' } else { 'b' })
+        .collect()
+}
+
+struct Rules {
+    ids: Vec<u32>,
+    lines: Vec<String>,
+    known: HashMap<BTreeSet<String>, u32>,
+    letters: [u32; 2],
+}
+
+impl Rules {
+    fn new(rng: &mut StdRng) -> Rules {
+        let mut ids = (1..200)
+            .filter(|id| ![8, 11, 31, 42].contains(id))
+            .collect::<Vec<u32>>();
+        ids.shuffle(rng);
+        let letters = [ids.pop().unwrap(), ids.pop().unwrap()];
+        Rules {
+            ids,
+            lines: vec![
+                String::from("0: 8 11"),
+                String::from("8: 42"),
+                String::from("11: 42 31"),
+                format!("{}: \"a\"", letters[0]),
+                format!("{}: \"b\"", letters[1]),
+            ],
+            known: HashMap::new(),
+            letters,
+        }
+    }
+
+    /// Alternatives of a rule matching exactly the words in `words`, which share their length.
+    fn alternatives(&mut self, words: &BTreeSet<String>) -> String {
+        let mut alternatives = Vec::new();
+        for (i, letter) in ['a', 'b'].iter().enumerate() {
+            let rest = words
+                .iter()
+                .filter_map(|w| w.strip_prefix(*letter).map(String::from))
+                .collect::<BTreeSet<String>>();
+            if rest.is_empty() {
+                continue;
+            }
+            if rest.iter().all(|w| w.is_empty()) {
+                alternatives.push(self.letters[i].to_string());
+            } else {
+                let rule = self.rule(rest);
+                alternatives.push(format!("{} {}", self.letters[i], rule));
+            }
+        }
+        alternatives.join(" | ")
+    }
+
+    /// Id of a rule matching exactly the words in `words`, added unless there already is one.
+    fn rule(&mut self, words: BTreeSet<String>) -> u32 {
+        if let Some(&id) = self.known.get(&words) {
+            return id;
+        }
+        let id = self.ids.pop().unwrap();
+        let line = format!("{}: {}", id, self.alternatives(&words));
+        self.lines.push(line);
+        self.known.insert(words, id);
+        id
+    }
+
+    fn add(&mut self, id: u32, words: &BTreeSet<String>) {
+        let line = format!("{}: {}", id, self.alternatives(words));
+        self.lines.push(line);
+    }
+}
+
+impl Generator for Day19Generator {
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn default_size(&self) -> usize {
+        400
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let words = (0..1 << WORD)
+            .map(|w: u32| {
+                (0..WORD)
+                    .map(|i| if w & 1 << i != 0 { 'b' } else { 'a' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        let (first, last) = loop {
+            let (first, last): (Vec<String>, Vec<String>) =
+                words.iter().cloned().partition(|_| rng.gen_bool(0.5));
+            if !first.is_empty() && !last.is_empty() {
+                break (first, last);
+            }
+        };
+
+        let mut rules = Rules::new(rng);
+        rules.add(42, &first.iter().cloned().collect());
+        rules.add(31, &last.iter().cloned().collect());
+        rules.lines.shuffle(rng);
+
+        let mut messages = Vec::with_capacity(size);
+        let mut matching = 0;
+        let mut matching_loops = 0;
+        for _ in 0..size {
+            let heads = rng.gen_range(1..=5);
+            let tails = rng.gen_range(1..=3);
+            let mut message = String::new();
+            for _ in 0..heads {
+                message.push_str(first.choose(rng).unwrap());
+            }
+            for _ in 0..tails {
+                message.push_str(last.choose(rng).unwrap());
+            }
+            match rng.gen_range(0..5) {
+                0 => {
+                    let len = rng.gen_range(1..WORD);
+                    message.push_str(&letters(rng, len));
+                }
+                1 => message = message.chars().rev().collect(),
+                2 => {
+                    let len = WORD * rng.gen_range(2..=6);
+                    message = letters(rng, len);
+                }
+                _ => {}
+            }
+
+            let chunks = message
+                .as_bytes()
+                .chunks(WORD)
+                .map(|c| std::str::from_utf8(c).unwrap())
+                .collect::<Vec<&str>>();
+            if message.len().is_multiple_of(WORD) {
+                let heads = chunks
+                    .iter()
+                    .take_while(|c| first.iter().any(|w| w == *c))
+                    .count();
+                let tails = chunks[heads..]
+                    .iter()
+                    .filter(|c| last.iter().any(|w| w == *c))
+                    .count();
+                if heads + tails == chunks.len() && tails >= 1 && heads > tails {
+                    matching_loops += 1;
+                    if heads == 2 && tails == 1 {
+                        matching += 1;
+                    }
+                }
+            }
+            messages.push(message);
+        }
+
+        Generated::new(
+            format!(
+                "{}\n\n{}",
+                rules.lines.join("\n"),
+                generate::lines(messages)
+            ),
+            Some(matching.into()),
+            Some(matching_loops.into()),
+        )
+    }
+}
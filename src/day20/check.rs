@@ -0,0 +1,101 @@
+use common::check::{self, Checker, Line, Problem};
+use std::collections::HashMap;
+
+/// Camera tiles: a `Tile <id>:` header followed by a square of `.` and `#`, all tiles of the
+/// same size and separated by blank lines.
+pub struct Day20Checker;
+
+fn check_header(header: &Line) -> Result<u64, Problem> {
+    let mut s = header.scanner();
+    s.literal("Tile ")?;
+    let id = s.number::<u64>()?;
+    s.literal(":")?;
+    s.end()?;
+    Ok(id)
+}
+
+impl Checker for Day20Checker {
+    fn day(&self) -> u32 {
+        20
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let groups = check::groups(input);
+        if groups.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = Vec::new();
+        let mut ids = HashMap::new();
+        let size = groups[0].get(1).map(|row| row.len());
+
+        for group in &groups {
+            let header = &group[0];
+            match check_header(header) {
+                Ok(id) => {
+                    if let Some(first) = ids.insert(id, header.number) {
+                        problems.push(header.problem(
+                            6,
+                            format!("tile {} is already defined in line {}", id, first),
+                            "give every tile a unique id",
+                        ));
+                    }
+                }
+                Err(problem) => problems.push(problem),
+            }
+
+            let rows = &group[1..];
+            let grid = check::check_grid(rows, ".#");
+            if !grid.is_empty() {
+                problems.extend(grid);
+                continue;
+            }
+            let width = rows.first().map_or(0, |row| row.len());
+            if width != rows.len() {
+                problems.push(header.problem(
+                    1,
+                    format!("tile is {} wide but {} high", width, rows.len()),
+                    "make the tile square",
+                ));
+            } else if Some(width) != size {
+                problems.push(header.problem(
+                    1,
+                    format!(
+                        "tile is {} wide, the first one {}",
+                        width,
+                        size.unwrap_or_default()
+                    ),
+                    "make all tiles the same size",
+                ));
+            }
+        }
+        problems.sort_by_key(|p| (p.line, p.column));
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input = "Tile 1:\n#.\n.#\n\nTile 2\n..\n##\n\nTile 1:\n#.\n\nTile 3:\n#x\n..\n\n\
+                     Tile 4:\n#..\n...\n..#\n";
+        let problems = Day20Checker.check(input);
+        let messages = problems
+            .iter()
+            .map(|p| (p.line, p.column, p.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                (5, 7, "expected \":\", found end of line"),
+                (9, 1, "tile is 2 wide but 1 high"),
+                (9, 6, "tile 1 is already defined in line 1"),
+                (13, 2, "unexpected character 'x' in grid"),
+                (16, 1, "tile is 3 wide, the first one 2"),
+            ]
+        );
+    }
+}
@@ -1,3 +1,7 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, Grid, InputSource, ParseError, Solution};
 use nalgebra::{Point2, Vector2};
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
@@ -20,7 +24,7 @@ impl Orientation {
         }
     }
 
-    fn to_vec(&self) -> Vector2<i32> {
+    fn to_vec(self) -> Vector2<i32> {
         match self {
             Orientation::North => Vector2::new(0, -1),
             Orientation::South => Vector2::new(0, 1),
@@ -31,49 +35,60 @@ impl Orientation {
 }
 
 #[derive(Clone)]
-struct Tile {
+pub struct Tile {
     id: i32,
-    size: Point2<i32>,
-    data: Vec<char>,
+    grid: Grid<char>,
 }
 
 impl Tile {
-    fn from_string(s: &str) -> Tile {
-        let lines = s.split("\n").map(|line| line.trim()).collect::<Vec<&str>>();
-        let id = lines[0][5..9].parse::<i32>().unwrap();
-        let width = lines[1].len() as i32;
-        let height = (lines.len() - 1) as i32;
-        Tile {
-            id,
-            size: Point2::new(width, height),
-            data: lines[1..]
-                .iter()
-                .flat_map(|line| line.chars())
-                .collect::<Vec<char>>(),
-        }
+    pub fn from_string(s: &str) -> Result<Tile, String> {
+        let lines = s.split('\n').map(|line| line.trim()).collect::<Vec<&str>>();
+        let id = lines[0]
+            .strip_prefix("Tile ")
+            .and_then(|header| header.strip_suffix(':'))
+            .ok_or_else(|| format!("expected \"Tile <id>:\", got {:?}", lines[0]))?;
+        Ok(Tile {
+            id: id
+                .parse::<i32>()
+                .map_err(|e| format!("invalid tile id {:?}: {}", id, e))?,
+            grid: Grid::from_lines(&lines[1..], |c| match c {
+                '.' | '#' => Ok(c),
+                _ => Err(format!("unknown pixel {:?}", c)),
+            })?,
+        })
     }
 
-    fn count(&self, c: char) -> u32 {
-        self.data
-            .iter()
-            .fold(0, |acc, x| if *x == c { acc + 1 } else { acc })
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    fn width(&self) -> i32 {
+        self.grid.width()
+    }
+
+    fn height(&self) -> i32 {
+        self.grid.height()
     }
 
-    fn match_pattern(&mut self, pattern: &Tile) -> bool {
+    fn match_pattern(&mut self, pattern: &Grid<char>) -> bool {
         let x_min = 0;
         let y_min = 0;
-        let x_max = self.size[0] - pattern.size[0];
-        let y_max = self.size[1] - pattern.size[1];
+        let x_max = self.width() - pattern.width();
+        let y_max = self.height() - pattern.height();
 
         let mut any_matched = false;
 
-        for y in y_min..y_max {
-            for x in x_min..x_max {
+        for y in y_min..=y_max {
+            for x in x_min..=x_max {
                 let mut found = true;
-                'outer: for yp in 0..pattern.size[1] {
-                    for xp in 0..pattern.size[0] {
-                        let to_match = pattern.at(Point2::new(xp, yp));
-                        let actual = self.at(Point2::new(x + xp, y + yp));
+                'outer: for yp in 0..pattern.height() {
+                    for xp in 0..pattern.width() {
+                        let to_match = pattern[Point2::new(xp, yp)];
+                        let actual = self.grid[Point2::new(x + xp, y + yp)];
                         if to_match != ' ' && actual != to_match {
                             found = false;
                             break 'outer;
@@ -82,11 +97,10 @@ impl Tile {
                 }
                 if found {
                     any_matched = true;
-                    for yp in 0..pattern.size[1] {
-                        for xp in 0..pattern.size[0] {
-                            let to_match = pattern.at(Point2::new(xp, yp));
-                            if to_match != ' ' {
-                                self.data[((y + yp) * self.size[1] + (x + xp)) as usize] = 'O';
+                    for yp in 0..pattern.height() {
+                        for xp in 0..pattern.width() {
+                            if pattern[Point2::new(xp, yp)] != ' ' {
+                                self.grid[Point2::new(x + xp, y + yp)] = 'O';
                             }
                         }
                     }
@@ -96,95 +110,47 @@ impl Tile {
         any_matched
     }
 
-    fn roate_and_flip_while_trying_to_match_pattern(&mut self, pattern: &Tile) {
+    /// Rotates and flips the tile until `pattern` occurs in it and marks all occurrences with
+    /// `O`. Spaces in the pattern match any pixel.
+    pub fn orient_to_pattern(&mut self, pattern: &Grid<char>) -> Result<(), Error> {
         for _ in 0..5 {
             if self.match_pattern(pattern) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        self.flip_x();
+        self.grid.flip_x();
         for _ in 0..5 {
             if self.match_pattern(pattern) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        self.flip_y();
+        self.grid.flip_y();
         for _ in 0..5 {
             if self.match_pattern(pattern) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        self.flip_x();
+        self.grid.flip_x();
         for _ in 0..5 {
             if self.match_pattern(pattern) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        panic!("could not match pattern");
-    }
-
-    fn is_inside(&self, p: Point2<i32>) -> bool {
-        p[0] >= 0 && p[0] < self.size[0] && p[1] >= 0 && p[1] < self.size[1]
-    }
-
-    fn at(&self, p: Point2<i32>) -> char {
-        self.data[(p[1] * self.size[0] + p[0]) as usize]
+        Err(Error::no_solution(
+            "could not match pattern in any orientation",
+        ))
     }
 
-    fn flip_x(&mut self) {
-        let mut ret = vec![' '; self.data.len()];
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                ret[(y * self.size[0] + x) as usize] =
-                    self.data[(y * self.size[0] + (self.size[0] - 1 - x)) as usize];
-            }
-        }
-        self.data = ret;
-    }
-
-    fn flip_y(&mut self) {
-        let mut ret = vec![' '; self.data.len()];
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                ret[(y * self.size[0] + x) as usize] =
-                    self.data[((self.size[1] - 1 - y) * self.size[0] + x) as usize];
-            }
-        }
-        self.data = ret;
-    }
-
-    fn rot_right(&mut self) {
-        let mut ret = vec![' '; self.data.len()];
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                ret[(y * self.size[0] + x) as usize] =
-                    self.data[((self.size[1] - 1 - x) * self.size[0] + y) as usize];
-            }
-        }
-        self.data = ret;
-    }
-
-    // fn rot_left(&mut self) {
-    //     let mut ret = vec![' '; self.data.len()];
-    //     for y in 0..self.size[1] {
-    //         for x in 0..self.size[0] {
-    //             ret[(y * self.size[0] + x) as usize] =
-    //                 self.data[(x * self.size[0] + (self.size[1] - 1 - y)) as usize];
-    //         }
-    //     }
-    //     self.data = ret;
-    // }
-
     fn border_id(&self, start: Point2<i32>, dir: Vector2<i32>) -> u32 {
         let mut ret = 0u32;
         let mut p = start;
-        while self.is_inside(p) {
+        while let Some(c) = self.grid.get(p) {
             ret <<= 1;
-            if self.at(p) == '#' {
+            if *c == '#' {
                 ret += 1;
             }
             p += dir;
@@ -194,8 +160,8 @@ impl Tile {
     }
 
     fn border_id_at(&self, o: Orientation) -> u32 {
-        let w = self.size[0] - 1;
-        let h = self.size[1] - 1;
+        let w = self.width() - 1;
+        let h = self.height() - 1;
         match o {
             Orientation::North => self.border_id(Point2::new(0, 0), Vector2::new(1, 0)),
             Orientation::East => self.border_id(Point2::new(w, 0), Vector2::new(0, 1)),
@@ -207,8 +173,8 @@ impl Tile {
     fn possible_borders(&self) -> HashSet<u32> {
         let mut ret = HashSet::new();
 
-        let w = self.size[0] - 1;
-        let h = self.size[1] - 1;
+        let w = self.width() - 1;
+        let h = self.height() - 1;
 
         // Clockwise
         ret.insert(self.border_id(Point2::new(0, 0), Vector2::new(1, 0)));
@@ -238,7 +204,7 @@ impl Tile {
         &mut self,
         desired_orientation: Vec<Orientation>,
         matching_candidates: &HashSet<u32>,
-    ) {
+    ) -> Result<(), Error> {
         let test = |t: &Tile| {
             desired_orientation
                 .iter()
@@ -253,59 +219,52 @@ impl Tile {
 
         for _ in 0..5 {
             if test(self) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        self.flip_x();
+        self.grid.flip_x();
         for _ in 0..5 {
             if test(self) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        self.flip_y();
+        self.grid.flip_y();
         for _ in 0..5 {
             if test(self) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        self.flip_x();
+        self.grid.flip_x();
         for _ in 0..5 {
             if test(self) {
-                return;
+                return Ok(());
             }
-            self.rot_right();
+            self.grid.rotate_right();
         }
-        panic!("could not find matching rotation");
+        Err(Error::no_solution(format!(
+            "could not find matching rotation for tile {}",
+            self.id
+        )))
     }
 
     #[allow(dead_code)]
     fn display(&self) {
         println!("Tile: {}", self.id);
-        for y in 0..self.size[1] {
-            for x in 0..self.size[0] {
-                print!("{}", self.at(Point2::new(x, y)));
-            }
-            print!("\n");
-        }
+        println!("{}", self.grid);
     }
 }
 
-struct Image {
+pub struct Image {
     tiles: HashMap<i32, Tile>,
 }
 
 impl Image {
-    fn from_file(filename: &str) -> Image {
-        let groups = common::read_grouped_file(filename);
-        let tiles = groups
-            .iter()
-            .map(|s| Tile::from_string(s))
-            .collect::<Vec<Tile>>();
+    pub fn from_tiles(tiles: Vec<Tile>) -> Image {
         Image {
-            tiles: HashMap::from_iter(tiles.iter().map(|t| (t.id, t.clone()))),
+            tiles: HashMap::from_iter(tiles.into_iter().map(|t| (t.id, t))),
         }
     }
 
@@ -329,7 +288,8 @@ impl Image {
         }
     }
 
-    fn egde_border_ids(&self) -> Vec<i32> {
+    /// Ids of the tiles whose borders match exactly two other tiles.
+    pub fn corner_ids(&self) -> Vec<i32> {
         let mut ret = Vec::new();
         for (k, v) in &self.tiles {
             let mut match_cnt = 0;
@@ -350,22 +310,24 @@ impl Image {
         ret
     }
 
-    fn build_unified(&self) -> Tile {
+    /// Arranges all tiles and joins them, without their borders, into a single tile.
+    pub fn build_unified(&self) -> Result<Tile, Error> {
         let mut candidates = self.tiles.clone();
         // Find top left corner
-        let mut corner_tile = candidates
-            .get(self.egde_border_ids().first().unwrap())
-            .unwrap()
-            .clone();
+        let corner_id = *self
+            .corner_ids()
+            .first()
+            .ok_or_else(|| Error::no_solution("no corner tile found"))?;
+        let mut corner_tile = candidates.get(&corner_id).unwrap().clone();
         candidates.remove(&corner_tile.id);
         let possible_matches = candidates
-            .iter()
-            .flat_map(|(_, t)| t.possible_borders())
+            .values()
+            .flat_map(|t| t.possible_borders())
             .collect::<HashSet<u32>>();
         corner_tile.rotate_and_flip_until_it_matches(
             vec![Orientation::East, Orientation::South],
             &possible_matches,
-        );
+        )?;
 
         let mut finished_img = HashMap::<Point2<i32>, Tile>::new();
         finished_img.insert(Point2::new(0, 0), corner_tile.clone());
@@ -388,7 +350,7 @@ impl Image {
         let mut max_y = 0;
 
         while !open_borders.is_empty() {
-            let to_match = open_borders.first().unwrap().clone();
+            let to_match = *open_borders.first().unwrap();
             let mut matched_key: Option<i32> = None;
             //println!("Trying to match ({}, {}) -> {:?} - {}:", to_match.0[0], to_match.0[1], to_match.1, to_match.2);
             for (k, v) in &candidates {
@@ -398,8 +360,8 @@ impl Image {
                     let mut new_tile = v.clone();
                     new_tile.rotate_and_flip_until_it_matches(
                         vec![to_match.1.opposite()],
-                        &HashSet::from_iter(vec![to_match.2].iter().cloned()),
-                    );
+                        &HashSet::from_iter([to_match.2].iter().cloned()),
+                    )?;
                     matched_key = Some(*k);
                     let current_pos = to_match.0 + to_match.1.to_vec();
                     max_x = std::cmp::max(max_x, current_pos[0]);
@@ -443,8 +405,8 @@ impl Image {
             }
         }
 
-        let w = corner_tile.size[0] - 2;
-        let h = corner_tile.size[1] - 2;
+        let w = corner_tile.width() - 2;
+        let h = corner_tile.height() - 2;
         let total_w = (max_x + 1) * w;
         let total_h = (max_y + 1) * h;
 
@@ -457,43 +419,90 @@ impl Image {
                 let local_x = (x) % w + 1;
                 let local_y = (y) % h + 1;
                 //println!("({}, {}) -> ({}, {}), ({}, {})", x, y, block_x, block_y, local_x, local_y);
-                data.push(
-                    finished_img
-                        .get(&Point2::new(block_x, block_y))
-                        .unwrap()
-                        .at(Point2::new(local_x, local_y)),
-                );
+                let tile = finished_img
+                    .get(&Point2::new(block_x, block_y))
+                    .ok_or_else(|| {
+                        Error::no_solution(format!(
+                            "no tile fits at position ({}, {})",
+                            block_x, block_y
+                        ))
+                    })?;
+                data.push(tile.grid[Point2::new(local_x, local_y)]);
             }
         }
 
-        Tile {
+        Ok(Tile {
             id: 0,
-            size: Point2::new(total_w, total_h),
-            data,
-        }
+            grid: Grid::new(total_w, total_h, data),
+        })
     }
 }
 
-fn main() {
-    let img = Image::from_file("src/day20/input.txt");
-
-    let border_ids = img.egde_border_ids();
-    println!(
-        "Solution 1: {:?} -> {}",
-        border_ids,
-        border_ids.iter().fold(1u128, |acc, x| acc * (*x as u128))
-    );
-
-    let monster_pattern = Tile {
-        id: 0,
-        size: Point2::new(20, 3),
-        data: "                  # #    ##    ##    ### #  #  #  #  #  #   "
+pub fn monster_pattern() -> Grid<char> {
+    Grid::new(
+        20,
+        3,
+        "                  # #    ##    ##    ### #  #  #  #  #  #   "
             .chars()
             .collect::<Vec<char>>(),
-    };
+    )
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Image;
+
+    fn day(&self) -> u32 {
+        20
+    }
 
-    let mut t = img.build_unified();
-    t.roate_and_flip_while_trying_to_match_pattern(&monster_pattern);
-    t.display();
-    println!("Solution 2: {}", t.count('#'));
+    fn parse(&self, input: &InputSource) -> Result<Image, ParseError> {
+        common::try_parse_grouped_file(input, Tile::from_string).map(Image::from_tiles)
+    }
+
+    fn part1(&self, img: &Image) -> Result<Answer, Error> {
+        Ok(img
+            .corner_ids()
+            .iter()
+            .fold(1u128, |acc, x| acc * (*x as u128))
+            .into())
+    }
+
+    fn part2(&self, img: &Image) -> Result<Answer, Error> {
+        let mut t = img.build_unified()?;
+        t.orient_to_pattern(&monster_pattern())?;
+        Ok(t.grid.count(&'#').into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let img = Day20
+            .parse(&InputSource::from("src/day20/input_test.txt"))
+            .unwrap();
+        assert_eq!(Day20.part1(&img), Ok(Answer::from(20899048083289u64)));
+        assert_eq!(Day20.part2(&img), Ok(Answer::from(273)));
+    }
+
+    #[test]
+    fn test_tile_from_string() {
+        let tile = Tile::from_string("Tile 42:\n#.\n.#").unwrap();
+        assert_eq!(tile.id, 42);
+        assert_eq!(tile.grid.to_string(), "#.\n.#");
+        assert!(Tile::from_string("Tile x:\n#.\n.#").is_err());
+        assert!(Tile::from_string("Tile 42:\n#.\n.").is_err());
+    }
+
+    #[test]
+    fn test_match_pattern_at_edge() {
+        let pattern = Grid::new(2, 2, " ###".chars().collect::<Vec<char>>());
+        let mut tile = Tile::from_string("Tile 1:\n...\n..#\n.##").unwrap();
+        assert!(tile.match_pattern(&pattern));
+        assert_eq!(tile.grid.to_string(), "...\n..O\n.OO");
+    }
 }
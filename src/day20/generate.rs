@@ -0,0 +1,236 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+use std::collections::HashSet;
+
+const TILE: usize = 10;
+const INNER: usize = TILE - 2;
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+type Pixels = Vec<Vec<bool>>;
+type Offset = (i32, i32);
+
+/// Images cut into square tiles that are rotated, flipped and shuffled.
+///
+/// Sea monsters are hidden in a random image and it is regenerated if one appears by chance.
+/// Every border gets a pattern no other border has in either direction and that differs from its
+/// reverse, so tiles only fit together one way and the four corner tiles match exactly two others. The size is the number of tiles along a
+/// side, between 3 and 12.
+pub struct Day20Generator;
+
+fn monster_cells() -> Vec<(usize, usize)> {
+    MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect()
+}
+
+/// Cells of the sea monster in all eight orientations, moved to non-negative coordinates.
+fn monster_orientations() -> Vec<Vec<(usize, usize)>> {
+    let cells = monster_cells()
+        .iter()
+        .map(|&(x, y)| (x as i32, y as i32))
+        .collect::<Vec<Offset>>();
+    let transforms: [fn(Offset) -> Offset; 8] = [
+        |(x, y)| (x, y),
+        |(x, y)| (-y, x),
+        |(x, y)| (-x, -y),
+        |(x, y)| (y, -x),
+        |(x, y)| (-x, y),
+        |(x, y)| (y, x),
+        |(x, y)| (x, -y),
+        |(x, y)| (-y, -x),
+    ];
+    transforms
+        .iter()
+        .map(|t| {
+            let moved = cells.iter().map(|&c| t(c)).collect::<Vec<Offset>>();
+            let min_x = moved.iter().map(|c| c.0).min().unwrap();
+            let min_y = moved.iter().map(|c| c.1).min().unwrap();
+            moved
+                .iter()
+                .map(|&(x, y)| ((x - min_x) as usize, (y - min_y) as usize))
+                .collect()
+        })
+        .collect()
+}
+
+/// Number of sea monsters in any orientation.
+fn count_monsters(image: &Pixels) -> usize {
+    let side = image.len();
+    monster_orientations()
+        .iter()
+        .map(|cells| {
+            let width = cells.iter().map(|c| c.0).max().unwrap() + 1;
+            let height = cells.iter().map(|c| c.1).max().unwrap() + 1;
+            (0..=side - height)
+                .flat_map(|y| (0..=side - width).map(move |x| (x, y)))
+                .filter(|&(x, y)| cells.iter().all(|&(cx, cy)| image[y + cy][x + cx]))
+                .count()
+        })
+        .sum()
+}
+
+/// Random image with sea monsters that don't overlap, and the number of them.
+fn image_with_monsters(rng: &mut StdRng, side: usize) -> (Pixels, usize) {
+    let cells = monster_cells();
+    let (width, height) = (MONSTER[0].len(), MONSTER.len());
+    loop {
+        let mut image = (0..side)
+            .map(|_| (0..side).map(|_| rng.gen_bool(0.35)).collect())
+            .collect::<Pixels>();
+
+        let mut placed = Vec::<(usize, usize)>::new();
+        for _ in 0..side * side / 200 {
+            let x = rng.gen_range(0..=side - width);
+            let y = rng.gen_range(0..=side - height);
+            let overlaps = placed.iter().any(|&(px, py)| {
+                px < x + width && x < px + width && py < y + height && y < py + height
+            });
+            if !overlaps {
+                for &(cx, cy) in &cells {
+                    image[y + cy][x + cx] = true;
+                }
+                placed.push((x, y));
+            }
+        }
+
+        if count_monsters(&image) == placed.len() {
+            return (image, placed.len());
+        }
+    }
+}
+
+fn canonical(edge: &[bool]) -> Vec<bool> {
+    let reversed = edge.iter().rev().copied().collect::<Vec<bool>>();
+    std::cmp::min(edge.to_vec(), reversed)
+}
+
+/// Border of `TILE` pixels between the corners `a` and `b` whose pattern isn't used yet.
+/// Palindromes are avoided, a tile could be flipped along them and still fit.
+fn unique_border(rng: &mut StdRng, a: bool, b: bool, used: &mut HashSet<Vec<bool>>) -> Vec<bool> {
+    loop {
+        let mut edge = vec![a];
+        edge.extend((0..INNER).map(|_| rng.gen_bool(0.5)));
+        edge.push(b);
+        let palindrome = edge.iter().eq(edge.iter().rev());
+        if !palindrome && used.insert(canonical(&edge)) {
+            return edge;
+        }
+    }
+}
+
+fn rotate(tile: &Pixels) -> Pixels {
+    (0..TILE)
+        .map(|y| (0..TILE).map(|x| tile[TILE - 1 - x][y]).collect())
+        .collect()
+}
+
+impl Generator for Day20Generator {
+    fn day(&self) -> u32 {
+        20
+    }
+
+    fn default_size(&self) -> usize {
+        12
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let n = size.clamp(3, 12);
+        let (image, monsters) = image_with_monsters(rng, n * INNER);
+
+        let corners = (0..=n)
+            .map(|_| (0..=n).map(|_| rng.gen_bool(0.5)).collect())
+            .collect::<Pixels>();
+        let mut used = HashSet::new();
+        let horizontal = (0..=n)
+            .map(|r| {
+                (0..n)
+                    .map(|c| unique_border(rng, corners[r][c], corners[r][c + 1], &mut used))
+                    .collect()
+            })
+            .collect::<Vec<Pixels>>();
+        let vertical = (0..n)
+            .map(|r| {
+                (0..=n)
+                    .map(|c| unique_border(rng, corners[r][c], corners[r + 1][c], &mut used))
+                    .collect()
+            })
+            .collect::<Vec<Pixels>>();
+
+        let ids = index::sample(rng, 9000, n * n)
+            .iter()
+            .map(|i| i as u64 + 1000)
+            .collect::<Vec<u64>>();
+        let mut tiles = Vec::with_capacity(n * n);
+        for r in 0..n {
+            for c in 0..n {
+                let mut tile = vec![horizontal[r][c].clone()];
+                for y in 1..TILE - 1 {
+                    let mut row = vec![vertical[r][c][y]];
+                    row.extend_from_slice(&image[r * INNER + y - 1][c * INNER..(c + 1) * INNER]);
+                    row.push(vertical[r][c + 1][y]);
+                    tile.push(row);
+                }
+                tile.push(horizontal[r + 1][c].clone());
+
+                for _ in 0..rng.gen_range(0..4) {
+                    tile = rotate(&tile);
+                }
+                if rng.gen_bool(0.5) {
+                    tile.reverse();
+                }
+
+                let rows = tile
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|&p| if p { '#' } else { '.' })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<String>>();
+                tiles.push(format!("Tile {}:\n{}", ids[r * n + c], rows.join("\n")));
+            }
+        }
+        tiles.shuffle(rng);
+
+        let corner_ids = [0, n - 1, n * (n - 1), n * n - 1]
+            .iter()
+            .map(|&i| ids[i])
+            .product::<u64>();
+        let roughness =
+            image.iter().flatten().filter(|&&p| p).count() - monsters * monster_cells().len();
+
+        Generated::new(
+            tiles.join("\n\n") + "\n",
+            Some(corner_ids.into()),
+            Some(roughness.into()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_count_monsters() {
+        let mut image = vec![vec![false; 24]; 24];
+        assert_eq!(count_monsters(&image), 0);
+
+        for (x, y) in monster_cells() {
+            image[21 - y][4 + x] = true;
+        }
+        assert_eq!(count_monsters(&image), 1);
+    }
+}
@@ -0,0 +1,24 @@
+use common::check::{self, Checker, Problem};
+
+/// Foods: ingredients separated by spaces followed by `(contains <allergen>, ...)`.
+pub struct Day21Checker;
+
+impl Checker for Day21Checker {
+    fn day(&self) -> u32 {
+        21
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            loop {
+                s.word("an ingredient", |c| c.is_ascii_lowercase())?;
+                if !s.eat(" ") || s.peek() == Some('(') {
+                    break;
+                }
+            }
+            s.literal("(contains ")?;
+            s.list(", ", |s| s.word("an allergen", |c| c.is_ascii_lowercase()))?;
+            s.literal(")")
+        })
+    }
+}
@@ -1,11 +1,17 @@
+pub mod check;
+pub mod generate;
+
+use common::matching;
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use std::collections::{HashMap, HashSet};
 
-fn solve(filename: &str) -> (i32, String) {
+/// Occurrences of ingredients without allergens and the canonical dangerous ingredient list.
+pub fn solve(lines: &[String]) -> Result<(i32, String), Error> {
     let mut all_ingredients = HashSet::<String>::new();
     let mut all_allergenes = HashMap::<String, HashSet<String>>::new();
     let mut occurrences = HashMap::<String, i32>::new();
 
-    for line in common::read_file_linewise(filename) {
+    for line in lines {
         let tokens = line.split(" (").collect::<Vec<&str>>();
         let ingredients = tokens[0]
             .split(" ")
@@ -44,69 +50,69 @@ fn solve(filename: &str) -> (i32, String) {
         }
     }
 
-    loop {
-        let mut cleaned_allergenes = all_allergenes.clone();
-
-        for (k, v) in &all_allergenes {
-            if v.len() == 1 {
-                for (ko, vo) in cleaned_allergenes.iter_mut() {
-                    if k != ko {
-                        *vo = vo.difference(&v).cloned().collect();
-                    }
-                }
-            }
-        }
-
-        if cleaned_allergenes == all_allergenes {
-            break;
-        }
-
-        all_allergenes = cleaned_allergenes;
-    }
+    let dangerous = matching::assign_unique(&all_allergenes)?;
 
-    let known_ingredients = all_allergenes
-        .iter()
-        .fold(HashSet::<String>::new(), |acc, s| {
-            acc.union(s.1).cloned().collect()
-        });
+    let known_ingredients = dangerous.values().cloned().collect::<HashSet<String>>();
     let safe_ingredients = all_ingredients
         .difference(&known_ingredients)
         .cloned()
         .collect::<HashSet<String>>();
 
-    let mut canonical_list = all_allergenes.keys().cloned().collect::<Vec<String>>();
+    let mut canonical_list = dangerous.keys().cloned().collect::<Vec<String>>();
     canonical_list.sort();
 
-    let mut canoncial_list_translated = String::new();
-    let mut first = true;
-
-    for item in canonical_list {
-        if !first {
-            canoncial_list_translated.push(',');
-        } else {
-            first = false;
-        }
-        canoncial_list_translated.push_str(
-            &all_allergenes
-                .get(&item)
-                .unwrap()
-                .iter()
-                .next()
-                .unwrap()
-                .clone(),
-        );
-    }
+    let canonical_list_translated = canonical_list
+        .iter()
+        .map(|allergen| dangerous[allergen].clone())
+        .collect::<Vec<String>>()
+        .join(",");
 
-    (
+    Ok((
         safe_ingredients
             .iter()
             .fold(0, |acc, s| acc + occurrences.get(s).unwrap()),
-        canoncial_list_translated,
-    )
+        canonical_list_translated,
+    ))
 }
 
-fn main() {
-    let solution = solve("src/day21/input.txt");
-    println!("Solution 1: {}", solution.0);
-    println!("Solution 2: {}", solution.1);
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<String>;
+
+    fn day(&self) -> u32 {
+        21
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<String>, ParseError> {
+        common::try_read_file_linewise(input)
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<Answer, Error> {
+        Ok(solve(lines)?.0.into())
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<Answer, Error> {
+        Ok(solve(lines)?.1.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let lines = common::read_file_linewise("src/day21/input_test.txt");
+        assert_eq!(solve(&lines), Ok((5, String::from("mxmxvkd,sqjhc,fvjkl"))));
+    }
+
+    #[test]
+    fn test_ambiguous_allergens() {
+        let lines = vec![String::from("a b (contains x, y)")];
+        assert_eq!(
+            solve(&lines),
+            Err(Error::no_solution("assignment is ambiguous for x, y"))
+        );
+    }
 }
@@ -0,0 +1,120 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+const ALLERGENS: [&str; 9] = [
+    "dairy",
+    "eggs",
+    "fish",
+    "nuts",
+    "peanuts",
+    "sesame",
+    "shellfish",
+    "soy",
+    "wheat",
+];
+
+/// Lists of foods in which each allergen is contained in exactly one ingredient.
+///
+/// Every food lists at least one allergen and contains the ingredients of the allergens it lists,
+/// plus random others. For each allergen two foods list only it and share no other ingredient, so
+/// the ingredients of all foods listing an allergen have exactly its ingredient in common. The
+/// size is the number of foods, at least twice the number of allergens.
+pub struct Day21Generator;
+
+fn food(ingredients: &[String], allergens: &[&str]) -> String {
+    format!(
+        "{} (contains {})",
+        ingredients.join(" "),
+        allergens.join(", ")
+    )
+}
+
+impl Generator for Day21Generator {
+    fn day(&self) -> u32 {
+        21
+    }
+
+    fn default_size(&self) -> usize {
+        40
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let count = rng.gen_range(3..=ALLERGENS.len());
+        let allergens = ALLERGENS
+            .choose_multiple(rng, count)
+            .copied()
+            .collect::<Vec<&str>>();
+        let size = size.max(2 * allergens.len());
+
+        let mut names = generate::distinct_words(rng, 200 + allergens.len(), 6);
+        let dangerous = names.split_off(200);
+        let safe = names;
+        let contained_in = allergens
+            .iter()
+            .copied()
+            .zip(dangerous.iter().cloned())
+            .collect::<BTreeMap<&str, String>>();
+
+        let mut foods = Vec::with_capacity(size);
+        let mut safe_count = 0;
+        for (allergen, ingredient) in &contained_in {
+            let fillers = safe
+                .choose_multiple(rng, 20)
+                .cloned()
+                .collect::<Vec<String>>();
+            for half in fillers.chunks(10) {
+                let mut ingredients = half[..rng.gen_range(3..=10)].to_vec();
+                safe_count += ingredients.len();
+                ingredients.push(ingredient.clone());
+                ingredients.shuffle(rng);
+                foods.push(food(&ingredients, &[allergen]));
+            }
+        }
+
+        while foods.len() < size {
+            let count = rng.gen_range(1..=3);
+            let listed = allergens
+                .choose_multiple(rng, count)
+                .copied()
+                .collect::<Vec<&str>>();
+            let unlisted = dangerous
+                .iter()
+                .filter(|_| rng.gen_bool(0.3))
+                .cloned()
+                .collect::<Vec<String>>();
+            let count = rng.gen_range(5..=40);
+            let fillers = safe
+                .choose_multiple(rng, count)
+                .cloned()
+                .collect::<Vec<String>>();
+            safe_count += fillers.len();
+
+            let mut ingredients = fillers;
+            for ingredient in listed
+                .iter()
+                .map(|a| &contained_in[a])
+                .chain(unlisted.iter())
+            {
+                if !ingredients.contains(ingredient) {
+                    ingredients.push(ingredient.clone());
+                }
+            }
+            ingredients.shuffle(rng);
+            foods.push(food(&ingredients, &listed));
+        }
+        foods.shuffle(rng);
+
+        let canonical = contained_in
+            .values()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(",");
+        Generated::new(
+            generate::lines(foods),
+            Some(safe_count.into()),
+            Some(canonical.into()),
+        )
+    }
+}
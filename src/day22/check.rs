@@ -0,0 +1,49 @@
+use common::check::{self, Checker, Line, Problem};
+
+/// Decks of both players: `Player 1:` and `Player 2:` each followed by their cards, one per line.
+pub struct Day22Checker;
+
+fn check_deck(deck: &[Line], player: usize) -> Vec<Problem> {
+    let header = format!("Player {}:", player);
+    let mut problems = check::check_each(&deck[..1], |s| s.literal(&header));
+    if deck.len() < 2 {
+        problems.push(check::missing(
+            deck,
+            format!("deck of player {} is empty", player),
+            "list the cards of the player, one per line",
+        ));
+    }
+    problems.extend(check::check_each(&deck[1..], |s| {
+        s.number::<i32>().map(|_| ())
+    }));
+    problems
+}
+
+impl Checker for Day22Checker {
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let groups = check::groups(input);
+        if groups.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = Vec::new();
+        for (i, deck) in groups.iter().take(2).enumerate() {
+            problems.extend(check_deck(deck, i + 1));
+        }
+        if groups.len() < 2 {
+            problems.push(check::missing(
+                &groups[0],
+                "missing deck of player 2",
+                "add a blank line, \"Player 2:\" and the cards of player 2",
+            ));
+        }
+        for group in groups.iter().skip(2) {
+            problems.extend(check::unexpected_lines(group, "the deck of player 2"));
+        }
+        problems
+    }
+}
@@ -1,3 +1,7 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
@@ -9,12 +13,12 @@ fn deck_from_string(s: &str) -> VecDeque<i32> {
         .collect()
 }
 
-fn build_decks(filename: &str) -> (VecDeque<i32>, VecDeque<i32>) {
-    let groups = common::read_grouped_file(filename);
+pub fn build_decks(groups: &[String]) -> (VecDeque<i32>, VecDeque<i32>) {
     (deck_from_string(&groups[0]), deck_from_string(&groups[1]))
 }
 
-fn play_game(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>) -> (i32, VecDeque<i32>) {
+/// Plays a game of Combat, returns the number of the winner and their deck.
+pub fn play_game(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>) -> (i32, VecDeque<i32>) {
     while !deck1.is_empty() && !deck2.is_empty() {
         let c1 = deck1.pop_front().unwrap();
         let c2 = deck2.pop_front().unwrap();
@@ -35,7 +39,11 @@ fn play_game(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>) -> (i32, VecDeq
     }
 }
 
-fn play_recursive_game(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>) -> (i32, VecDeque<i32>) {
+/// Plays a game of Recursive Combat, returns the number of the winner and their deck.
+pub fn play_recursive_game(
+    mut deck1: VecDeque<i32>,
+    mut deck2: VecDeque<i32>,
+) -> (i32, VecDeque<i32>) {
     let mut seen_game_states = HashSet::<u64>::new();
 
     while !deck1.is_empty() && !deck2.is_empty() {
@@ -88,29 +96,35 @@ fn play_recursive_game(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>) -> (i
     }
 }
 
-fn score(deck: VecDeque<i32>) -> i32 {
+pub fn score(deck: &VecDeque<i32>) -> i32 {
     deck.iter()
         .rev()
         .enumerate()
         .fold(0, |acc, (i, x)| acc + ((i + 1) as i32) * x)
 }
 
-fn main() {
-    let decks = build_decks("src/day22/input.txt");
-
-    let result = play_game(decks.0.clone(), decks.1.clone());
-    println!(
-        "Player {} wins with score: {}",
-        result.0 + 1,
-        score(result.1)
-    );
-
-    let result = play_recursive_game(decks.0.clone(), decks.1.clone());
-    println!(
-        "Player {} wins recursive with score: {}",
-        result.0 + 1,
-        score(result.1)
-    );
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = (VecDeque<i32>, VecDeque<i32>);
+
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Self::Input, ParseError> {
+        common::try_read_grouped_file(input).map(|groups| build_decks(&groups))
+    }
+
+    fn part1(&self, decks: &Self::Input) -> Result<Answer, Error> {
+        let (_winner, deck) = play_game(decks.0.clone(), decks.1.clone());
+        Ok(score(&deck).into())
+    }
+
+    fn part2(&self, decks: &Self::Input) -> Result<Answer, Error> {
+        let (_winner, deck) = play_recursive_game(decks.0.clone(), decks.1.clone());
+        Ok(score(&deck).into())
+    }
 }
 
 #[cfg(test)]
@@ -119,49 +133,49 @@ mod test {
 
     #[test]
     fn test_normal_game() {
-        let decks = build_decks("src/day22/input_test.txt");
+        let decks = build_decks(&common::read_grouped_file("src/day22/input_test.txt"));
 
         let result = play_game(decks.0, decks.1);
         assert_eq!(
             result,
             (
                 1,
-                vec![3, 2, 10, 6, 8, 5, 9, 4, 7, 1]
+                [3, 2, 10, 6, 8, 5, 9, 4, 7, 1]
                     .iter()
                     .cloned()
                     .collect::<VecDeque<i32>>()
             )
         );
-        assert_eq!(score(result.1), 306);
+        assert_eq!(score(&result.1), 306);
     }
 
     #[test]
     fn test_recursive_game_state() {
-        let deck1 = vec![43, 19].iter().cloned().collect::<VecDeque<i32>>();
-        let deck2 = vec![2, 29, 14].iter().cloned().collect::<VecDeque<i32>>();
+        let deck1 = [43, 19].iter().cloned().collect::<VecDeque<i32>>();
+        let deck2 = [2, 29, 14].iter().cloned().collect::<VecDeque<i32>>();
 
         let result = play_recursive_game(deck1, deck2);
         assert_eq!(
             result,
-            (0, vec![43, 19].iter().cloned().collect::<VecDeque<i32>>())
+            (0, [43, 19].iter().cloned().collect::<VecDeque<i32>>())
         );
     }
 
     #[test]
     fn test_recursive_game() {
-        let decks = build_decks("src/day22/input_test.txt");
+        let decks = build_decks(&common::read_grouped_file("src/day22/input_test.txt"));
 
         let result = play_recursive_game(decks.0, decks.1);
         assert_eq!(
             result,
             (
                 1,
-                vec![7, 5, 6, 2, 4, 1, 10, 8, 9, 3]
+                [7, 5, 6, 2, 4, 1, 10, 8, 9, 3]
                     .iter()
                     .cloned()
                     .collect::<VecDeque<i32>>()
             )
         );
-        assert_eq!(score(result.1), 291);
+        assert_eq!(score(&result.1), 291);
     }
 }
@@ -0,0 +1,68 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use std::collections::{HashSet, VecDeque};
+
+/// Decks of Space Cards dealt from a shuffled stack. The answer of plain Combat comes from playing
+/// it, decks that would play forever are dealt again. Recursive Combat is left to the solvers.
+/// The size is the number of cards, an even number of at least 2.
+pub struct Day22Generator;
+
+/// Score of the winning deck of a game of Combat, `None` if the game never ends.
+fn play(mut deck1: VecDeque<i32>, mut deck2: VecDeque<i32>) -> Option<i32> {
+    let mut seen = HashSet::new();
+    while !deck1.is_empty() && !deck2.is_empty() {
+        if !seen.insert((deck1.clone(), deck2.clone())) {
+            return None;
+        }
+        let (c1, c2) = (deck1.pop_front().unwrap(), deck2.pop_front().unwrap());
+        if c1 > c2 {
+            deck1.extend([c1, c2].iter());
+        } else {
+            deck2.extend([c2, c1].iter());
+        }
+    }
+    let winner = if deck1.is_empty() { deck2 } else { deck1 };
+    Some(
+        winner
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, c)| (i as i32 + 1) * c)
+            .sum(),
+    )
+}
+
+fn deck(player: u32, cards: &[i32]) -> String {
+    let cards = cards.iter().map(|c| c.to_string()).collect::<Vec<String>>();
+    format!("Player {}:\n{}", player, cards.join("\n"))
+}
+
+impl Generator for Day22Generator {
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn default_size(&self) -> usize {
+        50
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let size = (size.max(2) / 2 * 2) as i32;
+        let mut cards = (1..=size).collect::<Vec<i32>>();
+        loop {
+            cards.shuffle(rng);
+            let (deck1, deck2) = cards.split_at(cards.len() / 2);
+            let score = play(
+                deck1.iter().copied().collect(),
+                deck2.iter().copied().collect(),
+            );
+            if let Some(score) = score {
+                return Generated::new(
+                    format!("{}\n\n{}\n", deck(1, deck1), deck(2, deck2)),
+                    Some(score.into()),
+                    None,
+                );
+            }
+        }
+    }
+}
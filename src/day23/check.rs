@@ -0,0 +1,43 @@
+use common::check::{self, Checker, Problem};
+use std::collections::HashSet;
+
+/// Cup labels: a single line with each of the digits 1 to n exactly once.
+pub struct Day23Checker;
+
+impl Checker for Day23Checker {
+    fn day(&self) -> u32 {
+        23
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let mut problems = check::check_each(&lines[..1], |s| {
+            let labels = s.word("cup labels", |c| c.is_ascii_digit())?;
+            let mut seen = HashSet::new();
+            for (i, c) in labels.chars().enumerate() {
+                let label = c.to_digit(10).unwrap_or_default() as usize;
+                if label == 0 || label > labels.len() {
+                    return Err(s.problem_at(
+                        i + 1,
+                        format!("label {} is out of range", label),
+                        format!("use each label from 1 to {} once", labels.len()),
+                    ));
+                }
+                if !seen.insert(label) {
+                    return Err(s.problem_at(
+                        i + 1,
+                        format!("label {} appears twice", label),
+                        format!("use each label from 1 to {} once", labels.len()),
+                    ));
+                }
+            }
+            Ok(())
+        });
+        problems.extend(check::unexpected_lines(&lines[1..], "the cup labels"));
+        problems
+    }
+}
@@ -1,13 +1,18 @@
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, Location, ParseError, Solution};
 use std::collections::HashMap;
 use std::fmt::Debug;
 
-struct Ring {
+/// Circle of cups stored as successor per label.
+pub struct Ring {
     connections: HashMap<i32, i32>,
     current: i32,
 }
 
 impl Ring {
-    fn new(values: &Vec<i32>) -> Ring {
+    pub fn new(values: &[i32]) -> Ring {
         Ring {
             connections: values
                 .iter()
@@ -22,7 +27,7 @@ impl Ring {
         *self.connections.get(&v).unwrap()
     }
 
-    fn make_move(&mut self) {
+    pub fn make_move(&mut self) {
         let start = self.next(self.current);
         let mid = self.next(start);
         let end = self.next(mid);
@@ -48,7 +53,8 @@ impl Ring {
         self.current = self.next(self.current);
     }
 
-    fn solutions_str(&self) -> String {
+    /// Labels after cup 1, going clockwise.
+    pub fn solutions_str(&self) -> String {
         let mut ret = String::new();
 
         let mut x = self.next(1);
@@ -74,19 +80,18 @@ impl Debug for Ring {
     }
 }
 
-fn solve_1(values: Vec<i32>, num_moves: i32) -> String {
-    let mut r = Ring::new(&values);
+pub fn solve_1(values: &[i32], num_moves: i32) -> String {
+    let mut r = Ring::new(values);
     for _ in 0..num_moves {
         r.make_move();
     }
     r.solutions_str()
 }
 
-fn solve_2(values: Vec<i32>) -> i128 {
+/// Product of the two labels after cup 1 after ten million moves with a million cups.
+pub fn solve_2(values: &[i32]) -> i128 {
     let mut v = (1..1_000_001).collect::<Vec<i32>>();
-    for i in 0..values.len() {
-        v[i] = values[i];
-    }
+    v[..values.len()].copy_from_slice(values);
 
     let mut r = Ring::new(&v);
     for _ in 0..10_000_000 {
@@ -99,10 +104,32 @@ fn solve_2(values: Vec<i32>) -> i128 {
     (a as i128) * b
 }
 
-fn main() {
-    println!(
-        "Solution 1: {}",
-        solve_1(vec![5, 6, 2, 8, 9, 3, 1, 4, 7], 100)
-    );
-    println!("Solution 2: {}", solve_2(vec![5, 6, 2, 8, 9, 3, 1, 4, 7]));
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<i32>;
+
+    fn day(&self) -> u32 {
+        23
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<i32>, ParseError> {
+        let lines = common::try_parse_file_linewise(input, |line| {
+            line.chars()
+                .map(|c| c.to_digit(10).map(|d| d as i32).ok_or("expected a digit"))
+                .collect::<Result<Vec<i32>, _>>()
+        })?;
+        lines
+            .into_iter()
+            .next()
+            .ok_or_else(|| ParseError::new(input, Location::Input, "", "input is empty"))
+    }
+
+    fn part1(&self, values: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(solve_1(values, 100).into())
+    }
+
+    fn part2(&self, values: &Vec<i32>) -> Result<Answer, Error> {
+        Ok(solve_2(values).into())
+    }
 }
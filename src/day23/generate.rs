@@ -0,0 +1,67 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+
+/// Arrangements of the nine cups labeled 1 to 9; inputs can't have other sizes. A hundred moves
+/// are played to find the labels after cup 1, the ten million moves of part 2 are left to the
+/// solvers.
+pub struct Day23Generator;
+
+fn labels_after_moves(cups: &[u32], moves: usize) -> String {
+    let mut cups = cups.to_vec();
+    let max = cups.len() as u32;
+    for _ in 0..moves {
+        let current = cups[0];
+        let picked = cups.drain(1..4).collect::<Vec<u32>>();
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 {
+                max
+            } else {
+                destination - 1
+            };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        let at = cups.iter().position(|&c| c == destination).unwrap() + 1;
+        cups.splice(at..at, picked);
+        cups.rotate_left(1);
+    }
+
+    let one = cups.iter().position(|&c| c == 1).unwrap();
+    cups.rotate_left(one);
+    cups[1..].iter().map(|c| c.to_string()).collect()
+}
+
+impl Generator for Day23Generator {
+    fn day(&self) -> u32 {
+        23
+    }
+
+    fn default_size(&self) -> usize {
+        9
+    }
+
+    fn generate(&self, rng: &mut StdRng, _size: usize) -> Generated {
+        let mut cups = (1..=9).collect::<Vec<u32>>();
+        cups.shuffle(rng);
+        let line = cups.iter().map(|c| c.to_string()).collect::<String>();
+        Generated::new(
+            line + "\n",
+            Some(labels_after_moves(&cups, 100).into()),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_labels_after_moves() {
+        let cups = [3, 8, 9, 1, 2, 5, 4, 6, 7];
+        assert_eq!(labels_after_moves(&cups, 10), "92658374");
+        assert_eq!(labels_after_moves(&cups, 100), "67384529");
+    }
+}
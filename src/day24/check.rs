@@ -0,0 +1,19 @@
+use common::check::{self, Checker, Problem};
+
+/// Tile paths: directions `e`, `se`, `sw`, `w`, `nw` and `ne` without separators.
+pub struct Day24Checker;
+
+impl Checker for Day24Checker {
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        check::check_lines(input, |s| {
+            while !s.at_end() {
+                s.one_of(&["e", "se", "sw", "w", "nw", "ne"])?;
+            }
+            Ok(())
+        })
+    }
+}
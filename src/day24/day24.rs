@@ -1,23 +1,12 @@
-use nalgebra::{Point3, Vector3};
-use std::collections::HashMap;
+pub mod check;
+pub mod generate;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum TileState {
-    Black,
-    White,
-}
-
-impl TileState {
-    fn flipped(&self) -> TileState {
-        match self {
-            TileState::Black => TileState::White,
-            TileState::White => TileState::Black,
-        }
-    }
-}
+use common::automaton::{Automaton, Hex, LifeRule, SparseAutomaton};
+use common::{Answer, Error, InputSource, ParseError, Solution};
+use std::collections::HashSet;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Direction {
+pub enum Direction {
     East,
     SouthEast,
     SouthWest,
@@ -27,168 +16,126 @@ enum Direction {
 }
 
 impl Direction {
-    fn offset(&self) -> Vector3<i32> {
+    fn offset(&self) -> [i32; 2] {
         match self {
-            Direction::East => Vector3::new(1, 1, 0),
-            Direction::SouthEast => Vector3::new(1, 0, -1),
-            Direction::SouthWest => Vector3::new(0, -1, -1),
-            Direction::West => Vector3::new(-1, -1, 0),
-            Direction::NorthWest => Vector3::new(-1, 0, 1),
-            Direction::NorthEast => Vector3::new(0, 1, 1),
+            Direction::East => Hex::EAST,
+            Direction::SouthEast => Hex::SOUTH_EAST,
+            Direction::SouthWest => Hex::SOUTH_WEST,
+            Direction::West => Hex::WEST,
+            Direction::NorthWest => Hex::NORTH_WEST,
+            Direction::NorthEast => Hex::NORTH_EAST,
         }
     }
 }
 
-fn path_from_string(mut s: &str) -> Vec<Direction> {
+pub fn path_from_string(mut s: &str) -> Result<Vec<Direction>, String> {
     let mut ret = Vec::new();
 
     while !s.is_empty() {
         let mut crs = s.chars();
         let c = crs.next().unwrap();
         match c {
-            x if x == 'e' => {
+            'e' => {
                 ret.push(Direction::East);
                 s = &s[1..];
             }
-            x if x == 'w' => {
+            'w' => {
                 ret.push(Direction::West);
                 s = &s[1..];
             }
-            x if x == 's' => {
-                let c2 = crs.next().unwrap();
-                match c2 {
-                    y if y == 'e' => {
-                        ret.push(Direction::SouthEast);
-                        s = &s[2..];
-                    }
-                    y if y == 'w' => {
-                        ret.push(Direction::SouthWest);
-                        s = &s[2..];
-                    }
-                    _ => panic!("unknown dir"),
+            's' => {
+                match crs.next() {
+                    Some('e') => ret.push(Direction::SouthEast),
+                    Some('w') => ret.push(Direction::SouthWest),
+                    _ => return Err(format!("unknown direction at {:?}", s)),
                 }
+                s = &s[2..];
             }
-            x if x == 'n' => {
-                let c2 = crs.next().unwrap();
-                match c2 {
-                    y if y == 'e' => {
-                        ret.push(Direction::NorthEast);
-                        s = &s[2..];
-                    }
-                    y if y == 'w' => {
-                        ret.push(Direction::NorthWest);
-                        s = &s[2..];
-                    }
-                    _ => panic!("unknown dir"),
+            'n' => {
+                match crs.next() {
+                    Some('e') => ret.push(Direction::NorthEast),
+                    Some('w') => ret.push(Direction::NorthWest),
+                    _ => return Err(format!("unknown direction at {:?}", s)),
                 }
+                s = &s[2..];
             }
-            _ => panic!("unknown dir"),
+            _ => return Err(format!("unknown direction at {:?}", s)),
         }
     }
-    ret
+    Ok(ret)
 }
 
-fn init_tiles(filename: &str) -> HashMap<Point3<i32>, TileState> {
-    let dirs = common::parse_file_linewise(filename, |line| path_from_string(line));
-    let mut tile_flips = HashMap::<Point3<i32>, TileState>::new();
-
-    for dir in &dirs {
-        let p = dir
-            .iter()
-            .fold(Point3::<i32>::new(0, 0, 0), |acc, d| acc + d.offset());
-        match tile_flips.get_mut(&p) {
-            Some(v) => {
-                *v = v.flipped();
-            }
-            None => {
-                tile_flips.insert(p, TileState::Black);
-            }
-        }
+/// Black tiles in axial hex coordinates.
+pub type Floor = HashSet<[i32; 2]>;
+
+fn flip_tile(black_tiles: &mut Floor, dir: &[Direction]) {
+    let p = dir.iter().fold([0, 0], |acc, d| {
+        let o = d.offset();
+        [acc[0] + o[0], acc[1] + o[1]]
+    });
+    if !black_tiles.remove(&p) {
+        black_tiles.insert(p);
     }
+}
+
+/// Flips a tile for every path while the input is being read, the paths themselves are not kept.
+pub fn init_tiles<I, E>(dirs: I) -> Result<Floor, E>
+where
+    I: IntoIterator<Item = Result<Vec<Direction>, E>>,
+{
+    let mut black_tiles = Floor::new();
 
-    tile_flips
+    for dir in dirs {
+        flip_tile(&mut black_tiles, &dir?);
+    }
+
+    Ok(black_tiles)
 }
 
-fn solve_1(filename: &str) -> i32 {
-    init_tiles(filename).iter().fold(0, |acc, (_, v)| {
-        acc + if *v == TileState::Black { 1 } else { 0 }
-    })
+/// Number of black tiles.
+pub fn solve_1(tiles: &Floor) -> usize {
+    tiles.len()
 }
 
-fn solve_2(filename: &str) -> i32 {
-    let mut tiles = init_tiles(filename);
-
-    for _ in 0..100 {
-        let mut min_x = 0;
-        let mut min_y = 0;
-        let mut min_z = 0;
-        let mut max_x = 0;
-        let mut max_y = 0;
-        let mut max_z = 0;
-        for (k, _) in &tiles {
-            min_x = std::cmp::min(min_x, k[0]);
-            min_y = std::cmp::min(min_y, k[1]);
-            min_z = std::cmp::min(min_z, k[2]);
-            max_x = std::cmp::max(max_x, k[0]);
-            max_y = std::cmp::max(max_y, k[1]);
-            max_z = std::cmp::max(max_z, k[2]);
-        }
+/// Number of black tiles after 100 days.
+pub fn solve_2(tiles: &Floor) -> usize {
+    let mut floor = SparseAutomaton::new(tiles.iter().copied(), Hex, LifeRule::new(&[2], &[1, 2]));
+    floor.run(100);
+    floor.population()
+}
 
-        let mut new_tiles = HashMap::<Point3<i32>, TileState>::new();
-
-        for z in (min_z - 2)..(max_z + 2) {
-            for y in (min_y - 2)..(max_y + 2) {
-                for x in (min_x - 2)..(max_x + 2) {
-                    let p = Point3::new(x, y, z);
-                    let black_neighbors = vec![
-                        Direction::East,
-                        Direction::SouthEast,
-                        Direction::SouthWest,
-                        Direction::West,
-                        Direction::NorthWest,
-                        Direction::NorthEast,
-                    ]
-                    .iter()
-                    .fold(0, |acc, d| match tiles.get(&(p + d.offset())) {
-                        Some(v) => {
-                            if *v == TileState::Black {
-                                acc + 1
-                            } else {
-                                acc
-                            }
-                        }
-                        None => acc,
-                    });
-
-                    let p_state = tiles.get(&p);
-                    if p_state.is_some() && *p_state.unwrap() == TileState::Black{
-                        if black_neighbors == 1 || black_neighbors == 2{
-                            new_tiles.insert(p, TileState::Black);
-                        }
-                    }
-                    else{
-                        if black_neighbors == 2{
-                            new_tiles.insert(p, TileState::Black);
-                        }
-                    }
-                }
-            }
-        }
+pub struct Day24;
 
-        tiles = new_tiles;
+impl Solution for Day24 {
+    type Input = Floor;
 
-        // println!("At {}: {}",i+1, tiles.iter().fold(0, |acc, (_, v)| {
-        //     acc + if *v == TileState::Black { 1 } else { 0 }
-        // }));
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Self::Input, ParseError> {
+        init_tiles(common::reader::lines(input)?.parse(input, path_from_string))
+    }
+
+    fn part1(&self, tiles: &Self::Input) -> Result<Answer, Error> {
+        Ok(solve_1(tiles).into())
+    }
+
+    fn part2(&self, tiles: &Self::Input) -> Result<Answer, Error> {
+        Ok(solve_2(tiles).into())
     }
-    
-    tiles.iter().fold(0, |acc, (_, v)| {
-        acc + if *v == TileState::Black { 1 } else { 0 }
-    })
 }
 
-fn main() {
-    // TODO: Might be sped up by replacing the HashMap with a HashSet of only black tiles
-    println!("Solution 1: {}", solve_1("src/day24/input.txt"));
-    println!("Solution 2: {}", solve_2("src/day24/input.txt"));
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let tiles = Day24
+            .parse(&InputSource::from("src/day24/input_test.txt"))
+            .unwrap();
+        assert_eq!(solve_1(&tiles), 10);
+        assert_eq!(solve_2(&tiles), 2208);
+    }
 }
@@ -0,0 +1,66 @@
+use common::generate::{self, Generated, Generator, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Steps on the hexagonal floor with their offsets in axial coordinates.
+const STEPS: [(&str, (i32, i32)); 6] = [
+    ("e", (1, 0)),
+    ("w", (-1, 0)),
+    ("ne", (1, -1)),
+    ("nw", (0, -1)),
+    ("se", (0, 1)),
+    ("sw", (-1, 1)),
+];
+
+/// Lists of tiles to flip, where about a fifth of the lines lead to an earlier tile again in a
+/// different order. The tiles flipped an odd number of times are counted, the art exhibit is
+/// left to the solvers. The size is the number of lines.
+pub struct Day24Generator;
+
+impl Generator for Day24Generator {
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn default_size(&self) -> usize {
+        500
+    }
+
+    fn generate(&self, rng: &mut StdRng, size: usize) -> Generated {
+        let mut paths = Vec::<Vec<usize>>::with_capacity(size);
+        let mut black = HashSet::new();
+
+        for _ in 0..size {
+            let path = match paths.choose(rng) {
+                Some(earlier) if rng.gen_bool(0.2) => {
+                    let mut path = earlier.clone();
+                    path.shuffle(rng);
+                    path
+                }
+                _ => (0..rng.gen_range(5..=25))
+                    .map(|_| rng.gen_range(0..STEPS.len()))
+                    .collect(),
+            };
+
+            let tile = path.iter().fold((0, 0), |(q, r), &s| {
+                let (dq, dr) = STEPS[s].1;
+                (q + dq, r + dr)
+            });
+            if !black.remove(&tile) {
+                black.insert(tile);
+            }
+            paths.push(path);
+        }
+
+        Generated::new(
+            generate::lines(
+                paths
+                    .iter()
+                    .map(|path| path.iter().map(|&s| STEPS[s].0).collect::<String>()),
+            ),
+            Some(black.len().into()),
+            None,
+        )
+    }
+}
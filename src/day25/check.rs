@@ -0,0 +1,32 @@
+use common::check::{self, Checker, Problem};
+
+/// Public keys: the key of the card and the key of the door, one per line.
+pub struct Day25Checker;
+
+impl Checker for Day25Checker {
+    fn day(&self) -> u32 {
+        25
+    }
+
+    fn check(&self, input: &str) -> Vec<Problem> {
+        let lines = check::lines(input);
+        if lines.is_empty() {
+            return vec![check::empty_input()];
+        }
+
+        let keys = &lines[..lines.len().min(2)];
+        let mut problems = check::check_each(keys, |s| s.number::<u128>().map(|_| ()));
+        if lines.len() < 2 {
+            problems.push(check::missing(
+                &lines,
+                "missing public key of the door",
+                "add the second public key on its own line",
+            ));
+        }
+        problems.extend(check::unexpected_lines(
+            &lines[keys.len()..],
+            "the public keys",
+        ));
+        problems
+    }
+}
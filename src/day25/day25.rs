@@ -1,13 +1,19 @@
-fn transform_subject_number(mut curr: u128, subject_number: u128) -> u128{
+pub mod check;
+pub mod generate;
+
+use common::{Answer, Error, InputSource, ParseError, Solution};
+
+pub fn transform_subject_number(mut curr: u128, subject_number: u128) -> u128 {
     curr *= subject_number;
     curr % 20201227
 }
 
-fn find_loop_size(subject_number: u128, target: u128) -> u128{
+/// Number of transformations of `subject_number` needed to reach `target`.
+pub fn find_loop_size(subject_number: u128, target: u128) -> u128 {
     let mut loop_size = 0u128;
     let mut curr = 1;
     loop {
-        if curr == target{
+        if curr == target {
             return loop_size;
         }
         curr = transform_subject_number(curr, subject_number);
@@ -15,19 +21,46 @@ fn find_loop_size(subject_number: u128, target: u128) -> u128{
     }
 }
 
-fn find_encryption_key(subject_number: u128, public_key_door: u128, public_key_card: u128) -> u128{
+pub fn find_encryption_key(
+    subject_number: u128,
+    public_key_door: u128,
+    public_key_card: u128,
+) -> u128 {
     let loop_size_door = find_loop_size(subject_number, public_key_door);
     let mut key = 1;
-    println!("LSD: {}", loop_size_door);
-    for _ in 0..loop_size_door{
+    for _ in 0..loop_size_door {
         key = transform_subject_number(key, public_key_card);
     }
     key
 }
 
-fn main(){
-   println!("Key: {}", find_encryption_key(7, 6929599, 2448427));
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<u128>;
+
+    fn day(&self) -> u32 {
+        25
+    }
+
+    fn parse(&self, input: &InputSource) -> Result<Vec<u128>, ParseError> {
+        common::try_parse_file_linewise_as::<u128>(input)
+    }
+
+    fn part1(&self, public_keys: &Vec<u128>) -> Result<Answer, Error> {
+        match public_keys.as_slice() {
+            [card, door] => Ok(find_encryption_key(7, *card, *door).into()),
+            _ => Err(Error::invalid_input(format!(
+                "expected 2 public keys, got {}",
+                public_keys.len()
+            ))),
+        }
+    }
 
+    fn part2(&self, _public_keys: &Vec<u128>) -> Result<Answer, Error> {
+        // Day 25 only has a single puzzle
+        Ok(Answer::Empty)
+    }
 }
 
 #[cfg(test)]
@@ -38,4 +71,4 @@ mod test {
     fn test_encryption_key() {
         assert_eq!(find_encryption_key(7, 17807724, 5764801), 14897079);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,36 @@
+use common::generate::{Generated, Generator, StdRng};
+use rand::Rng;
+
+const MODULUS: u64 = 20201227;
+
+/// Public keys of the card and the door for random loop sizes of up to a million, which keeps
+/// brute-forcing them fast. The encryption key follows from the loop sizes. The day has no
+/// second part and the size is ignored, there are always two keys.
+pub struct Day25Generator;
+
+fn transform(subject: u64, loop_size: u64) -> u64 {
+    (0..loop_size).fold(1, |value, _| value * subject % MODULUS)
+}
+
+impl Generator for Day25Generator {
+    fn day(&self) -> u32 {
+        25
+    }
+
+    fn default_size(&self) -> usize {
+        2
+    }
+
+    fn generate(&self, rng: &mut StdRng, _size: usize) -> Generated {
+        let card_loop = rng.gen_range(1_000..1_000_000);
+        let door_loop = rng.gen_range(1_000..1_000_000);
+        let card = transform(7, card_loop);
+        let door = transform(7, door_loop);
+
+        Generated::new(
+            format!("{}\n{}\n", card, door),
+            Some(transform(door, card_loop).into()),
+            None,
+        )
+    }
+}
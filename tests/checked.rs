@@ -0,0 +1,46 @@
+use common::generate;
+use common::input;
+use std::fs;
+use std::path::Path;
+
+/// Checks the inputs and fixtures checked into the repository and generated inputs of every day,
+/// which are all well formed.
+#[test]
+fn test_checked() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let generators = common::generators();
+
+    let mut failures = Vec::new();
+    for checker in common::checkers() {
+        let mut inputs = Vec::new();
+        let dir = input::source_dir(root, checker.year(), checker.day());
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                inputs.push((
+                    path.display().to_string(),
+                    fs::read_to_string(&path).unwrap(),
+                ));
+            }
+        }
+        let generator = generators
+            .iter()
+            .find(|g| g.year() == checker.year() && g.day() == checker.day())
+            .unwrap();
+        for seed in 0..3 {
+            let generated = generator.generate(&mut generate::rng(seed), generator.default_size());
+            inputs.push((
+                format!("day {:02} seed {}", checker.day(), seed),
+                generated.input,
+            ));
+        }
+
+        for (name, input) in inputs {
+            for problem in checker.check(&input) {
+                failures.push(format!("{}:{} ({})", name, problem, problem.fix));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,29 @@
+use common::examples::{self, Examples};
+use common::InputSource;
+
+/// Runs every fixture listed in examples.toml and compares the parts it has answers for.
+#[test]
+fn test_examples() {
+    let path = examples::default_path();
+    let root = path.parent().unwrap();
+    let solutions = common::registry();
+
+    let mut failures = Vec::new();
+    for example in Examples::open(&path).unwrap().iter() {
+        let solution = solutions
+            .iter()
+            .find(|s| s.year() == example.year && s.day() == example.day)
+            .unwrap();
+        let source = InputSource::File(example.path(root));
+        for (part, expected) in &example.answers {
+            match solution.run_part(&source, *part) {
+                Ok(actual) if actual.to_string() == expected.to_string() => {}
+                result => failures.push(format!(
+                    "day {:02} {} part {}: expected {}, got {:?}",
+                    example.day, example.name, part, expected, result
+                )),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,55 @@
+use common::generate;
+use common::InputSource;
+use std::env;
+use std::fs;
+
+/// Solves generated inputs of every day and compares the parts whose answer is known from the
+/// construction of the input.
+#[test]
+fn test_generated() {
+    let solutions = common::registry();
+    let dir = env::temp_dir().join(format!("aoc_generated_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut failures = Vec::new();
+    for generator in common::generators() {
+        let solution = solutions
+            .iter()
+            .find(|s| s.year() == generator.year() && s.day() == generator.day())
+            .unwrap();
+        // Half the default size keeps the unoptimized test build fast
+        let size = generator.default_size() / 2;
+        for seed in 0..3 {
+            let generated = generator.generate(&mut generate::rng(seed), size);
+            let path = dir.join(format!(
+                "{}_day{:02}_{}.txt",
+                generator.year(),
+                generator.day(),
+                seed
+            ));
+            fs::write(&path, &generated.input).unwrap();
+            let source = InputSource::File(path);
+
+            for part in 1..=2 {
+                let expected = match generated.answer(part) {
+                    Some(expected) => expected,
+                    None => continue,
+                };
+                match solution.run_part(&source, part) {
+                    Ok(actual) if &actual == expected => {}
+                    result => failures.push(format!(
+                        "day {:02} seed {} part {}: expected {}, got {:?}",
+                        generator.day(),
+                        seed,
+                        part,
+                        expected,
+                        result
+                    )),
+                }
+            }
+        }
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
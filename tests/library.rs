@@ -0,0 +1,52 @@
+use common::day08::Program;
+use common::day20::{monster_pattern, Image, Tile};
+use common::day23;
+use common::{Answer, InputSource};
+
+#[test]
+fn test_program() {
+    let mut program = Program::from_file("src/day08/input_test.txt").unwrap();
+    assert!(!program.try_run());
+    assert_eq!(program.accumulator(), 5);
+    assert_eq!(program.try_fix_code(), Ok(()));
+    assert_eq!(program.accumulator(), 8);
+}
+
+#[test]
+fn test_image() {
+    let tiles = common::try_parse_grouped_file("src/day20/input_test.txt", Tile::from_string);
+    let image = Image::from_tiles(tiles.unwrap());
+
+    let mut corners = image.corner_ids();
+    corners.sort_unstable();
+    assert_eq!(corners, vec![1171, 1951, 2971, 3079]);
+
+    let mut unified = image.build_unified().unwrap();
+    unified.orient_to_pattern(&monster_pattern()).unwrap();
+    assert_eq!(unified.grid().count(&'#'), 273);
+}
+
+#[test]
+fn test_ring() {
+    assert_eq!(day23::solve_1(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 10), "92658374");
+}
+
+#[test]
+fn test_registry() {
+    let days = common::registry();
+    let keys = days
+        .iter()
+        .map(|d| (d.year(), d.day()))
+        .collect::<Vec<(u32, u32)>>();
+    assert_eq!(
+        keys[..25],
+        (1..=25).map(|d| (2020, d)).collect::<Vec<_>>()[..]
+    );
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+
+    let day21 = days.iter().find(|d| d.day() == 21).unwrap();
+    assert_eq!(
+        day21.run(&InputSource::from("src/day21/input_test.txt")),
+        Ok((Answer::from(5), Answer::from("mxmxvkd,sqjhc,fvjkl")))
+    );
+}